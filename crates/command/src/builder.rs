@@ -9,6 +9,7 @@ use std::str::FromStr;
 use std::sync::LazyLock;
 
 use anyhow::Context;
+use anyhow::bail;
 use anyhow::ensure;
 use camino::Utf8PathBuf;
 use itertools::Itertools;
@@ -19,18 +20,30 @@ use ron::to_string;
 use uuid::Uuid;
 use walkdir::WalkDir;
 use whimsi_lib::builder::MsiBuilder;
+use whimsi_lib::constants::PRODUCT_CODE_GUID_NAME;
+use whimsi_lib::constants::UPGRADE_CODE_PROPERTY;
 use whimsi_lib::tables::directory::directory_identifier::DirectoryIdentifier;
+use whimsi_lib::tables::environment::environment_variable::EnvironmentVariable;
+use whimsi_lib::tables::ini_file::ini_entry::IniEntry;
+use whimsi_lib::tables::ini_file::ini_file_action::IniFileAction;
+use whimsi_lib::tables::lock_permissions::lock_object::LockObject;
 use whimsi_lib::tables::meta::MetaInformation;
 use whimsi_lib::tables::service_control::event::Event;
+use whimsi_lib::tables::upgrade::major_upgrade::MajorUpgradeOptions;
+use whimsi_lib::types::column::condition::Condition;
 use whimsi_lib::types::column::default_dir::DefaultDir;
 use whimsi_lib::types::column::filename::Filename;
 use whimsi_lib::types::column::formatted::Formatted;
+use whimsi_lib::types::column::guid::Guid;
 use whimsi_lib::types::column::identifier::Identifier;
 use whimsi_lib::types::column::identifier::ToIdentifier;
 use whimsi_lib::types::column::shortcut::Shortcut;
+use whimsi_lib::types::helpers::reproducibility::Reproducibility;
 use whimsi_lib::types::helpers::to_unique_msi_identifier::ToUniqueMsiIdentifier;
 use whimsi_lib::types::properties::system_folder::SystemFolder;
 
+use crate::config::EnvironmentConfigInfo;
+use crate::config::MajorUpgradeConfigInfo;
 use crate::config::MsiConfig;
 use crate::config::Permission;
 use crate::config::ServiceInstallConfigInfo;
@@ -55,6 +68,7 @@ impl Builder {
         path_relativity: &PathRelativity,
         config_path: &Utf8PathBuf,
         output_path: &Utf8PathBuf,
+        seed: Option<&str>,
     ) -> anyhow::Result<()> {
         // Parse the config
         let options = Options::default()
@@ -84,8 +98,14 @@ impl Builder {
             .expect("Current working directory path is not UTF-8 compatible"),
         };
 
+        let reproducibility = match seed {
+            Some(seed) => Some(Reproducibility::from_seed(seed)?),
+            None => Reproducibility::from_env()?,
+        };
+
         // Build the MSI
-        let msi_builder = builder_from_config(&config, &base_path)?;
+        let msi_builder =
+            builder_from_config(&config, &base_path, reproducibility)?;
 
         // Open the output file
         let file = std::fs::File::options()
@@ -104,18 +124,36 @@ impl Builder {
 fn builder_from_config(
     config: &MsiConfig,
     base_path: &Utf8PathBuf,
+    reproducibility: Option<Reproducibility>,
 ) -> anyhow::Result<MsiBuilder> {
-    let properties =
-        generate_missing_properties(&config.summary, &config.properties)?;
+    let properties = generate_missing_properties(
+        &config.summary,
+        &config.properties,
+        config.major_upgrade.as_ref(),
+        reproducibility.as_ref(),
+    )?;
     let meta = MetaInformation::new(
         whimsi_msi::PackageType::Installer,
         config.summary.subject.clone(),
     )
     .with_author(Some(config.summary.author.clone()))
     .with_comments(config.summary.comments.clone());
-    let mut builder = MsiBuilder::default().with_meta(meta);
+    let mut builder = MsiBuilder::default()
+        .with_meta(meta)
+        .with_component_grouping(config.component_grouping);
+    builder.set_reproducibility(reproducibility);
     add_properties(&mut builder, &properties)?;
+    if let Some(major_upgrade) = &config.major_upgrade {
+        add_major_upgrade(&mut builder, major_upgrade)?;
+    }
+    for launch_condition in &config.launch_conditions {
+        builder.add_launch_condition(
+            Condition::from_str(&launch_condition.condition)?,
+            &launch_condition.message,
+        )?;
+    }
     add_paths(&mut builder, base_path, &config.paths, &properties)?;
+    add_registry_files(&mut builder, base_path, &config.registry_files)?;
     add_shortcuts(
         &mut builder,
         base_path,
@@ -124,7 +162,12 @@ fn builder_from_config(
         &config.paths,
     )?;
     add_services(&mut builder, &config.service_installs, &properties)?;
+    add_environment_variables(&mut builder, &config.environment_variables)?;
+    add_ini_files(&mut builder, &config.ini_files, &properties)?;
     add_permissions(&mut builder, &config.permissions, &properties)?;
+    for (target, guid) in config.component_guids.iter().sorted() {
+        builder.pin_component_guid(target, Guid::from_str(guid)?);
+    }
 
     Ok(builder)
 }
@@ -133,6 +176,8 @@ fn builder_from_config(
 fn generate_missing_properties(
     summary: &SummaryConfigInfo,
     properties: &HashMap<String, String>,
+    major_upgrade: Option<&MajorUpgradeConfigInfo>,
+    reproducibility: Option<&Reproducibility>,
 ) -> anyhow::Result<HashMap<String, String>> {
     let mut properties = properties.clone();
     const MANUFACTURER_PROPERTY: &str = "Manufacturer";
@@ -169,9 +214,27 @@ fn generate_missing_properties(
         eprintln!(
             "No `ProductCode` property defined for MSI. Generating one and continuing..."
         );
+        let product_code = match reproducibility {
+            // Products with a different UpgradeCode, version or name must
+            // never share a ProductCode.
+            Some(reproducibility) => reproducibility.guid(
+                PRODUCT_CODE_GUID_NAME,
+                &[
+                    properties
+                        .get(UPGRADE_CODE_PROPERTY)
+                        .map(String::as_str)
+                        .or(major_upgrade
+                            .map(|upgrade| upgrade.upgrade_code.as_str()))
+                        .unwrap_or_default(),
+                    &properties[PRODUCT_VERSION_PROPERTY],
+                    &properties[PRODUCT_NAME_PROPERTY],
+                ],
+            ),
+            None => Uuid::new_v4(),
+        };
         properties.insert(
             PRODUCT_CODE_PROPERTY.to_string(),
-            Uuid::new_v4().braced().to_string(),
+            product_code.braced().to_string().to_uppercase(),
         );
     }
 
@@ -192,19 +255,118 @@ fn add_properties(
     builder: &mut MsiBuilder,
     properties: &HashMap<String, String>,
 ) -> anyhow::Result<()> {
-    for (key, value) in properties {
+    // Sorted so that the Property table is written in the same order on every
+    // build.
+    for (key, value) in properties.iter().sorted() {
         builder.add_property(key, value)?
     }
     Ok(())
 }
 
+fn add_major_upgrade(
+    builder: &mut MsiBuilder,
+    major_upgrade: &MajorUpgradeConfigInfo,
+) -> anyhow::Result<()> {
+    let mut options = MajorUpgradeOptions::default()
+        .with_allow_downgrades(major_upgrade.allow_downgrades)
+        .with_allow_same_version_upgrades(
+            major_upgrade.allow_same_version_upgrades,
+        );
+    if let Some(message) = &major_upgrade.downgrade_error_message {
+        options = options.with_downgrade_error_message(message.clone());
+    }
+    builder.add_major_upgrade(
+        Guid::from_str(&major_upgrade.upgrade_code)?,
+        options,
+    )
+}
+
+fn add_environment_variables(
+    builder: &mut MsiBuilder,
+    environment_variables: &[EnvironmentConfigInfo],
+) -> anyhow::Result<()> {
+    for environment in environment_variables {
+        let mut variable = match &environment.value {
+            Some(value) => EnvironmentVariable::new(&environment.name, value),
+            None => EnvironmentVariable::removal(&environment.name),
+        }
+        .with_placement(environment.placement)
+        .with_scope(environment.scope);
+        if let Some(action) = environment.action {
+            variable = variable.with_action(action);
+        }
+        if let Some(separator) = environment.separator {
+            variable = variable.with_separator(separator);
+        }
+        if environment.permanent {
+            variable = variable.with_remove_on_uninstall(false);
+        }
+        builder.add_environment_variable(variable)?;
+    }
+    Ok(())
+}
+
+fn add_ini_files(
+    builder: &mut MsiBuilder,
+    ini_files: &HashMap<String, HashMap<String, HashMap<String, String>>>,
+    properties: &HashMap<String, String>,
+) -> anyhow::Result<()> {
+    for (path, sections) in ini_files.iter().sorted_by_key(|(path, _)| *path) {
+        let ini_path = PathBuf::from_str(path)?;
+        let Some(file_name) = ini_path.file_name() else {
+            bail!("INI file path {path} has no file name");
+        };
+        let file_name = file_name.to_string_lossy().to_string();
+        let directory = match ini_path
+            .parent()
+            .filter(|parent| !parent.as_os_str().is_empty())
+        {
+            Some(parent) => Some(
+                get_directory_id_of_path(parent, builder, properties)
+                    .with_context(|| {
+                        format!(
+                            "Failed to find the directory of INI file {path}"
+                        )
+                    })?,
+            ),
+            None => None,
+        };
+        // Every entry of a file shares the component of its first entry.
+        let mut component = None;
+        for (section, keys) in sections.iter().sorted_by_key(|(name, _)| *name)
+        {
+            for (key, value) in keys.iter().sorted_by_key(|(name, _)| *name) {
+                let entry = IniEntry::new(
+                    directory.clone(),
+                    file_name.clone(),
+                    section.clone(),
+                    key.clone(),
+                );
+                let ini_file_id = builder.add_ini_entry(
+                    entry,
+                    value,
+                    IniFileAction::AddLine,
+                    component.clone(),
+                )?;
+                if component.is_none() {
+                    component = builder
+                        .ini_file()
+                        .entry_with_id(&ini_file_id)
+                        .map(|dao| dao.component().clone());
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
 fn add_paths(
     builder: &mut MsiBuilder,
     base_path: &Utf8PathBuf,
     paths: &HashMap<Utf8PathBuf, String>,
     properties: &HashMap<String, String>,
 ) -> anyhow::Result<()> {
-    for (source_path, destination_path) in paths {
+    for (source_path, destination_path) in paths.iter().sorted() {
         let source_path = base_path.join(source_path);
         ensure!(
             source_path.exists(),
@@ -473,25 +635,24 @@ fn add_permissions(
     permissions: &HashMap<String, Permission>,
     properties: &HashMap<String, String>,
 ) -> anyhow::Result<()> {
-    for (path, permission) in permissions {
+    for (path, permission) in
+        permissions.iter().sorted_by_key(|(path, _)| *path)
+    {
         // Determine if this is a valid file or directory.
         let last_component = get_last_component(path, properties)
             .unwrap_or_else(|| todo!("Create a real error"));
         let filename = Filename::from_str(&last_component)
             .unwrap_or_else(|_| todo!("Create a real error"));
-        let (lock_object, table) = if let Some(file) =
+        let lock_object = if let Some(file) =
             builder.file().entry_with_name(&filename)
         {
-            (file.file().clone().into(), "File")
+            file.file().clone().into()
         } else if let Some(directory) =
             builder.directory().entry_with_name(&filename.into())
         {
-            // TODO: Add support for directory permissions. These can only be
-            // applied to CreateFolder table listings which is currently not
-            // supported.
-            todo!(
-                "Need to add support for CreateFolder table before this feature can be enabled."
-            );
+            // Directory permissions are applied through the directory's
+            // CreateFolder listing, which the builder adds when missing.
+            LockObject::CreateFolder(directory.directory().clone())
         } else {
             todo!(
                 "Create an error for when the path to set permissions on is not found"
@@ -509,6 +670,23 @@ fn add_permissions(
     Ok(())
 }
 
+fn add_registry_files(
+    builder: &mut MsiBuilder,
+    base_path: &Utf8PathBuf,
+    registry_files: &[Utf8PathBuf],
+) -> anyhow::Result<()> {
+    for registry_file in registry_files {
+        let registry_file = base_path.join(registry_file);
+        ensure!(
+            registry_file.exists(),
+            "Registry file {} doesn't exist",
+            registry_file
+        );
+        builder.add_reg_file(registry_file)?;
+    }
+    Ok(())
+}
+
 fn get_last_component(
     path: &str,
     properties: &HashMap<String, String>,
@@ -31,6 +31,11 @@ pub(crate) enum Commands {
         /// config.
         #[arg(short, long, default_value = "command")]
         relative_to: PathRelativity,
+        /// Build a reproducible MSI, deriving all generated GUIDs from this
+        /// seed and fixing all timestamps. Setting `SOURCE_DATE_EPOCH` also
+        /// enables reproducible builds and sets the timestamp used.
+        #[arg(long)]
+        seed: Option<String>,
     },
     Inspect {
         /// MSI to inspect
@@ -7,6 +7,10 @@ use flexstr::LocalStr;
 use serde::Deserialize;
 use serde_inline_default::serde_inline_default;
 use serde_with::skip_serializing_none;
+use whimsi_lib::tables::component::grouping::ComponentGrouping;
+use whimsi_lib::tables::environment::environment_variable::EnvironmentAction;
+use whimsi_lib::tables::environment::environment_variable::EnvironmentScope;
+use whimsi_lib::tables::environment::environment_variable::EnvironmentValuePlacement;
 use whimsi_lib::tables::lock_permissions::lock_permissions::LockPermissions;
 use whimsi_lib::tables::service_install::error_control::ErrorControl;
 use whimsi_lib::tables::service_install::service_type::ServiceType;
@@ -70,6 +74,53 @@ pub(crate) struct ShortcutConfigInfo {
     pub(crate) icon_path: Option<Utf8PathBuf>,
 }
 
+#[derive(Deserialize)]
+#[serde(rename = "LaunchCondition")]
+pub(crate) struct LaunchConditionConfigInfo {
+    /// Must be true for the installation to continue, e.g.
+    /// `Installed OR VersionNT >= 603`.
+    pub(crate) condition: String,
+    pub(crate) message: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename = "MajorUpgrade")]
+pub(crate) struct MajorUpgradeConfigInfo {
+    pub(crate) upgrade_code: String,
+    #[serde(default)]
+    pub(crate) allow_downgrades: bool,
+    #[serde(default)]
+    pub(crate) allow_same_version_upgrades: bool,
+    /// Shown when a newer version is already installed.
+    #[serde(default)]
+    pub(crate) downgrade_error_message: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename = "Environment")]
+pub(crate) struct EnvironmentConfigInfo {
+    pub(crate) name: String,
+    /// Formatted text, e.g. `[INSTALLDIR]bin`. Leave out to remove the
+    /// variable whatever its value is.
+    #[serde(default)]
+    pub(crate) value: Option<String>,
+    /// Either `Set`, `Create` or `Remove`. Defaults to `Set`, or `Remove`
+    /// when there is no value.
+    #[serde(default)]
+    pub(crate) action: Option<EnvironmentAction>,
+    /// Either `Replace`, `Prepend` or `Append`. Defaults to `Replace`.
+    #[serde(default)]
+    pub(crate) placement: EnvironmentValuePlacement,
+    #[serde(default)]
+    pub(crate) separator: Option<char>,
+    /// Either `User` or `System`. Defaults to `User`.
+    #[serde(default)]
+    pub(crate) scope: EnvironmentScope,
+    /// Keep the change when the product is uninstalled.
+    #[serde(default)]
+    pub(crate) permanent: bool,
+}
+
 #[derive(Deserialize)]
 #[serde(rename = "Msi")]
 pub(crate) struct MsiConfig {
@@ -79,6 +130,32 @@ pub(crate) struct MsiConfig {
     pub(crate) permissions: HashMap<String, Permission>,
     pub(crate) shortcuts: Vec<ShortcutConfigInfo>,
     pub(crate) service_installs: Vec<ServiceInstallConfigInfo>,
+    /// `.reg` files exported by the Windows Registry Editor to import into
+    /// the Registry and RemoveRegistry tables.
+    #[serde(default)]
+    pub(crate) registry_files: Vec<Utf8PathBuf>,
+    /// GUIDs to give components instead of deriving them, keyed by where the
+    /// component's key path is installed, e.g.
+    /// `[ProgramFilesFolder]App\app.exe`.
+    #[serde(default)]
+    pub(crate) component_guids: HashMap<String, String>,
+    /// Either `PerFile` or `PerDirectory`. Defaults to `PerFile`.
+    #[serde(default)]
+    pub(crate) component_grouping: ComponentGrouping,
+    /// Replace installed versions that share the upgrade code instead of
+    /// installing next to them.
+    #[serde(default)]
+    pub(crate) major_upgrade: Option<MajorUpgradeConfigInfo>,
+    #[serde(default)]
+    pub(crate) launch_conditions: Vec<LaunchConditionConfigInfo>,
+    #[serde(default)]
+    pub(crate) environment_variables: Vec<EnvironmentConfigInfo>,
+    /// Entries to write to INI files, keyed by the path of the file, e.g.
+    /// `[InstallDir]/legacy.ini`, then by section and then by key. A path
+    /// without a directory is in the Windows folder.
+    #[serde(default)]
+    pub(crate) ini_files:
+        HashMap<String, HashMap<String, HashMap<String, String>>>,
 }
 
 #[cfg(test)]
@@ -44,8 +44,13 @@ fn main() -> Result<()> {
 
     info!("Running WHIMSI...");
     match args.command {
-        Commands::Build { config, output, relative_to } => {
-            Builder::build_from_config(&relative_to, &config, &output)?
+        Commands::Build { config, output, relative_to, seed } => {
+            Builder::build_from_config(
+                &relative_to,
+                &config,
+                &output,
+                seed.as_deref(),
+            )?
         }
         Commands::Inspect { input_file, list_args } => {
             let output = inspector::inspect(&input_file, list_args)?;
@@ -1,15 +1,17 @@
-use std::cell::RefCell;
+use std::collections::HashMap;
+use std::collections::HashSet;
 use std::fs::File;
 use std::io::Read;
 use std::io::Seek;
 use std::io::Write;
 use std::path::Path;
 use std::path::PathBuf;
-use std::rc::Rc;
 use std::str::FromStr;
 
 use anyhow::Context;
+use anyhow::anyhow;
 use anyhow::bail;
+use anyhow::ensure;
 use getset::Getters;
 use getset::Setters;
 use getset::WithSetters;
@@ -18,8 +20,10 @@ use once_cell::sync::OnceCell;
 use once_cell::unsync::Lazy;
 use rand::distr::Alphanumeric;
 use rand::distr::SampleString;
+use strum::IntoEnumIterator;
 use tracing::debug;
 use tracing::info;
+use tracing::warn;
 use uuid::Uuid;
 use whimsi_msi::Insert;
 use whimsi_msi::Value;
@@ -27,19 +31,47 @@ use whimsi_msi::Value;
 use crate::constants::*;
 use crate::tables::admin_execute_sequence::table::AdminExecuteSequenceTable;
 use crate::tables::admin_ui_sequence::table::AdminUiSequenceTable;
+use crate::tables::advt_execute_sequence::dao::AdvtExecuteSequenceDao;
 use crate::tables::advt_execute_sequence::table::AdvtExecuteSequenceTable;
+use crate::tables::app_search::dao::AppSearchDao;
+use crate::tables::app_search::system_search::SearchResult;
+use crate::tables::app_search::system_search::SystemSearch;
 use crate::tables::app_search::table::AppSearchTable;
+use crate::tables::binary::dao::BinaryDao;
+use crate::tables::binary::table::BinaryIdentifier;
 use crate::tables::binary::table::BinaryTable;
 use crate::tables::builder_list::MsiBuilderList;
 use crate::tables::builder_table::MsiBuilderTable;
+use crate::tables::ccp_search::dao::CcpSearchDao;
+use crate::tables::ccp_search::table::CcpSearchTable;
+use crate::tables::comp_locator::dao::CompLocatorDao;
+use crate::tables::comp_locator::table::CompLocatorTable;
+use crate::tables::component::attributes::ComponentAttributes;
 use crate::tables::component::dao::ComponentDao;
+use crate::tables::component::grouping::ComponentGrouping;
+use crate::tables::component::grouping::ComponentKeyPath;
+use crate::tables::component::grouping::is_versioned;
 use crate::tables::component::table::ComponentIdentifier;
 use crate::tables::component::table::ComponentTable;
+use crate::tables::condition::dao::ConditionDao;
+use crate::tables::condition::table::ConditionTable;
+use crate::tables::create_folder::dao::CreateFolderDao;
+use crate::tables::create_folder::table::CreateFolderTable;
+use crate::tables::custom_action::custom_action_type::CustomActionType;
+use crate::tables::custom_action::dao::CustomActionDao;
 use crate::tables::custom_action::table::CustomActionTable;
 use crate::tables::dao::Dao;
 use crate::tables::directory::dao::DirectoryDao;
 use crate::tables::directory::directory_identifier::DirectoryIdentifier;
 use crate::tables::directory::table::DirectoryTable;
+use crate::tables::dr_locator::dao::DrLocatorDao;
+use crate::tables::dr_locator::table::DrLocatorTable;
+use crate::tables::environment::dao::EnvironmentDao;
+use crate::tables::environment::dao::EnvironmentIdentifier;
+use crate::tables::environment::environment_variable::EnvironmentVariable;
+use crate::tables::environment::table::EnvironmentTable;
+use crate::tables::feature::attributes::FeatureAttributes;
+use crate::tables::feature::dao::FeatureDao;
 use crate::tables::feature::identifier::FeatureIdentifier;
 use crate::tables::feature::table::FeatureTable;
 use crate::tables::feature_components::dao::FeatureComponentsDao;
@@ -49,12 +81,26 @@ use crate::tables::file::table::FileIdentifier;
 use crate::tables::file::table::FileTable;
 use crate::tables::generic_sequence::action_identifier::ActionIdentifier;
 use crate::tables::generic_sequence::dao::GenericSequenceDao;
+use crate::tables::generic_sequence::scheduling::ActionPlacement;
+use crate::tables::generic_sequence::scheduling::RelativeAction;
+use crate::tables::generic_sequence::scheduling::RelativePlacement;
+use crate::tables::generic_sequence::scheduling::SequenceTable;
+use crate::tables::generic_sequence::scheduling::resolve_sequence_numbers;
 use crate::tables::icon::dao::IconDao;
 use crate::tables::icon::table::IconIdentifier;
 use crate::tables::icon::table::IconTable;
 use crate::tables::id_generator_builder_list::IdGeneratorBuilderList;
+use crate::tables::ini_file::dao::IniFileDao;
+use crate::tables::ini_file::dao::IniFileIdentifier;
+use crate::tables::ini_file::ini_entry::IniEntry;
+use crate::tables::ini_file::ini_file_action::IniFileAction;
+use crate::tables::ini_file::ini_file_action::RemoveIniFileAction;
+use crate::tables::ini_file::table::IniFileTable;
+use crate::tables::ini_locator::dao::IniLocatorDao;
+use crate::tables::ini_locator::table::IniLocatorTable;
 use crate::tables::install_execute_sequence::table::InstallExecuteSequenceTable;
 use crate::tables::install_ui_sequence::table::InstallUiSequenceTable;
+use crate::tables::launch_condition::dao::LaunchConditionDao;
 use crate::tables::launch_condition::table::LaunchConditionTable;
 use crate::tables::lock_permissions::dao::LockPermissionsDao;
 use crate::tables::lock_permissions::lock_object::LockObject;
@@ -69,8 +115,26 @@ use crate::tables::msi_file_hash::dao::MsiFileHashDao;
 use crate::tables::msi_file_hash::table::MsiFileHashTable;
 use crate::tables::property::dao::PropertyDao;
 use crate::tables::property::table::PropertyTable;
+use crate::tables::reg_locator::dao::RegLocatorDao;
 use crate::tables::reg_locator::table::RegLocatorTable;
+use crate::tables::registry::dao::RegistryDao;
+use crate::tables::registry::dao::RegistryIdentifier;
+use crate::tables::registry::reg_file::RegFile;
+use crate::tables::registry::reg_file::RegFileEntry;
+use crate::tables::registry::registry_key_action::RegistryKeyAction;
+use crate::tables::registry::registry_root::RegistryRoot;
+use crate::tables::registry::registry_value::RegistryValue;
 use crate::tables::registry::table::RegistryTable;
+use crate::tables::remove_file::dao::RemoveFileDao;
+use crate::tables::remove_file::dao::RemoveFileIdentifier;
+use crate::tables::remove_file::install_mode::RemoveFileInstallMode;
+use crate::tables::remove_file::table::RemoveFileTable;
+use crate::tables::remove_ini_file::dao::RemoveIniFileDao;
+use crate::tables::remove_ini_file::dao::RemoveIniFileIdentifier;
+use crate::tables::remove_ini_file::table::RemoveIniFileTable;
+use crate::tables::remove_registry::dao::RemoveRegistryDao;
+use crate::tables::remove_registry::dao::RemoveRegistryIdentifier;
+use crate::tables::remove_registry::table::RemoveRegistryTable;
 use crate::tables::service_control::dao::ServiceControlDao;
 use crate::tables::service_control::dao::ServiceControlIdentifier;
 use crate::tables::service_control::event::Event;
@@ -85,22 +149,40 @@ use crate::tables::service_install::table::ServiceInstallTable;
 use crate::tables::shortcut::dao::ShortcutDao;
 use crate::tables::shortcut::table::ShortcutIdentifier;
 use crate::tables::shortcut::table::ShortcutTable;
+use crate::tables::signature::table::SignatureIdentifier;
 use crate::tables::signature::table::SignatureTable;
+use crate::tables::upgrade::attributes::UpgradeAttributes;
+use crate::tables::upgrade::dao::UpgradeDao;
+use crate::tables::upgrade::major_upgrade::MajorUpgradeOptions;
+use crate::tables::upgrade::table::UpgradeTable;
+use crate::types::column::condition::Condition;
+use crate::types::column::custom_source::CustomSource;
 use crate::types::column::default_dir::DefaultDir;
 use crate::types::column::filename::Filename;
 use crate::types::column::formatted::Formatted;
+use crate::types::column::formatted::FormattedColumns;
+use crate::types::column::formatted::FormattedSegment;
+use crate::types::column::guid::Guid;
 use crate::types::column::identifier::Identifier;
 use crate::types::column::identifier::ToIdentifier;
+use crate::types::column::reg_path::RegPath;
 use crate::types::column::sequence::Sequence;
 use crate::types::column::shortcut::Shortcut;
+use crate::types::column::version::Version;
 use crate::types::helpers::architecture::MsiArchitecture;
+use crate::types::helpers::binary_info::BinaryData;
+use crate::types::helpers::binary_info::BinaryInfo;
 use crate::types::helpers::cabinet_info::CabinetInfo;
 use crate::types::helpers::cabinets::Cabinets;
 use crate::types::helpers::icon::IconInfo;
 use crate::types::helpers::id_generator::IdGenerator;
+use crate::types::helpers::id_generator::SharedIdentifiers;
 use crate::types::helpers::page_count::PageCount;
+use crate::types::helpers::reproducibility::Reproducibility;
 use crate::types::helpers::security_flag::DocSecurity;
+use crate::types::helpers::to_unique_msi_identifier::ToUniqueMsiIdentifier;
 use crate::types::properties::system_folder::SystemFolder;
+use crate::types::standard_action::AdvtAction;
 use crate::types::standard_action::StandardAction;
 
 /// An in-memory representation of the final MSI to be created.
@@ -116,12 +198,37 @@ pub struct MsiBuilder {
     #[getset(set = "pub")]
     meta: Option<MetaInformation>,
 
+    /// When set, the build fixes every timestamp and derives every generated
+    /// GUID from a seed so that identical inputs produce identical MSIs.
+    #[getset(set = "pub")]
+    reproducibility: Option<Reproducibility>,
+
     /// A list of all identifiers used in this MSI. Used to ensure no duplicate
     /// Identifiers are created.
-    identifiers: Rc<RefCell<Vec<Identifier>>>,
+    identifiers: SharedIdentifiers,
 
     cabinets: Cabinets,
     icon_information: Vec<IconInfo>,
+    binary_information: Vec<BinaryInfo>,
+    /// Actions placed before or after other actions. They are given sequence
+    /// numbers when the package is built.
+    relative_actions: Vec<RelativeAction>,
+    /// The feature that newly created components are added to.
+    current_feature: FeatureIdentifier,
+    /// How `add_file_path` and `add_path_contents` put files into components.
+    component_grouping: ComponentGrouping,
+    /// The components shared by the unversioned files of a directory and
+    /// feature when files are grouped per directory.
+    shared_components:
+        Vec<(DirectoryIdentifier, FeatureIdentifier, ComponentIdentifier)>,
+    /// Key paths chosen for the shared components of a directory.
+    component_key_paths: HashMap<Identifier, ComponentKeyPath>,
+    /// The upgrade code and options of the major upgrade this package
+    /// performs, if any.
+    major_upgrade: Option<(Guid, MajorUpgradeOptions)>,
+    /// GUIDs given to components instead of derived ones, keyed by the lower
+    /// case target of the component's key path.
+    pinned_component_guids: HashMap<String, Guid>,
 
     component: ComponentTable,
     directory: DirectoryTable,
@@ -129,16 +236,22 @@ pub struct MsiBuilder {
     media: MediaTable,
     feature: FeatureTable,
     feature_components: FeatureComponentsTable,
+    condition: ConditionTable,
+    create_folder: CreateFolderTable,
+    remove_file: RemoveFileTable,
     // TODO: Ensure that the following properties are defined:
     // - ProductCode
     // - ProductName
     // - ProductVersion
     // - ProductLanguage
     // - Manufacturer
-    // - UpgradeCode
     // - ALLUSERS
     property: PropertyTable,
     registry: RegistryTable,
+    remove_registry: RemoveRegistryTable,
+    environment: EnvironmentTable,
+    ini_file: IniFileTable,
+    remove_ini_file: RemoveIniFileTable,
     msi_file_hash: MsiFileHashTable,
     admin_execute_sequence: AdminExecuteSequenceTable,
     admin_ui_sequence: AdminUiSequenceTable,
@@ -147,9 +260,14 @@ pub struct MsiBuilder {
     install_ui_sequence: InstallUiSequenceTable,
     signature: SignatureTable,
     launch_condition: LaunchConditionTable,
+    upgrade: UpgradeTable,
     binary: BinaryTable,
     reg_locator: RegLocatorTable,
+    dr_locator: DrLocatorTable,
+    comp_locator: CompLocatorTable,
+    ini_locator: IniLocatorTable,
     app_search: AppSearchTable,
+    ccp_search: CcpSearchTable,
     custom_action: CustomActionTable,
     service_control: ServiceControlTable,
     service_install: ServiceInstallTable,
@@ -164,6 +282,22 @@ impl MsiBuilder {
         self
     }
 
+    pub fn with_reproducibility(
+        mut self,
+        reproducibility: Reproducibility,
+    ) -> Self {
+        self.reproducibility = Some(reproducibility);
+        self
+    }
+
+    pub fn with_component_grouping(
+        mut self,
+        component_grouping: ComponentGrouping,
+    ) -> Self {
+        self.component_grouping = component_grouping;
+        self
+    }
+
     /// Insert a given filesystem path's contents into the MSI for installation.
     ///
     /// If the path leads to a directory, the directory and all contents will be
@@ -248,8 +382,14 @@ impl MsiBuilder {
         let parent = parent.into();
         debug!("Adding path [{:?}] contents to directory [{}]", path, parent);
 
+        // Sort the entries since `read_dir` order depends on the filesystem,
+        // and the order determines the generated identifiers.
         let directory_contents: Vec<std::fs::DirEntry> =
-            std::fs::read_dir(&path)?.try_collect()?;
+            std::fs::read_dir(&path)?
+                .try_collect::<_, Vec<_>, _>()?
+                .into_iter()
+                .sorted_by_key(|entry| entry.file_name())
+                .collect();
         for item in directory_contents {
             let filetype = item.file_type().expect(&format!(
                 "Failed to get file type for file {:?}",
@@ -261,6 +401,11 @@ impl MsiBuilder {
                 self.add_file_path(path, parent.clone())?;
             } else if filetype.is_dir() {
                 let id = self.add_directory_from_path(&path, parent.clone())?;
+                // Nothing else would create a directory without any files
+                // or subdirectories.
+                if std::fs::read_dir(&path)?.next().is_none() {
+                    self.add_empty_directory(id.clone())?;
+                }
                 self.add_path_contents(path, id)?;
             } else {
                 bail!("Create error for nonfile+nondir types")
@@ -334,13 +479,25 @@ impl MsiBuilder {
         parent_id: impl Into<DirectoryIdentifier>,
     ) -> anyhow::Result<()> {
         let path = path.into();
+        let parent_id = parent_id.into();
         debug!("Creating DAOs for {path:?}");
 
+        let versioned = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .is_some_and(is_versioned);
+        let shared = self.component_grouping == ComponentGrouping::PerDirectory
+            && !versioned;
         let file_id = self.file.generate_id();
-        let component_id = self.component.generate_id();
+        let component_id = if shared {
+            self.shared_component(&parent_id)?
+        } else {
+            let component_id = self.component.generate_id();
+            self.add_to_current_feature(&component_id)?;
+            component_id
+        };
         let file_hash_dao = MsiFileHashDao::from_path(file_id.clone(), &path)?;
         self.add_to_tables(file_hash_dao)?;
-        self.add_to_default_feature(&component_id)?;
         let sequence = self.add_to_media(file_id.clone(), path.clone());
         let file_dao = FileDao::install_file_from_path(
             file_id.clone(),
@@ -349,12 +506,56 @@ impl MsiBuilder {
             sequence,
         )?;
         self.add_to_tables(file_dao)?;
-        let component_dao = ComponentDao::new(component_id, parent_id.into())
-            .with_keypath(file_id.to_identifier());
-        self.add_to_tables(component_dao)?;
+        if !shared {
+            let component_dao = ComponentDao::new(component_id, parent_id)
+                .with_keypath(file_id.to_identifier());
+            self.add_to_tables(component_dao)?;
+        }
         Ok(())
     }
 
+    /// Choose the key path of the components shared by the files of
+    /// `directory` when files are grouped per directory.
+    ///
+    /// The choice is checked when the package is built, which fails if
+    /// `directory` has no shared components or the chosen file isn't in one.
+    pub fn set_component_key_path(
+        &mut self,
+        directory: impl Into<DirectoryIdentifier>,
+        key_path: ComponentKeyPath,
+    ) {
+        self.component_key_paths
+            .insert(directory.into().to_identifier(), key_path);
+    }
+
+    /// The component shared by the unversioned files of `directory` in the
+    /// current feature, which is created on first use.
+    fn shared_component(
+        &mut self,
+        directory: &DirectoryIdentifier,
+    ) -> anyhow::Result<ComponentIdentifier> {
+        if let Some((_, _, component)) =
+            self.shared_components.iter().find(|(shared, feature, _)| {
+                shared.to_identifier() == directory.to_identifier()
+                    && *feature == self.current_feature
+            })
+        {
+            return Ok(component.clone());
+        }
+        let component_id = self.component.generate_id();
+        self.add_to_tables(ComponentDao::new(
+            component_id.clone(),
+            directory.clone(),
+        ))?;
+        self.add_to_current_feature(&component_id)?;
+        self.shared_components.push((
+            directory.clone(),
+            self.current_feature.clone(),
+            component_id.clone(),
+        ));
+        Ok(component_id)
+    }
+
     /// Adds the given file to media so it will be installed when the MSI is
     /// run.
     ///
@@ -430,7 +631,7 @@ impl MsiBuilder {
     ) -> anyhow::Result<ShortcutIdentifier> {
         let shortcut_id = self.shortcut.generate_id();
         let component_id = self.component.generate_id();
-        self.add_to_default_feature(&component_id);
+        self.add_to_current_feature(&component_id)?;
         self.add_to_tables(ShortcutDao::new(
             shortcut_id.clone(),
             directory_id,
@@ -455,7 +656,7 @@ impl MsiBuilder {
         let icon_id = self.add_icon(icon_path)?;
         let shortcut_id = self.shortcut.generate_id();
         let component_id = self.component.generate_id();
-        self.add_to_default_feature(&component_id);
+        self.add_to_current_feature(&component_id)?;
         self.add_to_tables(
             ShortcutDao::new(
                 shortcut_id.clone(),
@@ -490,6 +691,31 @@ impl MsiBuilder {
         Ok(icon_id)
     }
 
+    /// Embeds binary data in the MSI, such as a custom action DLL, EXE or
+    /// script, or a UI bitmap.
+    ///
+    /// ## Arguments
+    ///
+    /// - *data* Either the path to a file on the building system, which is
+    ///   read when the MSI is built, or the bytes themselves.
+    ///
+    /// ## Returns
+    /// The identifier of the new `Binary` table entry. Custom actions can
+    /// reference it with `CustomSource::Binary`.
+    pub fn add_binary(
+        &mut self,
+        data: impl Into<BinaryData>,
+    ) -> anyhow::Result<BinaryIdentifier> {
+        let data = data.into();
+        if let BinaryData::Path(path) = &data {
+            ensure!(path.is_file(), "Binary path {path:?} is not a file");
+        }
+        let binary_id = self.binary.generate_id();
+        self.add_to_tables(BinaryDao::new(binary_id.clone()))?;
+        self.binary_information.push(BinaryInfo::new(data, binary_id.clone()));
+        Ok(binary_id)
+    }
+
     pub fn add_service_install(
         &mut self,
         name: Formatted,
@@ -499,7 +725,7 @@ impl MsiBuilder {
     ) -> anyhow::Result<ServiceInstallIdentifier> {
         let service_install_id = self.service_install.generate_id();
         let component_id = self.component.generate_id();
-        self.add_to_default_feature(&component_id);
+        self.add_to_current_feature(&component_id)?;
         self.add_to_tables(ServiceInstallDao::new(
             service_install_id.clone(),
             name,
@@ -519,7 +745,7 @@ impl MsiBuilder {
     ) -> anyhow::Result<ServiceControlIdentifier> {
         let component_id = self.component.generate_id();
         let service_control_id = self.service_control.generate_id();
-        self.add_to_default_feature(&component_id);
+        self.add_to_current_feature(&component_id)?;
         self.add_to_tables(ServiceControlDao::new(
             service_control_id.clone(),
             name,
@@ -530,486 +756,4499 @@ impl MsiBuilder {
         Ok(service_control_id)
     }
 
-    pub fn add_lock_permissions(
+    /// Adds a registry value that is written when the MSI is installed.
+    ///
+    /// The value gets its own component, installed to `TARGETDIR`, whose
+    /// keypath is the new `Registry` table entry. The component is added to
+    /// the current feature.
+    ///
+    /// ## Arguments
+    ///
+    /// - *root* Predefined registry key that `key` is relative to.
+    /// - *key* Path of the registry key, such as `Software\Whimsi`.
+    /// - *name* Name of the value, or `None` to set the key's default value.
+    /// - *value* The value and its registry type.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # use whimsi_lib::builder::MsiBuilder;
+    /// # use whimsi_lib::tables::registry::registry_root::RegistryRoot;
+    /// # use whimsi_lib::tables::registry::registry_value::RegistryValue;
+    /// let mut msi = MsiBuilder::default();
+    /// msi.add_registry_value(
+    ///     RegistryRoot::LocalMachine,
+    ///     r"Software\Whimsi",
+    ///     Some("Version"),
+    ///     RegistryValue::Dword(1),
+    /// )
+    /// .unwrap();
+    ///
+    /// assert_eq!(msi.registry().entries().len(), 1);
+    /// ```
+    pub fn add_registry_value(
         &mut self,
-        lock_object: LockObject,
-        user: Formatted,
-        permission: LockPermissions,
-    ) -> anyhow::Result<()> {
-        self.add_to_tables(LockPermissionsDao::new(
-            lock_object,
-            user,
-            permission,
+        root: RegistryRoot,
+        key: impl ToString,
+        name: Option<&str>,
+        value: RegistryValue,
+    ) -> anyhow::Result<RegistryIdentifier> {
+        let key = RegPath::from_str(&key.to_string())?;
+        let registry_id = self.registry.generate_id();
+        let component_id = self.component.generate_id();
+        self.add_to_tables(RegistryDao::new(
+            registry_id.clone(),
+            root,
+            key,
+            name.map(|name| name.to_string().into()),
+            Some(value),
+            component_id.clone(),
         ))?;
-        Ok(())
+        self.add_registry_component(&component_id, Some(&registry_id))?;
+        Ok(registry_id)
     }
 
-    /// Build the MSI from all information given to MSIBuilder.
-    pub fn build<F: std::io::Read + std::io::Write + std::io::Seek>(
-        self,
-        mut container: F,
-    ) -> anyhow::Result<whimsi_msi::Package<F>> {
-        let Some(ref meta) = self.meta else {
-            bail!("Meta information cannot be blank");
-        };
-        info!("Building MSI");
-
-        // Copy the information from the blank reference MSI to the container.
-        let mut reference_msi =
-            std::io::Cursor::new(include_bytes!("../resources/Schema.msi"));
-        std::io::copy(&mut reference_msi, &mut container);
-        let mut package = whimsi_msi::Package::open(container)?;
-
-        // TODO: Remove after getting everything working
-        let extra_tables = [
-            "ActionText",
-            // "Directory",
-            // "Media",
-            // "File",
-            // "Shortcut",
-            // "CustomAction",
-            // "ServiceControl",
-            "AdvtUISequence",
-            "AppId",
-            "BBControl",
-            "Billboard",
-            "BindImage",
-            "CCPSearch",
-            "CheckBox",
-            "Class",
-            "ComboBox",
-            "CompLocator",
-            "Complus",
-            // "Control",
-            "ControlCondition",
-            "ControlEvent",
-            "Dialog",
-            "DrLocator",
-            "DuplicateFile",
-            "Environment",
-            "EventMapping",
-            "Extension",
-            "FileSFPCatalog",
-            "Font",
-            "IniFile",
-            "IniLocator",
-            "IsolatedComponent",
-            "ListBox",
-            "ListView",
-            "LockPermissions",
-            "MIME",
-            "MoveFile",
-            "MsiAssembly",
-            "MsiAssemblyName",
-            "MsiDigitalCertificate",
-            "MsiDigitalSignature",
-            "MsiPatchHeaders",
-            "ODBCAttribute",
-            "ODBCDataSource",
-            "ODBCDriver",
-            "ODBCSourceAttribute",
-            "ODBCTranslator",
-            "Patch",
-            "PatchPackage",
-            "ProgId",
-            "PublishComponent",
-            "RadioButton",
-            "RemoveIniFile",
-            "RemoveRegistry",
-            "ReserveCost",
-            "SFPCatalog",
-            "SelfReg",
-            "TextStyle",
-            "TypeLib",
-            "UIText",
-            "Verb",
-        ];
-        for table in extra_tables {
-            package.drop_table(table);
-        }
+    /// Adds a registry key, without a value, that is created and/or deleted
+    /// with its component.
+    ///
+    /// The key gets its own component, installed to `TARGETDIR`, that is
+    /// added to the current feature.
+    ///
+    /// ## Arguments
+    ///
+    /// - *root* Predefined registry key that `key` is relative to.
+    /// - *key* Path of the registry key, such as `Software\Whimsi`.
+    /// - *action* Whether the key is created on install, deleted with all of
+    ///   its subkeys on uninstall, or both.
+    pub fn add_registry_key(
+        &mut self,
+        root: RegistryRoot,
+        key: impl ToString,
+        action: RegistryKeyAction,
+    ) -> anyhow::Result<RegistryIdentifier> {
+        let key = RegPath::from_str(&key.to_string())?;
+        let registry_id = self.registry.generate_id();
+        let component_id = self.component.generate_id();
+        self.add_to_tables(RegistryDao::new(
+            registry_id.clone(),
+            root,
+            key,
+            Some(action.into()),
+            None,
+            component_id.clone(),
+        ))?;
+        self.add_registry_component(&component_id, None)?;
+        Ok(registry_id)
+    }
 
-        // let mut package = whimsi_msi::Package::create(
-        //     whimsi_msi::PackageType::Installer,
-        //     container,
-        // )?;
-        self.write_meta_info_to_package(&mut package, meta)?;
-        self.write_tables_to_package(&mut package)?;
-        self.write_icons_to_package(&mut package)?;
-        self.write_cabinets_to_package(&mut package)?;
+    /// Removes a registry key, along with all of its values and subkeys, when
+    /// the MSI is installed. Useful for cleaning up keys left behind by older
+    /// versions of a product.
+    ///
+    /// ## Arguments
+    ///
+    /// - *root* Predefined registry key that `key` is relative to.
+    /// - *key* Path of the registry key to remove.
+    /// - *component* Component the removal belongs to. When `None`, a new
+    ///   component is added to `TARGETDIR` in the current feature.
+    pub fn add_registry_key_removal(
+        &mut self,
+        root: RegistryRoot,
+        key: impl ToString,
+        component: Option<ComponentIdentifier>,
+    ) -> anyhow::Result<RemoveRegistryIdentifier> {
+        self.add_registry_removal(
+            root,
+            key,
+            Some(REMOVE_REGISTRY_KEY_NAME),
+            component,
+        )
+    }
 
-        info!("Finished building MSI");
-        Ok(package)
+    /// Removes a registry value when the MSI is installed.
+    ///
+    /// ## Arguments
+    ///
+    /// - *root* Predefined registry key that `key` is relative to.
+    /// - *key* Path of the registry key that holds the value.
+    /// - *name* Name of the value, or `None` to remove the key's default
+    ///   value.
+    /// - *component* Component the removal belongs to. When `None`, a new
+    ///   component is added to `TARGETDIR` in the current feature.
+    pub fn add_registry_value_removal(
+        &mut self,
+        root: RegistryRoot,
+        key: impl ToString,
+        name: Option<&str>,
+        component: Option<ComponentIdentifier>,
+    ) -> anyhow::Result<RemoveRegistryIdentifier> {
+        ensure!(
+            name != Some(REMOVE_REGISTRY_KEY_NAME),
+            "Registry value cannot be named [{REMOVE_REGISTRY_KEY_NAME}]. Use `add_registry_key_removal` to remove a whole key."
+        );
+        self.add_registry_removal(root, key, name, component)
     }
 
-    pub(crate) fn write_meta_info_to_package<
-        F: std::io::Read + std::io::Write + std::io::Seek,
-    >(
-        &self,
-        package: &mut whimsi_msi::Package<F>,
-        meta: &MetaInformation,
-    ) -> anyhow::Result<()> {
-        let package_type = package.package_type();
-        package.set_database_codepage(whimsi_msi::CodePage::Windows1252);
-        let summary_info = package.summary_info_mut();
-        summary_info.set_codepage(whimsi_msi::CodePage::Windows1252);
-        // TODO: Ensure that `subject` is the same as `ProductName` in the
-        // `Property` table as specified [here](https://learn.microsoft.com/en-us/windows/win32/msi/subject-summary)
-        summary_info.set_subject(meta.subject());
+    fn add_registry_removal(
+        &mut self,
+        root: RegistryRoot,
+        key: impl ToString,
+        name: Option<&str>,
+        component: Option<ComponentIdentifier>,
+    ) -> anyhow::Result<RemoveRegistryIdentifier> {
+        let key = RegPath::from_str(&key.to_string())?;
+        let component_id = match component {
+            Some(component_id) => {
+                ensure!(
+                    self.has_component(&component_id),
+                    "Component [{component_id}] does not exist"
+                );
+                self.schedule_standard_action(
+                    StandardAction::RemoveRegistryValues,
+                )?;
+                component_id
+            }
+            None => {
+                let component_id = self.component.generate_id();
+                self.add_registry_component(&component_id, None)?;
+                component_id
+            }
+        };
+        let remove_registry_id = self.remove_registry.generate_id();
+        self.add_to_tables(RemoveRegistryDao::new(
+            remove_registry_id.clone(),
+            root,
+            key,
+            name.map(|name| name.to_string().into()),
+            component_id,
+        ))?;
+        Ok(remove_registry_id)
+    }
 
-        if let Some(author) = meta.author() {
-            summary_info.set_author(author);
-        }
-        summary_info.set_languages(meta.languages());
-        if let Some(arch) = meta.architecture() {
-            summary_info.set_arch(arch.to_string());
+    /// Imports a `.reg` file exported by the Windows Registry Editor.
+    ///
+    /// Keys and values in the file are written to the `Registry` table and
+    /// deletions (`[-HKEY_...]` and `"Name"=-`) are written to the
+    /// `RemoveRegistry` table. Names and string data are escaped so they are
+    /// written exactly as they appear in the file.
+    ///
+    /// Entries are grouped into one component per registry root, so per-user
+    /// and per-machine data never share a component. Each component is added
+    /// to the current feature and its keypath is the first value under its
+    /// root.
+    ///
+    /// ## Arguments
+    ///
+    /// - *path* Path to the `.reg` file on the building system.
+    ///
+    /// ## Returns
+    /// The identifiers of the components that were created for the file, in
+    /// the order their roots first appear in it.
+    pub fn add_reg_file(
+        &mut self,
+        path: impl AsRef<Path>,
+    ) -> anyhow::Result<Vec<ComponentIdentifier>> {
+        let reg_file = RegFile::from_path(path)?;
+        let mut roots = Vec::new();
+        for entry in reg_file.entries() {
+            if !roots.contains(&entry.root()) {
+                roots.push(entry.root());
+            }
         }
-        if let Some(comments) = meta.comments() {
-            summary_info.set_comments(comments);
+        let mut component_ids = Vec::new();
+        for root in roots {
+            let component_id = self.component.generate_id();
+            let mut keypath = None;
+            for entry in reg_file.entries() {
+                if entry.root() != root {
+                    continue;
+                }
+                match entry.clone() {
+                    RegFileEntry::Key { root, key } => {
+                        let has_values = reg_file.entries().iter().any(|other| {
+                            matches!(other, RegFileEntry::Value { root: r, key: k, .. } if *r == root && *k == key)
+                        });
+                        if has_values {
+                            // Writing a value creates its key.
+                            continue;
+                        }
+                        let registry_id = self.registry.generate_id();
+                        self.add_to_tables(RegistryDao::new(
+                            registry_id,
+                            root,
+                            key,
+                            Some(RegistryKeyAction::Create.into()),
+                            None,
+                            component_id.clone(),
+                        ))?;
+                    }
+                    RegFileEntry::Value { root, key, name, value } => {
+                        let registry_id = self.registry.generate_id();
+                        self.add_to_tables(RegistryDao::new(
+                            registry_id.clone(),
+                            root,
+                            key,
+                            name.as_deref().map(Formatted::escaped),
+                            Some(value.escaped()),
+                            component_id.clone(),
+                        ))?;
+                        keypath.get_or_insert(registry_id);
+                    }
+                    RegFileEntry::DeleteKey { root, key } => {
+                        let remove_registry_id =
+                            self.remove_registry.generate_id();
+                        self.add_to_tables(RemoveRegistryDao::new(
+                            remove_registry_id,
+                            root,
+                            key,
+                            Some(REMOVE_REGISTRY_KEY_NAME.to_string().into()),
+                            component_id.clone(),
+                        ))?;
+                    }
+                    RegFileEntry::DeleteValue { root, key, name } => {
+                        let remove_registry_id =
+                            self.remove_registry.generate_id();
+                        self.add_to_tables(RemoveRegistryDao::new(
+                            remove_registry_id,
+                            root,
+                            key,
+                            name.as_deref().map(Formatted::escaped),
+                            component_id.clone(),
+                        ))?;
+                    }
+                }
+            }
+            self.add_registry_component(&component_id, keypath.as_ref())?;
+            component_ids.push(component_id);
         }
-        // TODO: Change this after testing. Just trying to make everything
-        // exactly the same.
-        summary_info.set_creating_application("msitools 0.106");
-        summary_info.set_creation_time_to_now();
-        summary_info.set_last_save_time_to_now();
-        summary_info.set_keywords(meta.keywords());
-        summary_info.set_uuid(Uuid::new_v4());
-        summary_info.set_word_count(2);
-        // TODO: Determine if older versions should be supported.
-        // Only support versions after 5.0
-        summary_info.set_page_count(PageCount::_5_0 as i32);
-        summary_info.set_doc_security(DocSecurity::from_package_type(
-            &package_type,
-        ) as i32);
-        Ok(())
+        Ok(component_ids)
     }
 
-    /// Just writes the information stored in each of the table properties to
-    /// the package tables.
-    ///
-    /// Information is written based on a predetermined order so that
-    /// information that doesn't reference other table information is
-    /// written first.
-    pub(crate) fn write_tables_to_package<
-        F: std::io::Read + std::io::Write + std::io::Seek,
-    >(
-        &self,
-        package: &mut whimsi_msi::Package<F>,
+    /// Adds the component that owns `Registry` and `RemoveRegistry` entries
+    /// to `TARGETDIR` and the current feature, and schedules the actions that
+    /// process those tables.
+    fn add_registry_component(
+        &mut self,
+        component_id: &ComponentIdentifier,
+        keypath: Option<&RegistryIdentifier>,
     ) -> anyhow::Result<()> {
-        info!("Writing tables to package");
-        self.directory.write_to_package(package)?;
-        // NOTE: The order in which the tables are written to matters. WTF.
-        self.component.write_to_package(package)?;
-        self.file.write_to_package(package)?;
-        self.media.write_to_package(package)?;
-        self.feature.write_to_package(package)?;
-        self.feature_components.write_to_package(package)?;
-        self.property.write_to_package(package)?;
-        self.registry.write_to_package(package)?;
-        self.msi_file_hash.write_to_package(package)?;
-        // If this isn't the first sequence table to be filled, it is corrupted
-        // for some reason?
-        self.admin_execute_sequence.write_to_package(package)?;
-        self.admin_ui_sequence.write_to_package(package)?;
-        self.advt_execute_sequence.write_to_package(package)?;
-        self.install_execute_sequence.write_to_package(package)?;
-        self.install_ui_sequence.write_to_package(package)?;
-        self.service_control.write_to_package(package)?;
-        self.service_install.write_to_package(package)?;
-        // WARN: LockPermissions table is causing the MSI to be
-        // unreadable. `reference` example installs just fine with it commented
-        // out. Seems like the command `example.ron` for the commandline
-        // fails to install though even with it and everything from
-        // service_control down to icon commented out. Haven't tried
-        // commenting out any more but my guess is it's a data
-        // formatting issue since there are a different number of directories in
-        // the table between the `reference` example msi and the
-        // `example.ron` msi.
-        //
-        // write_to_package(package)?;
-        self.shortcut.write_to_package(package)?;
-        self.icon.write_to_package(package)?;
+        let mut component = ComponentDao::new(
+            component_id.clone(),
+            SystemFolder::TARGETDIR.to_identifier().into(),
+        );
+        if let Some(keypath) = keypath {
+            component = component
+                .with_attributes(ComponentAttributes::RegistryKeyPath)
+                .with_keypath(keypath.to_identifier());
+        }
+        self.add_to_tables(component)?;
+        self.add_to_current_feature(component_id)?;
+        self.schedule_standard_action(StandardAction::RemoveRegistryValues)?;
+        self.schedule_standard_action(StandardAction::WriteRegistryValues)
+    }
 
-        // NOTE: Empty tables that seem to be required?
-        self.signature.write_to_package(package)?;
-        self.launch_condition.write_to_package(package)?;
-        self.reg_locator.write_to_package(package)?;
-        self.app_search.write_to_package(package)?;
-        self.binary.write_to_package(package)?;
-        self.custom_action.write_to_package(package)?;
-        debug!(
-            "Wrote tables to MSI: {:?}",
-            package.tables().map(|t| t.name()).collect_vec()
+    /// Sets, changes or removes an environment variable when the MSI is
+    /// installed.
+    ///
+    /// The variable gets its own component, installed to `TARGETDIR`, that
+    /// is added to the current feature.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # use whimsi_lib::builder::MsiBuilder;
+    /// # use whimsi_lib::tables::environment::environment_variable::EnvironmentValuePlacement;
+    /// # use whimsi_lib::tables::environment::environment_variable::EnvironmentVariable;
+    /// let mut msi = MsiBuilder::default();
+    /// msi.add_environment_variable(
+    ///     EnvironmentVariable::new("PATH", "[INSTALLDIR]bin")
+    ///         .with_placement(EnvironmentValuePlacement::Append),
+    /// )
+    /// .unwrap();
+    /// ```
+    pub fn add_environment_variable(
+        &mut self,
+        variable: EnvironmentVariable,
+    ) -> anyhow::Result<EnvironmentIdentifier> {
+        let environment_id = self.environment.generate_id();
+        let component_id = self.component.generate_id();
+        self.add_to_tables(EnvironmentDao::new(
+            environment_id.clone(),
+            variable.msi_name()?,
+            variable.msi_value()?.map(Formatted::from),
+            component_id.clone(),
+        ))?;
+        self.add_to_tables(ComponentDao::new(
+            component_id.clone(),
+            SystemFolder::TARGETDIR.to_identifier().into(),
+        ))?;
+        self.add_to_current_feature(&component_id)?;
+        self.schedule_standard_action(
+            StandardAction::RemoveEnvironmentStrings,
+        )?;
+        self.schedule_standard_action(StandardAction::WriteEnvironmentStrings)?;
+        Ok(environment_id)
+    }
+
+    /// Writes an entry to an INI file when the MSI is installed. The entry
+    /// is removed again on uninstall.
+    ///
+    /// ## Arguments
+    ///
+    /// - *entry* The file, section and key to write.
+    /// - *value* Formatted text to write as the value.
+    /// - *action* Whether the entry is replaced, only created when missing,
+    ///   or has `value` appended to it as a tag.
+    /// - *component* The component that owns the entry. When `None` the
+    ///   entry gets its own component, installed to the entry's directory,
+    ///   that is added to the current feature.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # use whimsi_lib::builder::MsiBuilder;
+    /// # use whimsi_lib::tables::ini_file::ini_entry::IniEntry;
+    /// # use whimsi_lib::tables::ini_file::ini_file_action::IniFileAction;
+    /// let mut msi = MsiBuilder::default();
+    /// // `None` is the Windows folder.
+    /// let entry = IniEntry::new(
+    ///     None,
+    ///     "legacy.ini".to_string(),
+    ///     "Paths".to_string(),
+    ///     "Data".to_string(),
+    /// );
+    /// msi.add_ini_entry(entry, "[TARGETDIR]Legacy", IniFileAction::AddLine, None)
+    ///     .unwrap();
+    /// ```
+    pub fn add_ini_entry(
+        &mut self,
+        entry: IniEntry,
+        value: impl ToString,
+        action: IniFileAction,
+        component: Option<ComponentIdentifier>,
+    ) -> anyhow::Result<IniFileIdentifier> {
+        let (filename, dir_property, component_id) =
+            self.ini_entry_component(&entry, component)?;
+        let ini_file_id = self.ini_file.generate_id();
+        self.add_to_tables(IniFileDao::new(
+            ini_file_id.clone(),
+            filename,
+            dir_property,
+            entry.section().clone().into(),
+            entry.key().clone().into(),
+            value.to_string().into(),
+            action,
+            component_id,
+        ))?;
+        Ok(ini_file_id)
+    }
+
+    /// Removes an entry, or a single tag from its value, from an INI file
+    /// when the MSI is installed.
+    ///
+    /// ## Arguments
+    ///
+    /// - *entry* The file, section and key to remove.
+    /// - *action* Whether the whole entry or only a tag is removed.
+    /// - *tag* The tag to remove. Only used with
+    ///   [`RemoveIniFileAction::RemoveTag`].
+    /// - *component* The component that owns the removal. When `None` it gets
+    ///   its own component, installed to the entry's directory, that is added
+    ///   to the current feature.
+    pub fn add_ini_entry_removal(
+        &mut self,
+        entry: IniEntry,
+        action: RemoveIniFileAction,
+        tag: Option<&str>,
+        component: Option<ComponentIdentifier>,
+    ) -> anyhow::Result<RemoveIniFileIdentifier> {
+        ensure!(
+            tag.is_some() == (action == RemoveIniFileAction::RemoveTag),
+            "A tag must be given when, and only when, removing a tag from an INI entry"
         );
-        Ok(())
+        let (filename, dir_property, component_id) =
+            self.ini_entry_component(&entry, component)?;
+        let remove_ini_file_id = self.remove_ini_file.generate_id();
+        self.add_to_tables(RemoveIniFileDao::new(
+            remove_ini_file_id.clone(),
+            filename,
+            dir_property,
+            entry.section().clone().into(),
+            entry.key().clone().into(),
+            tag.map(|tag| tag.to_string().into()),
+            action,
+            component_id,
+        ))?;
+        Ok(remove_ini_file_id)
     }
 
-    pub(crate) fn write_cabinets_to_package<
-        F: std::io::Read + std::io::Write + std::io::Seek,
-    >(
-        &self,
-        package: &mut whimsi_msi::Package<F>,
-    ) -> anyhow::Result<()> {
-        let previous_last_sequence = 1;
-        for media in MsiBuilderTable::entries(&self.media)
-            .iter()
-            .sorted_by_key(|dao| Into::<i32>::into(*dao.last_sequence()))
-        {
-            let Some(cabinet_id) = media.cabinet_id() else {
-                // Ignore media listings that don't represent an internal
-                // cabinet file
-                continue;
-            };
-            let last_sequence = Into::<i32>::into(*media.last_sequence());
-            if last_sequence == 0 {
-                // Skip this cabinet file if no files are to be written to it.
-                continue;
+    /// Validates the location of `entry` and finds or adds the component
+    /// that owns it.
+    fn ini_entry_component(
+        &mut self,
+        entry: &IniEntry,
+        component: Option<ComponentIdentifier>,
+    ) -> anyhow::Result<(Filename, Option<Identifier>, ComponentIdentifier)>
+    {
+        let filename = Filename::parse(entry.file_name())?;
+        ensure!(
+            !entry.section().is_empty() && !entry.key().is_empty(),
+            "INI entries in [{}] need a section and a key",
+            entry.file_name()
+        );
+        let directory = match entry.directory() {
+            Some(directory) => self
+                .existing_directory(directory.clone())
+                .with_context(|| {
+                    format!(
+                        "Invalid location of INI file [{}]",
+                        entry.file_name()
+                    )
+                })?,
+            None => SystemFolder::TARGETDIR.to_identifier().into(),
+        };
+        let component_id = match component {
+            Some(component_id) => {
+                ensure!(
+                    self.has_component(&component_id),
+                    "Component [{component_id}] does not exist"
+                );
+                component_id
             }
-            let cabinet_info = self.cabinets.find_id(&cabinet_id).expect(
-                "Cabinet of ID [{}] could not be found when trying to build it!",
-            );
-            let files = self
-                .file
-                .in_sequence_range(previous_last_sequence, last_sequence);
-            if files.len() == 0 {
-                unreachable!(
-                    "No files found for given cabinet file. This should not happen."
-                )
+            None => {
+                let component_id = self.component.generate_id();
+                self.add_to_tables(ComponentDao::new(
+                    component_id.clone(),
+                    directory,
+                ))?;
+                self.add_to_current_feature(&component_id)?;
+                component_id
             }
+        };
+        self.schedule_standard_action(StandardAction::RemoveIniValues)?;
+        self.schedule_standard_action(StandardAction::WriteIniValues)?;
+        Ok((
+            filename,
+            entry.directory().as_ref().map(ToIdentifier::to_identifier),
+            component_id,
+        ))
+    }
 
-            let mut cabinet_file = self.create_cabinet_file(&cabinet_info)?;
-            // Have to set the poisition of the file reader back to 0 so that it
-            // gets read from the beginning when it gets read again.
-            cabinet_file.rewind();
+    /// Creates `directory` on install even when no files are installed to
+    /// it, and removes it on uninstall if it is empty.
+    ///
+    /// The directory gets its own component, whose key path is the
+    /// directory, that is added to the current feature.
+    pub fn add_empty_directory(
+        &mut self,
+        directory: impl Into<DirectoryIdentifier>,
+    ) -> anyhow::Result<ComponentIdentifier> {
+        let directory = self.existing_directory(directory.into())?;
+        let component_id = self.component.generate_id();
+        self.add_to_tables(ComponentDao::new(
+            component_id.clone(),
+            directory.clone(),
+        ))?;
+        self.add_create_folder(&directory, &component_id)?;
+        self.add_to_current_feature(&component_id)?;
+        Ok(component_id)
+    }
 
-            self.write_cabinet_to_package(
-                &cabinet_info,
-                &mut cabinet_file,
-                package,
-            )?;
+    /// Removes files created at runtime, such as logs or caches, from
+    /// `directory`.
+    ///
+    /// ## Arguments
+    ///
+    /// - *directory* The directory to remove the files from.
+    /// - *pattern* The files to remove, where `*` and `?` are wildcards. When
+    ///   `None` the directory itself is removed, if it is empty by then.
+    /// - *mode* Whether the files are removed on install, uninstall or both.
+    /// - *component* The component that owns the removal. When `None` it gets
+    ///   its own component, installed to `directory`, that is added to the
+    ///   current feature.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # use whimsi_lib::builder::MsiBuilder;
+    /// # use whimsi_lib::tables::remove_file::install_mode::RemoveFileInstallMode;
+    /// # use whimsi_lib::types::properties::system_folder::SystemFolder;
+    /// let mut msi = MsiBuilder::default();
+    /// let logs = msi.add_directory("logs", SystemFolder::ProgramFilesFolder).unwrap();
+    /// msi.add_file_removal(logs, Some("*.log"), RemoveFileInstallMode::OnUninstall, None)
+    ///     .unwrap();
+    /// ```
+    pub fn add_file_removal(
+        &mut self,
+        directory: impl Into<DirectoryIdentifier>,
+        pattern: Option<&str>,
+        mode: RemoveFileInstallMode,
+        component: Option<ComponentIdentifier>,
+    ) -> anyhow::Result<RemoveFileIdentifier> {
+        if let Some(pattern) = pattern {
+            ensure!(
+                !pattern.is_empty()
+                    && !pattern.contains(['/', '\\', '<', '>', ':', '"']),
+                "[{pattern}] is not a valid file name pattern"
+            );
         }
-        Ok(())
+        let directory = self.existing_directory(directory.into())?;
+        let component_id =
+            self.file_removal_component(&directory, component)?;
+        let remove_file_id = self.remove_file.generate_id();
+        self.add_to_tables(RemoveFileDao::new(
+            remove_file_id.clone(),
+            component_id,
+            pattern.map(str::to_string),
+            directory.to_identifier(),
+            mode,
+        ))?;
+        self.schedule_standard_action(StandardAction::RemoveFiles)?;
+        Ok(remove_file_id)
     }
 
-    pub(crate) fn create_cabinet_file(
-        &self,
-        cabinet_info: &CabinetInfo,
-    ) -> anyhow::Result<std::fs::File> {
-        debug!("Creating cabinet file [{}]", cabinet_info.id());
-        let mut cab_builder = cab::CabinetBuilder::new();
-        let mut folder = cab_builder.add_folder(cab::CompressionType::MsZip);
-        cabinet_info.files().iter().for_each(|file| {
-            /// NOTE: From what I can tell attributes only need to be set on
-            /// files in the File table as those attributes
-            /// overwrite the attributes that are set in the cabinet
-            /// file.
-            folder.add_file(file.id().to_string());
-        });
-        let file = tempfile::tempfile().with_context(|| {
-            format!(
-                "Failed to create tempfile for cabinet [{}]",
-                cabinet_info.id()
-            )
-        })?;
-        let mut cab_writer = cab_builder.build(file).with_context(|| {
-            format!(
-                "Failed to create cabinet file writer for cabinet file [{}]",
-                cabinet_info.id()
-            )
-        })?;
-
-        let mut files_iter = cabinet_info.files().iter();
-        while let Some(mut writer) =
-            cab_writer.next_file().expect("Failed to open")
-            && let Some(file) = files_iter.next()
-        {
-            let mut reader = std::fs::File::open(file.path()).unwrap();
-            std::io::copy(&mut reader, &mut writer).unwrap();
+    /// Removes every file in `directory`, and then the directory itself, on
+    /// uninstall. Subdirectories need their own cleanup.
+    pub fn add_directory_cleanup(
+        &mut self,
+        directory: impl Into<DirectoryIdentifier>,
+    ) -> anyhow::Result<ComponentIdentifier> {
+        let directory = self.existing_directory(directory.into())?;
+        let component_id = self.file_removal_component(&directory, None)?;
+        for pattern in [Some("*"), None] {
+            self.add_file_removal(
+                directory.clone(),
+                pattern,
+                RemoveFileInstallMode::OnUninstall,
+                Some(component_id.clone()),
+            )?;
         }
+        Ok(component_id)
+    }
 
-        Ok(cab_writer.finish()?)
+    /// The component that owns a file removal in `directory`. When
+    /// `component` is `None` a new one is installed to `directory` and added
+    /// to the current feature.
+    fn file_removal_component(
+        &mut self,
+        directory: &DirectoryIdentifier,
+        component: Option<ComponentIdentifier>,
+    ) -> anyhow::Result<ComponentIdentifier> {
+        if let Some(component_id) = component {
+            ensure!(
+                self.has_component(&component_id),
+                "Component [{component_id}] does not exist"
+            );
+            return Ok(component_id);
+        }
+        let component_id = self.component.generate_id();
+        self.add_to_tables(ComponentDao::new(
+            component_id.clone(),
+            directory.clone(),
+        ))?;
+        self.add_to_current_feature(&component_id)?;
+        Ok(component_id)
     }
 
-    pub(crate) fn write_cabinet_to_package<
-        F: std::io::Read + std::io::Write + std::io::Seek,
-    >(
-        &self,
-        cabinet_info: &CabinetInfo,
-        cabinet: &mut std::fs::File,
-        package: &mut whimsi_msi::Package<F>,
+    /// Adds a `CreateFolder` entry for `directory` to `component` and
+    /// schedules the actions that create and remove it.
+    fn add_create_folder(
+        &mut self,
+        directory: &DirectoryIdentifier,
+        component: &ComponentIdentifier,
     ) -> anyhow::Result<()> {
-        let cabinet_id = cabinet_info.id();
-        debug!(
-            "Writing cabinet file for cabinet ID [{}] to package",
-            cabinet_id
-        );
-        let mut writer = package
-            .write_stream(&cabinet_id.to_string())
-            .context("Failed to create cabinet stream writier for package")?;
-        std::io::copy(cabinet, &mut writer).with_context(|| {
-            format!("Failed to copy cabinet [{cabinet_id}] to package")
-        })?;
+        self.add_to_tables(CreateFolderDao::new(
+            directory.clone(),
+            component.clone(),
+        ))?;
+        self.schedule_standard_action(StandardAction::RemoveFolders)?;
+        self.schedule_standard_action(StandardAction::CreateFolders)
+    }
 
-        Ok(())
+    /// Checks that `directory` is in the `Directory` table, adding it if it
+    /// is a system folder.
+    fn existing_directory(
+        &mut self,
+        directory: DirectoryIdentifier,
+    ) -> anyhow::Result<DirectoryIdentifier> {
+        if let Ok(system_folder) =
+            SystemFolder::try_from(directory.to_identifier())
+        {
+            // The system folder may already be in the table.
+            let _ = self.add_directory_dao(system_folder.into());
+        } else {
+            ensure!(
+                MsiBuilderList::entries(&self.directory)
+                    .iter()
+                    .any(|dao| *dao.directory() == directory),
+                "Directory [{directory}] does not exist"
+            );
+        }
+        Ok(directory)
     }
 
-    // Icon stream writing information can be found partially
-    // [here](https://learn.microsoft.com/en-us/windows/win32/msi/ole-limitations-on-streams) in
-    // the first limitation listed
-    pub(crate) fn write_icons_to_package<
-        F: std::io::Read + std::io::Write + std::io::Seek,
-    >(
-        &self,
-        package: &mut whimsi_msi::Package<F>,
-    ) -> anyhow::Result<()> {
-        for icon in &self.icon_information {
-            let mut icon_data = File::open(icon.path()).with_context(|| {
-                format!("Failed to open icon at path {:?}", icon.path())
-            })?;
-            let icon_id = icon.identifier().to_string();
-            // Stream name for Icons is defined
-            // [here](https://learn.microsoft.com/en-us/windows/win32/msi/ole-limitations-on-streams) in
-            // in the first limitation listed
-            let stream_name = [self.icon.name(), ".", &icon_id].concat();
+    /// Adds a custom action and schedules it in the `InstallExecuteSequence`
+    /// table.
+    ///
+    /// ## Arguments
+    ///
+    /// - *action* Name of the custom action. Cannot be the name of a standard
+    ///   action.
+    /// - *typ* What the action runs and how it is executed. `source` must
+    ///   reference the table that `typ` reads its source from.
+    /// - *source* The binary, file, directory or property the action uses, if
+    ///   any.
+    /// - *target* The entry point, command line, script or value the action
+    ///   uses, depending on `typ`.
+    /// - *condition* The action is skipped when this evaluates to false.
+    /// - *placement* Position of the action in the `InstallExecuteSequence`
+    ///   table. Deferred, rollback and commit actions must be sequenced
+    ///   between `InstallInitialize` and `InstallFinalize`.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # use std::str::FromStr;
+    /// # use whimsi_lib::builder::MsiBuilder;
+    /// # use whimsi_lib::tables::custom_action::custom_action_type::CustomActionKind;
+    /// # use whimsi_lib::tables::generic_sequence::scheduling::ActionPlacement;
+    /// # use whimsi_lib::tables::custom_action::custom_action_type::CustomActionType;
+    /// # use whimsi_lib::types::column::condition::Condition;
+    /// # use whimsi_lib::types::column::custom_source::CustomSource;
+    /// # use whimsi_lib::types::column::identifier::Identifier;
+    /// let mut msi = MsiBuilder::default();
+    /// msi.add_custom_action(
+    ///     "SetInstallMode",
+    ///     CustomActionType::new(CustomActionKind::SetProperty),
+    ///     Some(CustomSource::Property(Identifier::from_str("MODE").unwrap())),
+    ///     Some("Full".to_string().into()),
+    ///     Some(Condition::from_str("NOT Installed").unwrap()),
+    ///     ActionPlacement::Sequence(1001),
+    /// )
+    /// .unwrap();
+    /// ```
+    pub fn add_custom_action(
+        &mut self,
+        action: &str,
+        typ: CustomActionType,
+        source: Option<CustomSource>,
+        target: Option<Formatted>,
+        condition: Option<Condition>,
+        placement: ActionPlacement,
+    ) -> anyhow::Result<Identifier> {
+        let action_id = Identifier::from_str(action)?;
+        ensure!(
+            StandardAction::from_str(action).is_err(),
+            "Custom action [{action}] has the same name as a standard action"
+        );
+        typ.validate(source.as_ref())?;
+        match &source {
+            Some(CustomSource::Binary(binary)) => ensure!(
+                self.binary.entries().iter().any(|dao| dao.name() == binary),
+                "Binary [{binary}] used by custom action [{action}] does not exist"
+            ),
+            Some(CustomSource::File(file)) => ensure!(
+                self.file.entries().iter().any(|dao| dao.file() == file),
+                "File [{file}] used by custom action [{action}] does not exist"
+            ),
+            Some(CustomSource::Directory(directory)) => ensure!(
+                self.directory
+                    .entries()
+                    .iter()
+                    .any(|dao| dao.directory() == directory),
+                "Directory [{directory}] used by custom action [{action}] does not exist"
+            ),
+            Some(CustomSource::Property(_)) | None => (),
+        }
+        if let (true, ActionPlacement::Sequence(sequence)) =
+            (typ.is_in_script(), &placement)
+        {
+            ensure_in_script_sequence(&action_id, *sequence)?;
+        }
 
-            let mut writer = package
-                .write_stream(&stream_name)
-                .context("Failed to create icon stream writer for package")?;
-            std::io::copy(&mut icon_data, &mut writer).with_context(|| {
-                format!(
-                    "Failed to copy icon stream data [{icon_id}] to package"
-                )
-            })?;
+        self.add_to_tables(CustomActionDao::new(
+            action_id.clone(),
+            typ,
+            source,
+            target,
+        ))?;
+        if let Err(error) = self.schedule_action(
+            SequenceTable::InstallExecute,
+            ActionIdentifier::CustomAction(action_id.clone()),
+            condition,
+            placement,
+        ) {
+            // Don't leave behind a custom action that is never run.
+            MsiBuilderList::entries_mut(&mut self.custom_action)
+                .retain(|dao| *dao.action() != action_id);
+            return Err(error);
         }
-        Ok(())
+        Ok(action_id)
     }
 
-    fn add_to_default_feature(
+    /// Schedules an action in one of the sequence tables.
+    ///
+    /// Actions placed before or after another action are given their
+    /// sequence numbers when the package is built, so the action they are
+    /// placed relative to can be scheduled afterwards.
+    ///
+    /// ## Arguments
+    ///
+    /// - *table* The sequence table to schedule the action in.
+    /// - *action* A standard action, or a custom action that was already
+    ///   added with `add_custom_action`.
+    /// - *condition* The action is skipped when this evaluates to false.
+    /// - *placement* Where in the table the action runs.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # use whimsi_lib::builder::MsiBuilder;
+    /// # use whimsi_lib::tables::generic_sequence::scheduling::ActionPlacement;
+    /// # use whimsi_lib::tables::generic_sequence::scheduling::SequenceTable;
+    /// # use whimsi_lib::types::standard_action::StandardAction;
+    /// let mut msi = MsiBuilder::default();
+    /// msi.schedule_action(
+    ///     SequenceTable::InstallExecute,
+    ///     StandardAction::RemoveExistingProducts,
+    ///     None,
+    ///     ActionPlacement::After(StandardAction::InstallValidate.into()),
+    /// )
+    /// .unwrap();
+    /// ```
+    pub fn schedule_action(
         &mut self,
-        component_id: &ComponentIdentifier,
+        table: SequenceTable,
+        action: impl Into<ActionIdentifier>,
+        condition: Option<Condition>,
+        placement: ActionPlacement,
     ) -> anyhow::Result<()> {
-        // Get the default feature DAO.
-        let Some(default) = self.feature.get_default_feature() else {
-            bail!("No default feature could be found");
+        let action = action.into();
+        if let ActionIdentifier::CustomAction(custom) = &action {
+            ensure!(
+                MsiBuilderList::entries(&self.custom_action)
+                    .iter()
+                    .any(|dao| dao.action() == custom),
+                "Custom action [{custom}] does not exist"
+            );
+        }
+        let name = action.to_string();
+        ensure!(
+            !self.is_scheduled(table, &name),
+            "Action [{name}] is already scheduled in {table}"
+        );
+
+        let (anchor, before) = match placement {
+            ActionPlacement::Sequence(sequence) => {
+                return self
+                    .add_sequence_entry(table, action, condition, sequence);
+            }
+            ActionPlacement::Before(anchor) => (anchor, true),
+            ActionPlacement::After(anchor) => (anchor, false),
         };
-        // Add the component to the default feature.
-        self.add_component_to_feature(&default.feature().clone(), component_id)
+        self.relative_actions.push(RelativeAction {
+            table,
+            action,
+            condition,
+            placement: RelativePlacement {
+                action: name,
+                anchor: anchor.to_string(),
+                before,
+            },
+        });
+        Ok(())
     }
 
-    fn add_component_to_feature(
+    /// Secures a file, registry key, service or directory.
+    ///
+    /// Directories are secured through their `CreateFolder` entry, which is
+    /// added with [`MsiBuilder::add_empty_directory`] when the directory
+    /// doesn't have one yet.
+    pub fn add_lock_permissions(
         &mut self,
-        feature_id: &FeatureIdentifier,
-        component_id: &ComponentIdentifier,
+        lock_object: LockObject,
+        user: Formatted,
+        permission: LockPermissions,
     ) -> anyhow::Result<()> {
-        self.feature_components.add(FeatureComponentsDao::new(
+        if let LockObject::CreateFolder(directory) = &lock_object
+            && !MsiBuilderList::entries(&self.create_folder).iter().any(|dao| {
+                dao.directory().to_identifier() == directory.to_identifier()
+            })
+        {
+            self.add_empty_directory(directory.clone())?;
+        }
+        self.add_to_tables(LockPermissionsDao::new(
+            lock_object,
+            user,
+            permission,
+        ))?;
+        Ok(())
+    }
+
+    /// Add a feature that components can be installed as part of.
+    ///
+    /// ## Arguments
+    ///
+    /// * `parent` - The feature this one is shown under in the selection
+    ///   tree. Top level features have no parent.
+    /// * `title` - The short name shown in the selection tree.
+    /// * `description` - The longer text shown when the feature is selected.
+    /// * `level` - The install level of the feature. It is installed when
+    ///   this is at most the `INSTALLLEVEL` property, and never when it is
+    ///   `0`.
+    /// * `display` - Where the feature is shown in the selection tree. Odd
+    ///   numbers are shown expanded, even numbers collapsed and `None` or `0`
+    ///   hides the feature.
+    /// * `attributes` - Options for how the feature is installed.
+    /// * `directory` - A directory the user can change the location of when
+    ///   selecting this feature. It must be a system folder or have an upper
+    ///   case identifier.
+    ///
+    /// ## Returns
+    ///
+    /// The identifier of the new feature. Pass it to
+    /// [`MsiBuilder::set_current_feature`] to add components to it.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # use whimsi_lib::builder::MsiBuilder;
+    /// # use whimsi_lib::tables::feature::attributes::FeatureAttributes;
+    /// let mut builder = MsiBuilder::default();
+    /// let docs = builder
+    ///     .add_feature(
+    ///         None,
+    ///         "Documentation",
+    ///         Some("Manuals and examples"),
+    ///         1,
+    ///         Some(2),
+    ///         FeatureAttributes::default(),
+    ///         None,
+    ///     )
+    ///     .unwrap();
+    /// builder.set_current_feature(&docs).unwrap();
+    /// ```
+    #[allow(clippy::too_many_arguments)]
+    pub fn add_feature(
+        &mut self,
+        parent: Option<&FeatureIdentifier>,
+        title: impl ToString,
+        description: Option<&str>,
+        level: i16,
+        display: Option<i16>,
+        attributes: FeatureAttributes,
+        directory: Option<DirectoryIdentifier>,
+    ) -> anyhow::Result<FeatureIdentifier> {
+        if let Some(parent) = parent {
+            ensure!(
+                self.has_feature(parent),
+                "Parent feature [{parent}] does not exist"
+            );
+        }
+        ensure!(level >= 0, "Feature level [{level}] cannot be negative");
+        let title = title.to_string();
+        ensure!(
+            title.len() <= TITLE_MAX_LEN,
+            "Feature title [{title}] is longer than {TITLE_MAX_LEN} characters"
+        );
+        if let Some(description) = description {
+            ensure!(
+                description.len() <= DESCRIPTION_MAX_LEN,
+                "Feature description is longer than {DESCRIPTION_MAX_LEN} characters"
+            );
+        }
+        let directory = directory.map(|directory| directory.to_identifier());
+        if let Some(directory) = &directory {
+            if let Ok(system_folder) = SystemFolder::try_from(directory.clone())
+            {
+                // The system folder may already be in the table.
+                let _ = self.add_directory_dao(system_folder.into());
+            } else {
+                // Only public properties can be changed by the user.
+                ensure!(
+                    directory.to_string()
+                        == directory.to_string().to_uppercase(),
+                    "Directory [{directory}] for feature [{title}] must be upper case so it can be configured"
+                );
+                ensure!(
+                    MsiBuilderList::entries(&self.directory).iter().any(
+                        |dao| dao.directory().to_identifier() == *directory
+                    ),
+                    "Directory [{directory}] for feature [{title}] does not exist"
+                );
+            }
+        }
+
+        let feature_id = self.feature.generate_id();
+        self.add_to_tables(
+            FeatureDao::new(feature_id.clone(), title)
+                .with_feature_parent(parent.cloned())
+                .with_description(description.map(str::to_string))
+                .with_level(level)
+                .with_display(display)
+                .with_directory(directory)
+                .with_attributes(attributes.bits()),
+        )?;
+        Ok(feature_id)
+    }
+
+    /// Set the feature that components created from now on are added to.
+    ///
+    /// This covers the components made by [`MsiBuilder::add_path_contents`],
+    /// [`MsiBuilder::add_file_path`], [`MsiBuilder::add_shortcut`], the
+    /// service methods and the registry methods. Components are added to the
+    /// default feature until this is called.
+    pub fn set_current_feature(
+        &mut self,
+        feature: &FeatureIdentifier,
+    ) -> anyhow::Result<()> {
+        ensure!(
+            self.has_feature(feature),
+            "Feature [{feature}] does not exist"
+        );
+        self.current_feature = feature.clone();
+        Ok(())
+    }
+
+    /// Change the install level of a feature to `level` when `condition` is
+    /// true. A level of `0` keeps the feature from being installed.
+    pub fn add_feature_condition(
+        &mut self,
+        feature: &FeatureIdentifier,
+        level: i16,
+        condition: Condition,
+    ) -> anyhow::Result<()> {
+        ensure!(
+            self.has_feature(feature),
+            "Feature [{feature}] does not exist"
+        );
+        ensure!(level >= 0, "Feature level [{level}] cannot be negative");
+        self.add_to_tables(ConditionDao::new(
+            feature.clone(),
+            level,
+            Some(condition),
+        ))
+    }
+
+    /// Stop the installation with `message` unless `condition` is true.
+    ///
+    /// Conditions are checked in both the UI and execute sequences, so they
+    /// also apply to silent installations. Most conditions should include
+    /// `Installed OR` so that they don't block repairs and uninstalls.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # use std::str::FromStr;
+    /// # use whimsi_lib::builder::MsiBuilder;
+    /// # use whimsi_lib::types::column::condition::Condition;
+    /// let mut builder = MsiBuilder::default();
+    /// builder
+    ///     .add_launch_condition(
+    ///         Condition::from_str("Installed OR DOTNETRUNTIME").unwrap(),
+    ///         "[ProductName] requires the .NET runtime.",
+    ///     )
+    ///     .unwrap();
+    /// ```
+    pub fn add_launch_condition(
+        &mut self,
+        condition: Condition,
+        message: impl ToString,
+    ) -> anyhow::Result<()> {
+        self.add_to_tables(LaunchConditionDao::new(
+            condition,
+            Formatted::from(message.to_string()),
+        ))?;
+        for table in [SequenceTable::InstallUi, SequenceTable::InstallExecute] {
+            self.schedule_standard_action_in(
+                table,
+                StandardAction::LaunchConditions,
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Require a version of Windows of at least `version_nt`, as reported by
+    /// the `VersionNT` property. For example `601` is Windows 7 and `603`
+    /// is Windows 8.1 and later.
+    ///
+    /// [*Reference*](https://learn.microsoft.com/en-us/windows/win32/msi/operating-system-property-values)
+    pub fn require_minimum_windows_version(
+        &mut self,
+        version_nt: i16,
+        message: impl ToString,
+    ) -> anyhow::Result<()> {
+        self.add_launch_condition(
+            Condition::from_str(&format!(
+                "Installed OR VersionNT >= {version_nt}"
+            ))?,
+            message,
+        )
+    }
+
+    /// Require a 64-bit version of Windows.
+    pub fn require_64_bit_windows(
+        &mut self,
+        message: impl ToString,
+    ) -> anyhow::Result<()> {
+        self.add_launch_condition(
+            Condition::from_str("Installed OR VersionNT64")?,
+            message,
+        )
+    }
+
+    /// Require the installation to run with administrator privileges.
+    pub fn require_admin_privileges(
+        &mut self,
+        message: impl ToString,
+    ) -> anyhow::Result<()> {
+        self.add_launch_condition(Condition::from_str("Privileged")?, message)
+    }
+
+    /// Set the public property `property` to what `search` finds on the
+    /// system before the installation starts. When nothing is found the
+    /// property keeps its value from the `Property` table, if it has one.
+    ///
+    /// The returned signature can be used as the parent of a
+    /// [`SystemSearch::Directory`] search.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # use whimsi_lib::builder::MsiBuilder;
+    /// # use whimsi_lib::tables::app_search::system_search::FileSignature;
+    /// # use whimsi_lib::tables::app_search::system_search::SearchResult;
+    /// # use whimsi_lib::tables::app_search::system_search::SystemSearch;
+    /// # use whimsi_lib::tables::registry::registry_root::RegistryRoot;
+    /// let mut builder = MsiBuilder::default();
+    /// let install_dir = builder
+    ///     .add_search(
+    ///         "PREVIOUSINSTALLDIR",
+    ///         SystemSearch::Registry {
+    ///             root: RegistryRoot::LocalMachine,
+    ///             key: "Software\\Example".to_string(),
+    ///             name: Some("InstallDir".to_string()),
+    ///             result: SearchResult::Directory,
+    ///             win64: true,
+    ///         },
+    ///     )
+    ///     .unwrap();
+    /// builder
+    ///     .add_search(
+    ///         "EXAMPLEEXE",
+    ///         SystemSearch::Directory {
+    ///             parent: Some(install_dir),
+    ///             path: String::new(),
+    ///             depth: 0,
+    ///             file: Some(FileSignature::new("example.exe")),
+    ///         },
+    ///     )
+    ///     .unwrap();
+    /// ```
+    pub fn add_search(
+        &mut self,
+        property: impl ToString,
+        search: SystemSearch,
+    ) -> anyhow::Result<SignatureIdentifier> {
+        let property = property.to_string();
+        ensure!(
+            property == property.to_uppercase(),
+            "Searches can only set public properties, which are upper case. [{property}] is not"
+        );
+        let signature = self.add_locator(search)?;
+        self.add_to_tables(AppSearchDao::new(
+            Identifier::from_str(&property)?,
+            signature.clone(),
+        ))?;
+        self.add_secure_custom_properties(&[&property])?;
+        for table in [SequenceTable::InstallUi, SequenceTable::InstallExecute] {
+            self.schedule_standard_action_in(table, StandardAction::AppSearch)?;
+        }
+        Ok(signature)
+    }
+
+    /// Look for a qualifying product on the system with `search`, such as an
+    /// earlier version for an upgrade-only package. The `CCPSearch` action
+    /// sets `CCP_SUCCESS` when any of these searches succeeds. When it
+    /// doesn't, the `RMCCPSearch` action repeats the searches on the
+    /// removable drive in `CCP_DRIVE`, if that property is set.
+    ///
+    /// The searches only run on first install. Call
+    /// [`MsiBuilder::add_ccp_launch_condition`] to stop the installation when
+    /// no qualifying product is found.
+    ///
+    /// [*Reference*](https://learn.microsoft.com/en-us/windows/win32/msi/ccpsearch-action)
+    pub fn add_ccp_search(
+        &mut self,
+        search: SystemSearch,
+    ) -> anyhow::Result<SignatureIdentifier> {
+        let signature = self.add_locator(search)?;
+        self.add_to_tables(CcpSearchDao::new(signature.clone()))?;
+        let searches = [
+            (
+                SequenceTable::InstallUi,
+                StandardAction::CcpsSearch,
+                "NOT Installed",
+            ),
+            (
+                SequenceTable::InstallUi,
+                StandardAction::RmccpSearch,
+                &format!(
+                    "NOT Installed AND NOT {CCP_SUCCESS_PROPERTY} AND {CCP_DRIVE_PROPERTY}"
+                ),
+            ),
+            (
+                SequenceTable::InstallExecute,
+                StandardAction::CcpsSearch,
+                "NOT Installed",
+            ),
+        ];
+        for (table, action, condition) in searches {
+            if self.is_scheduled(table, &action.to_string()) {
+                continue;
+            }
+            let Some(sequence) = action.suggested_sequence() else {
+                bail!(
+                    "Standard action [{action}] has no suggested sequence number"
+                );
+            };
+            self.schedule_action(
+                table,
+                action,
+                Some(Condition::from_str(condition)?),
+                ActionPlacement::Sequence(sequence),
+            )?;
+        }
+        Ok(signature)
+    }
+
+    /// Stop the installation with `message` when none of the searches added
+    /// with [`MsiBuilder::add_ccp_search`] finds a qualifying product.
+    ///
+    /// The `LaunchConditions` action is moved after the searches so it sees
+    /// their result.
+    pub fn add_ccp_launch_condition(
+        &mut self,
+        message: impl ToString,
+    ) -> anyhow::Result<()> {
+        ensure!(
+            !self.ccp_search.is_empty(),
+            "Add a CCP search before its launch condition"
+        );
+        self.add_to_tables(LaunchConditionDao::new(
+            Condition::from_str(&format!(
+                "Installed OR {CCP_SUCCESS_PROPERTY}"
+            ))?,
+            Formatted::from(message.to_string()),
+        ))?;
+        let action = StandardAction::LaunchConditions.to_string();
+        MsiBuilderList::entries_mut(&mut self.install_ui_sequence)
+            .retain(|dao| dao.action().to_string() != action);
+        MsiBuilderList::entries_mut(&mut self.install_execute_sequence)
+            .retain(|dao| dao.action().to_string() != action);
+        for (table, anchor) in [
+            (SequenceTable::InstallUi, StandardAction::RmccpSearch),
+            (SequenceTable::InstallExecute, StandardAction::CcpsSearch),
+        ] {
+            if !self.is_scheduled(table, &action) {
+                self.schedule_action(
+                    table,
+                    StandardAction::LaunchConditions,
+                    None,
+                    ActionPlacement::After(anchor.into()),
+                )?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Adds the locator rows of `search`, and its `Signature` row when it
+    /// looks for a file.
+    fn add_locator(
+        &mut self,
+        search: SystemSearch,
+    ) -> anyhow::Result<SignatureIdentifier> {
+        let signature = self.signature.generate_id();
+        let file = match search {
+            SystemSearch::Registry { root, key, name, result, win64 } => {
+                ensure!(
+                    root != RegistryRoot::PerUserOrMachine,
+                    "Registry searches need an explicit root"
+                );
+                let mut typ = result.locator_type();
+                if win64 {
+                    typ |= REG_LOCATOR_TYPE_64_BIT;
+                }
+                self.add_to_tables(RegLocatorDao::new(
+                    signature.clone(),
+                    root,
+                    RegPath::from_str(&key)?,
+                    name.map(Formatted::from),
+                    Some(typ),
+                ))?;
+                result.file().cloned()
+            }
+            SystemSearch::Directory { parent, path, depth, file } => {
+                if let Some(parent) = &parent {
+                    ensure!(
+                        self.has_locator(parent),
+                        "Parent search [{parent}] does not exist"
+                    );
+                }
+                ensure!(depth >= 0, "Search depth cannot be negative");
+                self.add_to_tables(DrLocatorDao::new(
+                    signature.clone(),
+                    parent,
+                    Some(path).filter(|path| !path.is_empty()),
+                    Some(depth),
+                ))?;
+                file
+            }
+            SystemSearch::Component { component_id, result } => {
+                ensure!(
+                    result != SearchResult::Raw,
+                    "Component searches can only find directories and files"
+                );
+                self.add_to_tables(CompLocatorDao::new(
+                    signature.clone(),
+                    component_id,
+                    Some(result.locator_type()),
+                ))?;
+                result.file().cloned()
+            }
+            SystemSearch::Ini { file_name, section, key, field, result } => {
+                ensure!(field >= 0, "INI fields cannot be negative");
+                self.add_to_tables(IniLocatorDao::new(
+                    signature.clone(),
+                    file_name,
+                    section,
+                    key,
+                    Some(field),
+                    Some(result.locator_type()),
+                ))?;
+                result.file().cloned()
+            }
+        };
+        if let Some(file) = file {
+            self.add_to_tables(file.to_dao(signature.clone())?)?;
+        }
+        Ok(signature)
+    }
+
+    fn has_locator(&self, signature: &SignatureIdentifier) -> bool {
+        MsiBuilderList::entries(&self.reg_locator)
+            .iter()
+            .any(|dao| dao.signature() == signature)
+            || MsiBuilderList::entries(&self.dr_locator)
+                .iter()
+                .any(|dao| dao.signature() == signature)
+            || MsiBuilderList::entries(&self.comp_locator)
+                .iter()
+                .any(|dao| dao.signature() == signature)
+            || MsiBuilderList::entries(&self.ini_locator)
+                .iter()
+                .any(|dao| dao.signature() == signature)
+    }
+
+    /// Make this package replace installed versions of the product that
+    /// share `upgrade_code`, instead of installing next to them.
+    ///
+    /// Sets the `UpgradeCode` property. When the package is built, the
+    /// `Upgrade` table is filled in from the `ProductVersion` property and
+    /// the actions that find and remove the older versions are scheduled.
+    /// Unless downgrades are allowed, a launch condition stops the
+    /// installation when a newer version is installed.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # use std::str::FromStr;
+    /// # use whimsi_lib::builder::MsiBuilder;
+    /// # use whimsi_lib::tables::upgrade::major_upgrade::MajorUpgradeOptions;
+    /// # use whimsi_lib::types::column::guid::Guid;
+    /// let upgrade_code =
+    ///     Guid::from_str("{B3E4E0A8-2F1C-4C43-9A57-0C5E7D0F3C11}").unwrap();
+    /// let builder = MsiBuilder::default()
+    ///     .with_property("ProductVersion", "1.2.0")
+    ///     .unwrap()
+    ///     .with_major_upgrade(
+    ///         upgrade_code,
+    ///         MajorUpgradeOptions::default().with_allow_same_version_upgrades(true),
+    ///     )
+    ///     .unwrap();
+    /// ```
+    pub fn with_major_upgrade(
+        mut self,
+        upgrade_code: Guid,
+        options: MajorUpgradeOptions,
+    ) -> anyhow::Result<Self> {
+        self.add_major_upgrade(upgrade_code, options)?;
+        Ok(self)
+    }
+
+    pub fn add_major_upgrade(
+        &mut self,
+        upgrade_code: Guid,
+        options: MajorUpgradeOptions,
+    ) -> anyhow::Result<()> {
+        ensure!(
+            self.major_upgrade.is_none(),
+            "A major upgrade has already been added"
+        );
+        match self.property_value(UPGRADE_CODE_PROPERTY) {
+            Some(existing) => ensure!(
+                Guid::from_str(&existing).ok().as_ref() == Some(&upgrade_code),
+                "{UPGRADE_CODE_PROPERTY} property [{existing}] doesn't match the major upgrade's [{upgrade_code}]"
+            ),
+            None => self.add_property(UPGRADE_CODE_PROPERTY, &upgrade_code)?,
+        }
+        self.major_upgrade = Some((upgrade_code, options));
+        Ok(())
+    }
+
+    /// Give the component whose key path installs to `target` a fixed GUID
+    /// instead of the one derived when the package is built.
+    ///
+    /// Targets are written the way the installer resolves them, starting at
+    /// a system folder. Files are `[ProgramFilesFolder]App\app.exe` and
+    /// registry values are `HKLM\Software\App\Name`. Components without a
+    /// file or registry key path use what they install instead, separated by
+    /// `;` when there are several: registry values and removals as above,
+    /// environment variables as `%PATH%`, INI entries as
+    /// `[WindowsFolder]app.ini[Section]Key` and created or cleaned up
+    /// directories as their path, followed by the file name pattern for
+    /// removals. Components that install none of these use their directory
+    /// followed by their identifier. Targets are compared case insensitively.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # use std::str::FromStr;
+    /// # use whimsi_lib::builder::MsiBuilder;
+    /// # use whimsi_lib::types::column::guid::Guid;
+    /// let mut builder = MsiBuilder::default();
+    /// builder.pin_component_guid(
+    ///     "[ProgramFilesFolder]App\\app.exe",
+    ///     Guid::from_str("{6F9619FF-8B86-D011-B42D-00C04FC964FF}").unwrap(),
+    /// );
+    /// ```
+    pub fn pin_component_guid(&mut self, target: impl ToString, guid: Guid) {
+        self.pinned_component_guids
+            .insert(target.to_string().to_lowercase(), guid);
+    }
+
+    /// Build the MSI from all information given to MSIBuilder.
+    pub fn build<F: std::io::Read + std::io::Write + std::io::Seek>(
+        mut self,
+        container: F,
+    ) -> anyhow::Result<whimsi_msi::Package<F>> {
+        // Steps that add rows run first so the rows are validated and the
+        // actions they schedule can be placed relative to.
+        self.add_major_upgrade_entries()?;
+        self.assign_key_paths()?;
+        self.resolve_relative_actions()?;
+        self.validate_formatted_references()?;
+        self.assign_component_guids()?;
+        let Some(ref meta) = self.meta else {
+            bail!("Meta information cannot be blank");
+        };
+        info!("Building MSI");
+
+        // Copy the information from the blank reference MSI to the container.
+        let mut package = copy_schema_to(container, *meta.cfb_version())?;
+
+        // TODO: Remove after getting everything working
+        let extra_tables = [
+            "ActionText",
+            // "Directory",
+            // "Media",
+            // "File",
+            // "Shortcut",
+            // "CustomAction",
+            // "ServiceControl",
+            "AdvtUISequence",
+            "AppId",
+            "BBControl",
+            "Billboard",
+            "BindImage",
+            "CheckBox",
+            "Class",
+            "ComboBox",
+            "Complus",
+            // "Control",
+            "ControlCondition",
+            "ControlEvent",
+            "Dialog",
+            "DuplicateFile",
+            "EventMapping",
+            "Extension",
+            "FileSFPCatalog",
+            "Font",
+            "IsolatedComponent",
+            "ListBox",
+            "ListView",
+            // Recreated from `LockPermissionsTable` when it is written.
+            "LockPermissions",
+            "MIME",
+            "MoveFile",
+            "MsiAssembly",
+            "MsiAssemblyName",
+            "MsiDigitalCertificate",
+            "MsiDigitalSignature",
+            "MsiPatchHeaders",
+            "ODBCAttribute",
+            "ODBCDataSource",
+            "ODBCDriver",
+            "ODBCSourceAttribute",
+            "ODBCTranslator",
+            "Patch",
+            "PatchPackage",
+            "ProgId",
+            "PublishComponent",
+            "RadioButton",
+            "ReserveCost",
+            "SFPCatalog",
+            "SelfReg",
+            "TextStyle",
+            "TypeLib",
+            "UIText",
+            "Verb",
+        ];
+        for table in extra_tables {
+            package.drop_table(table);
+        }
+
+        // let mut package = whimsi_msi::Package::create(
+        //     whimsi_msi::PackageType::Installer,
+        //     container,
+        // )?;
+        self.write_meta_info_to_package(&mut package, meta)?;
+        self.write_tables_to_package(&mut package)?;
+        self.write_icons_to_package(&mut package)?;
+        self.write_binaries_to_package(&mut package)?;
+        self.write_cabinets_to_package(&mut package)?;
+        if let Some(reproducibility) = &self.reproducibility {
+            let package_code = reproducibility.package_code(&mut package)?;
+            package.summary_info_mut().set_uuid(package_code);
+        }
+
+        info!("Finished building MSI");
+        Ok(package)
+    }
+
+    /// Checks that the `Formatted` columns of every table can be parsed and
+    /// that the files and components they reference are in the package.
+    ///
+    /// Properties can also be set on the command line or by the installer
+    /// itself, so unknown properties are only logged.
+    pub(crate) fn validate_formatted_references(&self) -> anyhow::Result<()> {
+        let files: HashSet<Identifier> = self
+            .file
+            .entries()
+            .iter()
+            .filter_map(ToUniqueMsiIdentifier::to_unique_msi_identifier)
+            .collect();
+        let components: HashSet<Identifier> = self
+            .component
+            .entries()
+            .iter()
+            .filter_map(ToUniqueMsiIdentifier::to_unique_msi_identifier)
+            .collect();
+        let properties: HashSet<Identifier> =
+            MsiBuilderList::entries(&self.property)
+                .iter()
+                .filter_map(ToUniqueMsiIdentifier::to_unique_msi_identifier)
+                .chain(self.directory.entries().iter().filter_map(
+                    ToUniqueMsiIdentifier::to_unique_msi_identifier,
+                ))
+                .collect();
+
+        let tables: [(&str, Vec<&Formatted>); 10] = [
+            ("Registry", formatted_columns(&self.registry)),
+            ("RemoveRegistry", formatted_columns(&self.remove_registry)),
+            ("Environment", formatted_columns(&self.environment)),
+            ("IniFile", formatted_columns(&self.ini_file)),
+            ("RemoveIniFile", formatted_columns(&self.remove_ini_file)),
+            ("Shortcut", formatted_columns(&self.shortcut)),
+            ("ServiceInstall", formatted_columns(&self.service_install)),
+            ("ServiceControl", formatted_columns(&self.service_control)),
+            ("CustomAction", formatted_columns(&self.custom_action)),
+            ("LaunchCondition", formatted_columns(&self.launch_condition)),
+        ];
+        for (table, values) in tables {
+            for value in values {
+                let mut segments = value.segments().with_context(|| {
+                    format!("Invalid formatted text [{value}] in table {table}")
+                })?;
+                while let Some(segment) = segments.pop() {
+                    match segment {
+                        FormattedSegment::Group(inner) => {
+                            segments.extend(inner)
+                        }
+                        FormattedSegment::FileFullPath(file)
+                        | FormattedSegment::FileShortPath(file) => ensure!(
+                            files.contains(&file),
+                            "File [{file}] referenced by [{value}] in table {table} does not exist"
+                        ),
+                        FormattedSegment::ComponentDirectory(component) => {
+                            ensure!(
+                                components.contains(&component),
+                                "Component [{component}] referenced by [{value}] in table {table} does not exist"
+                            )
+                        }
+                        FormattedSegment::Property(property)
+                            if !properties.contains(&property)
+                                && property.as_system_folder().is_none() =>
+                        {
+                            warn!(
+                                "Property [{property}] referenced by [{value}] in table {table} is not set by the package"
+                            );
+                        }
+                        _ => {}
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    pub(crate) fn write_meta_info_to_package<
+        F: std::io::Read + std::io::Write + std::io::Seek,
+    >(
+        &self,
+        package: &mut whimsi_msi::Package<F>,
+        meta: &MetaInformation,
+    ) -> anyhow::Result<()> {
+        let package_type = package.package_type();
+        package.set_database_codepage(whimsi_msi::CodePage::Windows1252);
+        let summary_info = package.summary_info_mut();
+        summary_info.set_codepage(whimsi_msi::CodePage::Windows1252);
+        // TODO: Ensure that `subject` is the same as `ProductName` in the
+        // `Property` table as specified [here](https://learn.microsoft.com/en-us/windows/win32/msi/subject-summary)
+        summary_info.set_subject(meta.subject());
+
+        if let Some(author) = meta.author() {
+            summary_info.set_author(author);
+        }
+        summary_info.set_languages(meta.languages());
+        if let Some(arch) = meta.architecture() {
+            summary_info.set_arch(arch.to_string());
+        }
+        if let Some(comments) = meta.comments() {
+            summary_info.set_comments(comments);
+        }
+        // TODO: Change this after testing. Just trying to make everything
+        // exactly the same.
+        summary_info.set_creating_application("msitools 0.106");
+        summary_info.set_keywords(meta.keywords());
+        if let Some(reproducibility) = &self.reproducibility {
+            summary_info.set_creation_time(*reproducibility.timestamp());
+            summary_info.set_last_save_time(*reproducibility.timestamp());
+            // The PackageCode is set once the package contents are written.
+        } else {
+            summary_info.set_creation_time_to_now();
+            summary_info.set_last_save_time_to_now();
+            summary_info.set_uuid(Uuid::new_v4());
+        }
+        summary_info.set_word_count(2);
+        // TODO: Determine if older versions should be supported.
+        // Only support versions after 5.0
+        summary_info.set_page_count(PageCount::_5_0 as i32);
+        summary_info.set_doc_security(DocSecurity::from_package_type(
+            &package_type,
+        ) as i32);
+        Ok(())
+    }
+
+    /// Just writes the information stored in each of the table properties to
+    /// the package tables.
+    ///
+    /// Information is written based on a predetermined order so that
+    /// information that doesn't reference other table information is
+    /// written first.
+    pub(crate) fn write_tables_to_package<
+        F: std::io::Read + std::io::Write + std::io::Seek,
+    >(
+        &self,
+        package: &mut whimsi_msi::Package<F>,
+    ) -> anyhow::Result<()> {
+        info!("Writing tables to package");
+        self.directory.write_to_package(package)?;
+        // NOTE: The order in which the tables are written to matters. WTF.
+        self.component.write_to_package(package)?;
+        self.file.write_to_package(package)?;
+        self.media.write_to_package(package)?;
+        self.feature.write_to_package(package)?;
+        self.feature_components.write_to_package(package)?;
+        self.condition.write_to_package(package)?;
+        self.create_folder.write_to_package(package)?;
+        self.remove_file.write_to_package(package)?;
+        self.property.write_to_package(package)?;
+        self.registry.write_to_package(package)?;
+        self.remove_registry.write_to_package(package)?;
+        self.environment.write_to_package(package)?;
+        self.ini_file.write_to_package(package)?;
+        self.remove_ini_file.write_to_package(package)?;
+        self.msi_file_hash.write_to_package(package)?;
+        // If this isn't the first sequence table to be filled, it is corrupted
+        // for some reason?
+        self.admin_execute_sequence.write_to_package(package)?;
+        self.admin_ui_sequence.write_to_package(package)?;
+        self.advt_execute_sequence.write_to_package(package)?;
+        self.install_execute_sequence.write_to_package(package)?;
+        self.install_ui_sequence.write_to_package(package)?;
+        self.service_control.write_to_package(package)?;
+        self.service_install.write_to_package(package)?;
+        self.lock_permissions.write_to_package(package)?;
+        self.shortcut.write_to_package(package)?;
+        self.icon.write_to_package(package)?;
+
+        // NOTE: Empty tables that seem to be required?
+        self.signature.write_to_package(package)?;
+        self.launch_condition.write_to_package(package)?;
+        self.upgrade.write_to_package(package)?;
+        self.reg_locator.write_to_package(package)?;
+        self.dr_locator.write_to_package(package)?;
+        self.comp_locator.write_to_package(package)?;
+        self.ini_locator.write_to_package(package)?;
+        self.app_search.write_to_package(package)?;
+        self.ccp_search.write_to_package(package)?;
+        self.binary.write_to_package(package)?;
+        self.custom_action.write_to_package(package)?;
+        debug!(
+            "Wrote tables to MSI: {:?}",
+            package.tables().map(|t| t.name()).collect_vec()
+        );
+        Ok(())
+    }
+
+    pub(crate) fn write_cabinets_to_package<
+        F: std::io::Read + std::io::Write + std::io::Seek,
+    >(
+        &self,
+        package: &mut whimsi_msi::Package<F>,
+    ) -> anyhow::Result<()> {
+        let previous_last_sequence = 1;
+        for media in MsiBuilderTable::entries(&self.media)
+            .iter()
+            .sorted_by_key(|dao| Into::<i32>::into(*dao.last_sequence()))
+        {
+            let Some(cabinet_id) = media.cabinet_id() else {
+                // Ignore media listings that don't represent an internal
+                // cabinet file
+                continue;
+            };
+            let last_sequence = Into::<i32>::into(*media.last_sequence());
+            if last_sequence == 0 {
+                // Skip this cabinet file if no files are to be written to it.
+                continue;
+            }
+            let cabinet_info = self.cabinets.find_id(&cabinet_id).expect(
+                "Cabinet of ID [{}] could not be found when trying to build it!",
+            );
+            let files = self
+                .file
+                .in_sequence_range(previous_last_sequence, last_sequence);
+            if files.len() == 0 {
+                unreachable!(
+                    "No files found for given cabinet file. This should not happen."
+                )
+            }
+
+            let mut cabinet_file = self.create_cabinet_file(&cabinet_info)?;
+            // Have to set the poisition of the file reader back to 0 so that it
+            // gets read from the beginning when it gets read again.
+            cabinet_file.rewind();
+
+            self.write_cabinet_to_package(
+                &cabinet_info,
+                &mut cabinet_file,
+                package,
+            )?;
+        }
+        Ok(())
+    }
+
+    pub(crate) fn create_cabinet_file(
+        &self,
+        cabinet_info: &CabinetInfo,
+    ) -> anyhow::Result<std::fs::File> {
+        debug!("Creating cabinet file [{}]", cabinet_info.id());
+        let mut cab_builder = cab::CabinetBuilder::new();
+        let mut folder = cab_builder.add_folder(cab::CompressionType::MsZip);
+        cabinet_info.files().iter().for_each(|file| {
+            /// NOTE: From what I can tell attributes only need to be set on
+            /// files in the File table as those attributes
+            /// overwrite the attributes that are set in the cabinet
+            /// file.
+            let file = folder.add_file(file.id().to_string());
+            if let Some(reproducibility) = &self.reproducibility {
+                file.set_datetime(reproducibility.cabinet_datetime());
+            }
+        });
+        let file = tempfile::tempfile().with_context(|| {
+            format!(
+                "Failed to create tempfile for cabinet [{}]",
+                cabinet_info.id()
+            )
+        })?;
+        let mut cab_writer = cab_builder.build(file).with_context(|| {
+            format!(
+                "Failed to create cabinet file writer for cabinet file [{}]",
+                cabinet_info.id()
+            )
+        })?;
+
+        let mut files_iter = cabinet_info.files().iter();
+        while let Some(mut writer) =
+            cab_writer.next_file().expect("Failed to open")
+            && let Some(file) = files_iter.next()
+        {
+            let mut reader = std::fs::File::open(file.path()).unwrap();
+            std::io::copy(&mut reader, &mut writer).unwrap();
+        }
+
+        Ok(cab_writer.finish()?)
+    }
+
+    pub(crate) fn write_cabinet_to_package<
+        F: std::io::Read + std::io::Write + std::io::Seek,
+    >(
+        &self,
+        cabinet_info: &CabinetInfo,
+        cabinet: &mut std::fs::File,
+        package: &mut whimsi_msi::Package<F>,
+    ) -> anyhow::Result<()> {
+        let cabinet_id = cabinet_info.id();
+        debug!(
+            "Writing cabinet file for cabinet ID [{}] to package",
+            cabinet_id
+        );
+        let mut writer = package
+            .write_stream(&cabinet_id.to_string())
+            .context("Failed to create cabinet stream writier for package")?;
+        std::io::copy(cabinet, &mut writer).with_context(|| {
+            format!("Failed to copy cabinet [{cabinet_id}] to package")
+        })?;
+
+        Ok(())
+    }
+
+    // Icon stream writing information can be found partially
+    // [here](https://learn.microsoft.com/en-us/windows/win32/msi/ole-limitations-on-streams) in
+    // the first limitation listed
+    pub(crate) fn write_icons_to_package<
+        F: std::io::Read + std::io::Write + std::io::Seek,
+    >(
+        &self,
+        package: &mut whimsi_msi::Package<F>,
+    ) -> anyhow::Result<()> {
+        for icon in &self.icon_information {
+            let mut icon_data = File::open(icon.path()).with_context(|| {
+                format!("Failed to open icon at path {:?}", icon.path())
+            })?;
+            let icon_id = icon.identifier().to_string();
+            // Stream name for Icons is defined
+            // [here](https://learn.microsoft.com/en-us/windows/win32/msi/ole-limitations-on-streams) in
+            // in the first limitation listed
+            let stream_name = [self.icon.name(), ".", &icon_id].concat();
+
+            let mut writer = package
+                .write_stream(&stream_name)
+                .context("Failed to create icon stream writer for package")?;
+            std::io::copy(&mut icon_data, &mut writer).with_context(|| {
+                format!(
+                    "Failed to copy icon stream data [{icon_id}] to package"
+                )
+            })?;
+        }
+        Ok(())
+    }
+
+    // Binary streams are named the same way as Icon streams, described
+    // [here](https://learn.microsoft.com/en-us/windows/win32/msi/ole-limitations-on-streams)
+    pub(crate) fn write_binaries_to_package<
+        F: std::io::Read + std::io::Write + std::io::Seek,
+    >(
+        &self,
+        package: &mut whimsi_msi::Package<F>,
+    ) -> anyhow::Result<()> {
+        for binary in &self.binary_information {
+            let binary_id = binary.identifier().to_string();
+            let stream_name = [self.binary.name(), ".", &binary_id].concat();
+            let mut writer = package
+                .write_stream(&stream_name)
+                .context("Failed to create binary stream writer for package")?;
+            match binary.data() {
+                BinaryData::Path(path) => {
+                    let mut data = File::open(path).with_context(|| {
+                        format!("Failed to open binary at path {path:?}")
+                    })?;
+                    std::io::copy(&mut data, &mut writer)
+                }
+                BinaryData::Bytes(bytes) => {
+                    std::io::copy(&mut bytes.as_slice(), &mut writer)
+                }
+            }
+            .with_context(|| {
+                format!(
+                    "Failed to copy binary stream data [{binary_id}] to package"
+                )
+            })?;
+        }
+        Ok(())
+    }
+
+    /// Names and sequence numbers of the actions already in `table`.
+    fn sequenced_actions(
+        &self,
+        table: SequenceTable,
+    ) -> Vec<(String, Option<i16>)> {
+        let generic = |entries: &Vec<GenericSequenceDao>| {
+            entries
+                .iter()
+                .map(|dao| (dao.action().to_string(), *dao.sequence()))
+                .collect_vec()
+        };
+        match table {
+            SequenceTable::AdminUi => {
+                generic(MsiBuilderList::entries(&self.admin_ui_sequence))
+            }
+            SequenceTable::AdminExecute => {
+                generic(MsiBuilderList::entries(&self.admin_execute_sequence))
+            }
+            SequenceTable::InstallUi => {
+                generic(MsiBuilderList::entries(&self.install_ui_sequence))
+            }
+            SequenceTable::InstallExecute => {
+                generic(MsiBuilderList::entries(&self.install_execute_sequence))
+            }
+            SequenceTable::AdvtExecute => {
+                MsiBuilderList::entries(&self.advt_execute_sequence)
+                    .iter()
+                    .map(|dao| (dao.action().to_string(), *dao.sequence()))
+                    .collect_vec()
+            }
+        }
+    }
+
+    /// Whether the action has a sequence number or a relative placement in
+    /// the table.
+    fn is_scheduled(&self, table: SequenceTable, action: &str) -> bool {
+        self.sequenced_actions(table).iter().any(|(other, _)| other == action)
+            || self.relative_actions.iter().any(|relative| {
+                relative.table == table && relative.placement.action == action
+            })
+    }
+
+    /// Schedules a standard action at its suggested sequence number, unless
+    /// it is already in the table.
+    fn schedule_standard_action_in(
+        &mut self,
+        table: SequenceTable,
+        action: StandardAction,
+    ) -> anyhow::Result<()> {
+        if self.is_scheduled(table, &action.to_string()) {
+            return Ok(());
+        }
+        let Some(sequence) = action.suggested_sequence() else {
+            bail!(
+                "Standard action [{action}] has no suggested sequence number"
+            );
+        };
+        self.add_sequence_entry(table, action.into(), None, sequence)
+    }
+
+    fn add_sequence_entry(
+        &mut self,
+        table: SequenceTable,
+        action: ActionIdentifier,
+        condition: Option<Condition>,
+        sequence: i16,
+    ) -> anyhow::Result<()> {
+        if table == SequenceTable::AdvtExecute {
+            let ActionIdentifier::StandardAction(standard) = action else {
+                bail!("Only standard actions can be scheduled in {table}");
+            };
+            let advt = AdvtAction::try_from(standard).map_err(|_| {
+                anyhow!("Action [{standard}] cannot be scheduled in {table}")
+            })?;
+            return self.advt_execute_sequence.add(
+                AdvtExecuteSequenceDao::new(advt, condition, Some(sequence)),
+            );
+        }
+        let dao = GenericSequenceDao::new(action, condition, Some(sequence));
+        match table {
+            SequenceTable::AdminUi => self.admin_ui_sequence.add(dao),
+            SequenceTable::AdminExecute => self.admin_execute_sequence.add(dao),
+            SequenceTable::InstallUi => self.install_ui_sequence.add(dao),
+            SequenceTable::InstallExecute => {
+                self.install_execute_sequence.add(dao)
+            }
+            SequenceTable::AdvtExecute => unreachable!(),
+        }
+    }
+
+    /// Gives the actions placed before or after other actions their sequence
+    /// numbers and adds them to their tables.
+    pub(crate) fn resolve_relative_actions(&mut self) -> anyhow::Result<()> {
+        let relative_actions = std::mem::take(&mut self.relative_actions);
+        for table in SequenceTable::iter() {
+            let actions = relative_actions
+                .iter()
+                .filter(|relative| relative.table == table)
+                .collect_vec();
+            if actions.is_empty() {
+                continue;
+            }
+            let sequenced = self
+                .sequenced_actions(table)
+                .into_iter()
+                .filter_map(|(action, sequence)| Some((action, sequence?)))
+                .collect_vec();
+            let placements = actions
+                .iter()
+                .map(|relative| relative.placement.clone())
+                .collect_vec();
+            let numbers =
+                resolve_sequence_numbers(table, &sequenced, &placements)?;
+            for (relative, sequence) in actions.into_iter().zip(numbers) {
+                self.add_sequence_entry(
+                    table,
+                    relative.action.clone(),
+                    relative.condition.clone(),
+                    sequence,
+                )?;
+            }
+        }
+
+        for custom_action in MsiBuilderList::entries(&self.custom_action) {
+            if !custom_action.typ().is_in_script() {
+                continue;
+            }
+            let scheduled =
+                MsiBuilderList::entries(&self.install_execute_sequence)
+                    .iter()
+                    .find(|dao| {
+                        *dao.action()
+                            == ActionIdentifier::CustomAction(
+                                custom_action.action().clone(),
+                            )
+                    })
+                    .and_then(|dao| *dao.sequence());
+            if let Some(sequence) = scheduled {
+                ensure_in_script_sequence(custom_action.action(), sequence)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Adds a standard action to the `InstallExecuteSequence` table at its
+    /// suggested sequence number if it isn't already scheduled.
+    fn schedule_standard_action(
+        &mut self,
+        action: StandardAction,
+    ) -> anyhow::Result<()> {
+        let dao = GenericSequenceDao::from(action);
+        if self.install_execute_sequence.contains(&dao) {
+            return Ok(());
+        }
+        self.install_execute_sequence.add(dao)
+    }
+
+    /// Adds the `Upgrade` rows, properties, launch condition and actions of
+    /// the major upgrade.
+    fn add_major_upgrade_entries(&mut self) -> anyhow::Result<()> {
+        let Some((upgrade_code, options)) = self.major_upgrade.clone() else {
+            return Ok(());
+        };
+        let Some(version) = self.property_value(PRODUCT_VERSION_PROPERTY)
+        else {
+            bail!(
+                "A major upgrade needs the {PRODUCT_VERSION_PROPERTY} property to be set"
+            );
+        };
+        let version = Version::from_str(&version)?;
+
+        let mut older = UpgradeAttributes::empty();
+        older.set(
+            UpgradeAttributes::MIGRATE_FEATURES,
+            *options.migrate_features(),
+        );
+        older.set(
+            UpgradeAttributes::VERSION_MAX_INCLUSIVE,
+            *options.allow_same_version_upgrades(),
+        );
+        self.add_to_tables(UpgradeDao::new(
+            upgrade_code.clone(),
+            None,
+            Some(version.clone()),
+            None,
+            older.bits(),
+            None,
+            OLDER_VERSION_DETECTED_PROPERTY.parse()?,
+        ))?;
+        let mut detected = vec![OLDER_VERSION_DETECTED_PROPERTY];
+        if !options.allow_downgrades() {
+            self.add_to_tables(UpgradeDao::new(
+                upgrade_code,
+                Some(version),
+                None,
+                None,
+                UpgradeAttributes::ONLY_DETECT.bits(),
+                None,
+                NEWER_VERSION_DETECTED_PROPERTY.parse()?,
+            ))?;
+            detected.push(NEWER_VERSION_DETECTED_PROPERTY);
+            self.add_launch_condition(
+                Condition::from_str(&format!(
+                    "NOT {NEWER_VERSION_DETECTED_PROPERTY}"
+                ))?,
+                options.downgrade_error_message(),
+            )?;
+        }
+        self.add_secure_custom_properties(&detected)?;
+
+        for table in [SequenceTable::InstallUi, SequenceTable::InstallExecute] {
+            self.schedule_standard_action_in(
+                table,
+                StandardAction::FindRelatedProducts,
+            )?;
+            if *options.migrate_features() {
+                self.schedule_standard_action_in(
+                    table,
+                    StandardAction::MigrateFeatureStates,
+                )?;
+            }
+        }
+        // Leave the removal alone if it was scheduled by hand.
+        if !self.is_scheduled(
+            SequenceTable::InstallExecute,
+            &StandardAction::RemoveExistingProducts.to_string(),
+        ) {
+            self.schedule_action(
+                SequenceTable::InstallExecute,
+                StandardAction::RemoveExistingProducts,
+                None,
+                options.remove_existing_products().clone(),
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Adds properties to `SecureCustomProperties` so their values are passed
+    /// from the client to the server side of the installation.
+    fn add_secure_custom_properties(
+        &mut self,
+        properties: &[&str],
+    ) -> anyhow::Result<()> {
+        let entries = MsiBuilderList::entries_mut(&mut self.property);
+        let mut secured = match entries.iter().position(|property| {
+            property.property().to_string() == SECURE_CUSTOM_PROPERTIES_PROPERTY
+        }) {
+            Some(index) => entries
+                .remove(index)
+                .value()
+                .to_string()
+                .split(';')
+                .map(str::to_string)
+                .collect_vec(),
+            None => Vec::new(),
+        };
+        for property in properties {
+            if !secured.iter().any(|secure| secure == property) {
+                secured.push(property.to_string());
+            }
+        }
+        self.add_property(SECURE_CUSTOM_PROPERTIES_PROPERTY, secured.join(";"))
+    }
+
+    /// The value of a property in the `Property` table.
+    fn property_value(&self, property: &str) -> Option<String> {
+        MsiBuilderList::entries(&self.property)
+            .iter()
+            .find(|dao| dao.property().to_string() == property)
+            .map(|dao| dao.value().to_string())
+    }
+
+    /// Gives the components shared by the files of a directory their key
+    /// paths.
+    ///
+    /// Components without a chosen key path use their first file.
+    pub(crate) fn assign_key_paths(&mut self) -> anyhow::Result<()> {
+        let mut unused =
+            self.component_key_paths.keys().cloned().collect::<HashSet<_>>();
+        for (directory, _, component_id) in self.shared_components.clone() {
+            let directory_key = directory.to_identifier();
+            let files = MsiBuilderList::entries(&self.file)
+                .iter()
+                .filter(|file| *file.component() == component_id)
+                .collect_vec();
+            let Some(first_file) = files.first() else {
+                bail!("Component [{component_id}] has no files");
+            };
+            let mut key_path = Some(first_file.file().to_identifier());
+            let mut attributes = ComponentAttributes::LocalOnly;
+            match self.component_key_paths.get(&directory_key).cloned() {
+                Some(ComponentKeyPath::File(name)) => {
+                    if let Some(file) = files.iter().find(|file| {
+                        file.name().to_string().eq_ignore_ascii_case(&name)
+                    }) {
+                        key_path = Some(file.file().to_identifier());
+                        unused.remove(&directory_key);
+                    }
+                }
+                Some(ComponentKeyPath::Directory) => {
+                    key_path = None;
+                    self.add_create_folder(&directory, &component_id)?;
+                    unused.remove(&directory_key);
+                }
+                Some(ComponentKeyPath::Registry { root, key, name, value }) => {
+                    // Two components can't share a key path.
+                    ensure!(
+                        self.shared_components
+                            .iter()
+                            .filter(|(shared, _, _)| {
+                                shared.to_identifier() == directory_key
+                            })
+                            .count()
+                            == 1,
+                        "Directory [{directory}] has files in several features so its components can't share a registry key path"
+                    );
+                    let registry_id = self.registry.generate_id();
+                    self.add_to_tables(RegistryDao::new(
+                        registry_id.clone(),
+                        root,
+                        RegPath::from_str(&key)?,
+                        name.map(Into::into),
+                        Some(value),
+                        component_id.clone(),
+                    ))?;
+                    self.schedule_standard_action(
+                        StandardAction::RemoveRegistryValues,
+                    )?;
+                    self.schedule_standard_action(
+                        StandardAction::WriteRegistryValues,
+                    )?;
+                    key_path = Some(registry_id.to_identifier());
+                    attributes = ComponentAttributes::RegistryKeyPath;
+                    unused.remove(&directory_key);
+                }
+                None => {}
+            }
+
+            let component = MsiBuilderList::entries_mut(&mut self.component)
+                .iter_mut()
+                .find(|component| *component.component() == component_id)
+                .with_context(|| {
+                    format!("Component [{component_id}] does not exist")
+                })?;
+            let mut updated = component.clone().with_attributes(attributes);
+            if let Some(key_path) = key_path {
+                updated = updated.with_keypath(key_path);
+            }
+            *component = updated;
+        }
+
+        if let Some(directory) =
+            unused.iter().map(ToString::to_string).sorted().next()
+        {
+            bail!(
+                "The key path chosen for directory [{directory}] doesn't match any component shared by its files. Versioned files are always the key path of their own component"
+            );
+        }
+        Ok(())
+    }
+
+    /// Gives every component a GUID so the installer can reference count it.
+    ///
+    /// Pinned GUIDs are used first, then GUIDs already set on the component.
+    /// The rest are UUIDv5s of the component's target in a namespace taken
+    /// from the `UpgradeCode` property, so they stay the same across builds
+    /// and versions of the product.
+    pub(crate) fn assign_component_guids(&mut self) -> anyhow::Result<()> {
+        let namespace = self.component_guid_namespace()?;
+        let mut pinned = self.pinned_component_guids.clone();
+        let mut guids = Vec::new();
+        for component in MsiBuilderList::entries(&self.component) {
+            let target = self.component_target(component)?.to_lowercase();
+            let guid = match (pinned.remove(&target), component.component_id())
+            {
+                (Some(guid), _) => guid,
+                (None, Some(guid)) => guid.clone(),
+                (None, None) => match namespace {
+                    Some(namespace) => {
+                        Uuid::new_v5(&namespace, target.as_bytes()).into()
+                    }
+                    None => Uuid::new_v4().into(),
+                },
+            };
+            guids.push((component.component().clone(), guid));
+        }
+        ensure!(
+            pinned.is_empty(),
+            "Pinned component GUIDs for [{}] do not match any component",
+            pinned.keys().sorted().join(", ")
+        );
+        if let Some((component, _)) =
+            guids.iter().duplicates_by(|(_, guid)| guid).next()
+        {
+            bail!(
+                "Component [{component}] has the same GUID as another component"
+            );
+        }
+
+        for (component, (_, guid)) in
+            MsiBuilderList::entries_mut(&mut self.component)
+                .iter_mut()
+                .zip(guids)
+        {
+            *component = component.clone().with_component_id(guid);
+        }
+        Ok(())
+    }
+
+    /// The namespace component GUIDs are derived in, if builds are meant to
+    /// give the same GUIDs.
+    fn component_guid_namespace(&self) -> anyhow::Result<Option<Uuid>> {
+        if let Some(upgrade_code) = self.property_value(UPGRADE_CODE_PROPERTY) {
+            let namespace =
+                Uuid::parse_str(upgrade_code.trim()).with_context(|| {
+                    format!(
+                        "{UPGRADE_CODE_PROPERTY} [{upgrade_code}] is not a valid GUID"
+                    )
+                })?;
+            return Ok(Some(namespace));
+        }
+        if let Some(reproducibility) = &self.reproducibility {
+            return Ok(Some(reproducibility.guid(COMPONENT_ID_GUID_NAME, &[])));
+        }
+        warn!(
+            "No {UPGRADE_CODE_PROPERTY} property is set, component GUIDs will change on every build"
+        );
+        Ok(None)
+    }
+
+    /// Where the key path of the component is installed to. See
+    /// [`MsiBuilder::pin_component_guid`] for the format.
+    fn component_target(
+        &self,
+        component: &ComponentDao,
+    ) -> anyhow::Result<String> {
+        let directory = self.directory_target(component.directory())?;
+        let key_path = component.key_path().as_ref();
+        if let Some(file) = key_path.and_then(|key_path| {
+            MsiBuilderList::entries(&self.file)
+                .iter()
+                .find(|file| file.file().to_identifier() == *key_path)
+        }) {
+            return Ok(format!("{directory}{}", file.name()));
+        }
+        if let Some(registry) = key_path.and_then(|key_path| {
+            MsiBuilderList::entries(&self.registry).iter().find(|registry| {
+                registry.registry().to_identifier() == *key_path
+            })
+        }) {
+            return Ok(registry_target(
+                registry.root(),
+                registry.key(),
+                registry.name().as_ref(),
+            ));
+        }
+
+        let contents = self.component_contents(component.component())?;
+        if contents.is_empty() {
+            return Ok(format!("{directory}{}", component.component()));
+        }
+        Ok(contents.into_iter().sorted().join(";"))
+    }
+
+    /// Describes everything a component without a file or registry key path
+    /// installs, so its target doesn't depend on the generated component
+    /// identifier.
+    fn component_contents(
+        &self,
+        component: &ComponentIdentifier,
+    ) -> anyhow::Result<Vec<String>> {
+        let mut contents = Vec::new();
+        for registry in MsiBuilderList::entries(&self.registry)
+            .iter()
+            .filter(|dao| dao.component() == component)
+        {
+            contents.push(registry_target(
+                registry.root(),
+                registry.key(),
+                registry.name().as_ref(),
+            ));
+        }
+        for removal in MsiBuilderList::entries(&self.remove_registry)
+            .iter()
+            .filter(|dao| dao.component() == component)
+        {
+            contents.push(registry_target(
+                removal.root(),
+                removal.key(),
+                removal.name().as_ref(),
+            ));
+        }
+        for environment in MsiBuilderList::entries(&self.environment)
+            .iter()
+            .filter(|dao| dao.component() == component)
+        {
+            // The prefix only says how the variable is changed.
+            let name = environment
+                .name()
+                .trim_start_matches(['=', '+', '-', '!', '*']);
+            contents.push(format!("%{name}%"));
+        }
+        for ini in MsiBuilderList::entries(&self.ini_file)
+            .iter()
+            .filter(|dao| dao.component() == component)
+        {
+            contents.push(format!(
+                "{}{}[{}]{}",
+                self.ini_directory_target(ini.dir_property().as_ref())?,
+                ini.filename(),
+                ini.section(),
+                ini.key()
+            ));
+        }
+        for ini in MsiBuilderList::entries(&self.remove_ini_file)
+            .iter()
+            .filter(|dao| dao.component() == component)
+        {
+            contents.push(format!(
+                "{}{}[{}]{}",
+                self.ini_directory_target(ini.dir_property().as_ref())?,
+                ini.filename(),
+                ini.section(),
+                ini.key()
+            ));
+        }
+        for folder in MsiBuilderList::entries(&self.create_folder)
+            .iter()
+            .filter(|dao| dao.component() == component)
+        {
+            contents.push(self.directory_target(folder.directory())?);
+        }
+        for removal in MsiBuilderList::entries(&self.remove_file)
+            .iter()
+            .filter(|dao| dao.component() == component)
+        {
+            contents.push(format!(
+                "{}{}",
+                self.property_target(removal.dir_property())?,
+                removal.filename().as_deref().unwrap_or_default()
+            ));
+        }
+        Ok(contents)
+    }
+
+    /// The directory an INI file is in. Files without a directory property
+    /// are in the Windows folder.
+    fn ini_directory_target(
+        &self,
+        dir_property: Option<&Identifier>,
+    ) -> anyhow::Result<String> {
+        match dir_property {
+            Some(dir_property) => self.property_target(dir_property),
+            None => Ok("[WindowsFolder]".to_string()),
+        }
+    }
+
+    /// The directory held by a property, which is either a directory in the
+    /// `Directory` table or a property set at install time.
+    fn property_target(&self, property: &Identifier) -> anyhow::Result<String> {
+        match MsiBuilderList::entries(&self.directory)
+            .iter()
+            .find(|dao| dao.directory().to_identifier() == *property)
+        {
+            Some(dao) => self.directory_target(dao.directory()),
+            None => Ok(format!("[{property}]")),
+        }
+    }
+
+    /// The path of a directory starting from the system folder it is in, such
+    /// as `[ProgramFilesFolder]App\bin\`.
+    fn directory_target(
+        &self,
+        directory: &DirectoryIdentifier,
+    ) -> anyhow::Result<String> {
+        let directories = MsiBuilderList::entries(&self.directory);
+        let mut segments = Vec::new();
+        let mut current = directory.clone();
+        while SystemFolder::try_from(current.to_identifier()).is_err() {
+            let Some(dao) =
+                directories.iter().find(|dao| *dao.directory() == current)
+            else {
+                bail!("Directory [{current}] does not exist");
+            };
+            let Some(parent) = dao.parent() else {
+                break;
+            };
+            ensure!(
+                segments.len() < directories.len(),
+                "Directory [{directory}] is its own ancestor"
+            );
+            segments.push(dao.default_dir().to_string());
+            current = parent.clone();
+        }
+        // Resolved directories end with a separator, as they do in the
+        // installer.
+        let path = segments
+            .iter()
+            .rev()
+            .map(|segment| format!("{segment}\\"))
+            .join("");
+        Ok(format!("[{current}]{path}"))
+    }
+
+    fn add_to_current_feature(
+        &mut self,
+        component_id: &ComponentIdentifier,
+    ) -> anyhow::Result<()> {
+        let feature = self.current_feature.clone();
+        self.add_component_to_feature(&feature, component_id)
+    }
+
+    fn has_component(&self, component: &ComponentIdentifier) -> bool {
+        MsiBuilderList::entries(&self.component)
+            .iter()
+            .any(|dao| dao.component() == component)
+    }
+
+    fn has_feature(&self, feature: &FeatureIdentifier) -> bool {
+        MsiBuilderList::entries(&self.feature)
+            .iter()
+            .any(|dao| dao.feature() == feature)
+    }
+
+    fn add_component_to_feature(
+        &mut self,
+        feature_id: &FeatureIdentifier,
+        component_id: &ComponentIdentifier,
+    ) -> anyhow::Result<()> {
+        self.feature_components.add(FeatureComponentsDao::new(
             feature_id.clone(),
             component_id.clone(),
         ))
     }
 
-    /// Insert the given DAO into it's respective table.
-    pub fn add_to_tables(&mut self, dao: impl Into<Dao>) -> anyhow::Result<()> {
-        let dao = Into::<Dao>::into(dao);
-        match dao {
-            Dao::Component(component_dao) => {
-                IdGeneratorBuilderList::add(&mut self.component, component_dao)
-            }
-            Dao::Directory(directory_dao) => {
-                IdGeneratorBuilderList::add(&mut self.directory, directory_dao)
-            }
-            Dao::File(file_dao) => {
-                IdGeneratorBuilderList::add(&mut self.file, file_dao)
-            }
-            Dao::Registry(registry_dao) => {
-                IdGeneratorBuilderList::add(&mut self.registry, registry_dao)
+    /// Insert the given DAO into it's respective table.
+    pub fn add_to_tables(&mut self, dao: impl Into<Dao>) -> anyhow::Result<()> {
+        let dao = Into::<Dao>::into(dao);
+        match dao {
+            Dao::Component(component_dao) => {
+                IdGeneratorBuilderList::add(&mut self.component, component_dao)
+            }
+            Dao::Directory(directory_dao) => {
+                IdGeneratorBuilderList::add(&mut self.directory, directory_dao)
+            }
+            Dao::File(file_dao) => {
+                IdGeneratorBuilderList::add(&mut self.file, file_dao)
+            }
+            Dao::Registry(registry_dao) => {
+                IdGeneratorBuilderList::add(&mut self.registry, registry_dao)
+            }
+            Dao::RemoveRegistry(dao) => {
+                IdGeneratorBuilderList::add(&mut self.remove_registry, dao)
+            }
+            Dao::Environment(dao) => {
+                IdGeneratorBuilderList::add(&mut self.environment, dao)
+            }
+            Dao::IniFile(dao) => {
+                IdGeneratorBuilderList::add(&mut self.ini_file, dao)
+            }
+            Dao::RemoveIniFile(dao) => {
+                IdGeneratorBuilderList::add(&mut self.remove_ini_file, dao)
+            }
+            Dao::RemoveFile(dao) => {
+                IdGeneratorBuilderList::add(&mut self.remove_file, dao)
+            }
+            Dao::Feature(feature_dao) => {
+                IdGeneratorBuilderList::add(&mut self.feature, feature_dao)
+            }
+            Dao::Shortcut(shortcut_dao) => {
+                IdGeneratorBuilderList::add(&mut self.shortcut, shortcut_dao)
+            }
+            Dao::ServiceInstall(dao) => {
+                IdGeneratorBuilderList::add(&mut self.service_install, dao)
+            }
+            Dao::ServiceControl(dao) => {
+                IdGeneratorBuilderList::add(&mut self.service_control, dao)
+            }
+            Dao::Icon(dao) => IdGeneratorBuilderList::add(&mut self.icon, dao),
+            Dao::Binary(dao) => {
+                IdGeneratorBuilderList::add(&mut self.binary, dao)
+            }
+            Dao::Signature(dao) => {
+                IdGeneratorBuilderList::add(&mut self.signature, dao)
+            }
+            Dao::Property(dao) => self.property.add(dao),
+            Dao::Media(dao) => self.media.add(dao),
+            Dao::MsiFileHash(dao) => self.msi_file_hash.add(dao),
+            Dao::FeatureComponents(dao) => self.feature_components.add(dao),
+            Dao::Condition(dao) => self.condition.add(dao),
+            Dao::CreateFolder(dao) => self.create_folder.add(dao),
+            Dao::LaunchCondition(dao) => self.launch_condition.add(dao),
+            Dao::Upgrade(dao) => self.upgrade.add(dao),
+            Dao::LockPermissions(dao) => self.lock_permissions.add(dao),
+            Dao::CustomAction(dao) => self.custom_action.add(dao),
+            Dao::RegLocator(dao) => self.reg_locator.add(dao),
+            Dao::DrLocator(dao) => self.dr_locator.add(dao),
+            Dao::CompLocator(dao) => self.comp_locator.add(dao),
+            Dao::IniLocator(dao) => self.ini_locator.add(dao),
+            Dao::AppSearch(dao) => self.app_search.add(dao),
+            Dao::CcpSearch(dao) => self.ccp_search.add(dao),
+        }
+    }
+}
+
+/// Copies the blank reference MSI into `container` and opens it.
+///
+/// The reference MSI is stored with 512-byte sectors, so when another version
+/// of the compound file format is requested its streams are copied one by one
+/// into a new compound file of that version.
+fn copy_schema_to<F: std::io::Read + std::io::Write + std::io::Seek>(
+    mut container: F,
+    version: whimsi_msi::CfbVersion,
+) -> anyhow::Result<whimsi_msi::Package<F>> {
+    let mut reference_msi =
+        std::io::Cursor::new(include_bytes!("../resources/Schema.msi"));
+    let mut source = cfb::CompoundFile::open(&mut reference_msi)
+        .context("Failed to open reference MSI")?;
+    if source.version() == version {
+        reference_msi.rewind()?;
+        std::io::copy(&mut reference_msi, &mut container)?;
+        return Ok(whimsi_msi::Package::open(container)?);
+    }
+
+    debug!("Converting reference MSI to compound file version {version:?}");
+    let mut destination =
+        cfb::CompoundFile::create_with_version(version, container)?;
+    destination.set_storage_clsid("/", *source.root_entry().clsid())?;
+    let entries = source
+        .walk()
+        .filter(|entry| !entry.is_root())
+        .map(|entry| (entry.path().to_path_buf(), entry.is_storage()))
+        .collect_vec();
+    for (path, is_storage) in entries {
+        if is_storage {
+            destination.create_storage(&path)?;
+            continue;
+        }
+        let mut reader = source.open_stream(&path)?;
+        let mut writer = destination.create_stream(&path)?;
+        std::io::copy(&mut reader, &mut writer).with_context(|| {
+            format!("Failed to copy reference MSI stream {path:?}")
+        })?;
+    }
+    destination.flush()?;
+    Ok(whimsi_msi::Package::open(destination.into_inner())?)
+}
+
+impl Default for MsiBuilder {
+    fn default() -> Self {
+        let empty_entries = SharedIdentifiers::default();
+        Self {
+            meta: None,
+            reproducibility: None,
+
+            // Non-table trackers
+            icon_information: Default::default(),
+            binary_information: Default::default(),
+            relative_actions: Default::default(),
+            current_feature: DEFAULT_FEATURE_IDENTIFIER.parse().unwrap(),
+            component_grouping: Default::default(),
+            shared_components: Default::default(),
+            component_key_paths: Default::default(),
+            major_upgrade: None,
+            pinned_component_guids: Default::default(),
+
+            // Non-tables that need access to all or generate entity IDs.
+            identifiers: empty_entries.clone(),
+            cabinets: Cabinets::new(empty_entries.clone()),
+
+            // Tables that don't have IDs for their entries.
+            property: Default::default(),
+            media: Default::default(),
+            feature_components: Default::default(),
+            condition: Default::default(),
+            create_folder: Default::default(),
+            msi_file_hash: Default::default(),
+            admin_execute_sequence: Default::default(),
+            admin_ui_sequence: Default::default(),
+            advt_execute_sequence: Default::default(),
+            install_execute_sequence: Default::default(),
+            install_ui_sequence: Default::default(),
+            launch_condition: Default::default(),
+            upgrade: Default::default(),
+            reg_locator: Default::default(),
+            dr_locator: Default::default(),
+            comp_locator: Default::default(),
+            ini_locator: Default::default(),
+            app_search: Default::default(),
+            ccp_search: Default::default(),
+            custom_action: Default::default(),
+            lock_permissions: Default::default(),
+
+            // Tables that can generate IDs for their entries and the IDs must
+            // be uniqe across the MSI.
+            component: ComponentTable::new(empty_entries.clone()),
+            directory: DirectoryTable::new(empty_entries.clone()),
+            feature: FeatureTable::new(empty_entries.clone()),
+            file: FileTable::new(empty_entries.clone()),
+            registry: RegistryTable::new(empty_entries.clone()),
+            remove_registry: RemoveRegistryTable::new(empty_entries.clone()),
+            environment: EnvironmentTable::new(empty_entries.clone()),
+            ini_file: IniFileTable::new(empty_entries.clone()),
+            remove_ini_file: RemoveIniFileTable::new(empty_entries.clone()),
+            remove_file: RemoveFileTable::new(empty_entries.clone()),
+            service_install: ServiceInstallTable::new(empty_entries.clone()),
+            service_control: ServiceControlTable::new(empty_entries.clone()),
+            shortcut: ShortcutTable::new(empty_entries.clone()),
+            icon: IconTable::new(empty_entries.clone()),
+            binary: BinaryTable::new(empty_entries.clone()),
+            signature: SignatureTable::new(empty_entries.clone()),
+        }
+    }
+}
+
+#[derive(Debug, thiserror::Error, PartialEq)]
+pub enum MsiBuilderError {
+    #[error(
+        "Property with identifier {identifier} not found in Property table"
+    )]
+    InvalidTargetDirChild { identifier: Identifier },
+    #[error("TARGETDIR cannot be a subdirectory")]
+    SubRootDirectory,
+    #[error(
+        "Directory with identifier {identifier} not found in Directory table"
+    )]
+    DirectoryNotFound { identifier: Identifier },
+    #[error("Directory with ID {identifier} already exists in Directory Table")]
+    DirectoryIdentifierConflict { identifier: Identifier },
+    #[error(
+        "Identifier {identifier} already exists for MSI. Identifiers must be unique."
+    )]
+    IdentifierConflict { identifier: Identifier },
+    #[error("No directory name could be found for path [{path}]")]
+    NoDirectoryName { path: PathBuf },
+    #[error("Invalid directory name found for path [{path}]")]
+    InvalidDirectoryName { path: PathBuf },
+}
+
+/// Deferred, rollback and commit custom actions only run when they are
+/// sequenced inside the installation script.
+fn ensure_in_script_sequence(
+    action: &Identifier,
+    sequence: i16,
+) -> anyhow::Result<()> {
+    let first = StandardAction::InstallInitialize as i16;
+    let last = StandardAction::InstallFinalize as i16;
+    ensure!(
+        first < sequence && sequence < last,
+        "Custom action [{action}] runs in the installation script so it must be sequenced between InstallInitialize ({first}) and InstallFinalize ({last})"
+    );
+    Ok(())
+}
+
+/// A registry value or key as it appears in component targets, such as
+/// `HKLM\Software\App\Name`.
+fn registry_target(
+    root: &RegistryRoot,
+    key: &RegPath,
+    name: Option<&Formatted>,
+) -> String {
+    let root = match root {
+        RegistryRoot::PerUserOrMachine => "HKMU",
+        RegistryRoot::ClassesRoot => "HKCR",
+        RegistryRoot::CurrentUser => "HKCU",
+        RegistryRoot::LocalMachine => "HKLM",
+        RegistryRoot::Users => "HKU",
+    };
+    let name = name.map(ToString::to_string).unwrap_or_default();
+    format!("{root}\\{key}\\{name}")
+}
+
+fn formatted_columns<T>(table: &T) -> Vec<&Formatted>
+where
+    T: MsiBuilderList,
+    T::ListValue: FormattedColumns,
+{
+    table
+        .entries()
+        .iter()
+        .flat_map(FormattedColumns::formatted_columns)
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use std::io::Read;
+    use std::str::FromStr;
+    use std::time::Duration;
+    use std::time::UNIX_EPOCH;
+
+    use assert_fs::TempDir;
+    use assert_fs::prelude::*;
+    use itertools::Itertools;
+    use test_case::test_case;
+
+    use super::MsiBuilder;
+    use crate::tables::app_search::system_search::FileSignature;
+    use crate::tables::app_search::system_search::SearchResult;
+    use crate::tables::app_search::system_search::SystemSearch;
+    use crate::tables::builder_list::MsiBuilderList;
+    use crate::tables::component::grouping::ComponentGrouping;
+    use crate::tables::component::grouping::ComponentKeyPath;
+    use crate::tables::custom_action::custom_action_type::CustomActionExecution;
+    use crate::tables::custom_action::custom_action_type::CustomActionKind;
+    use crate::tables::custom_action::custom_action_type::CustomActionType;
+    use crate::tables::directory::dao::DirectoryDao;
+    use crate::tables::directory::directory_identifier::DirectoryIdentifier;
+    use crate::tables::environment::environment_variable::EnvironmentScope;
+    use crate::tables::environment::environment_variable::EnvironmentValuePlacement;
+    use crate::tables::environment::environment_variable::EnvironmentVariable;
+    use crate::tables::feature::attributes::FeatureAttributes;
+    use crate::tables::feature::identifier::FeatureIdentifier;
+    use crate::tables::generic_sequence::action_identifier::ActionIdentifier;
+    use crate::tables::generic_sequence::scheduling::ActionPlacement;
+    use crate::tables::generic_sequence::scheduling::SequenceTable;
+    use crate::tables::ini_file::ini_entry::IniEntry;
+    use crate::tables::ini_file::ini_file_action::IniFileAction;
+    use crate::tables::ini_file::ini_file_action::RemoveIniFileAction;
+    use crate::tables::lock_permissions::lock_object::LockObject;
+    use crate::tables::lock_permissions::lock_permissions::LockPermissions;
+    use crate::tables::meta::MetaInformation;
+    use crate::tables::registry::registry_key_action::RegistryKeyAction;
+    use crate::tables::registry::registry_root::RegistryRoot;
+    use crate::tables::registry::registry_value::RegistryValue;
+    use crate::tables::remove_file::install_mode::RemoveFileInstallMode;
+    use crate::tables::upgrade::major_upgrade::MajorUpgradeOptions;
+    use crate::types::column::condition::Condition;
+    use crate::types::column::custom_source::CustomSource;
+    use crate::types::column::default_dir::DefaultDir;
+    use crate::types::column::filename::Filename;
+    use crate::types::column::guid::Guid;
+    use crate::types::column::identifier::Identifier;
+    use crate::types::column::identifier::ToIdentifier;
+    use crate::types::column::version::Version;
+    use crate::types::helpers::reproducibility::Reproducibility;
+    use crate::types::properties::system_folder::SystemFolder;
+    use crate::types::standard_action::StandardAction;
+
+    fn assert_send_sync<T: Send + Sync>() {}
+
+    #[test]
+    fn builder_is_send_and_sync() {
+        assert_send_sync::<MsiBuilder>();
+    }
+
+    #[test]
+    fn reproducible_builds_are_identical() {
+        let temp_dir = TempDir::new().unwrap();
+        temp_dir.child("b.txt").write_str("second").unwrap();
+        temp_dir.child("a.txt").write_str("first").unwrap();
+        temp_dir.child("sub/c.txt").write_str("third").unwrap();
+
+        let build = || {
+            let meta = MetaInformation::new(
+                whimsi_msi::PackageType::Installer,
+                "Reproducible".to_string(),
+            );
+            let reproducibility = Reproducibility::new(
+                "seed",
+                UNIX_EPOCH + Duration::from_secs(1_700_000_000),
+            );
+            let builder = MsiBuilder::default()
+                .with_meta(meta)
+                .with_reproducibility(reproducibility)
+                .with_path_contents(
+                    temp_dir.path(),
+                    SystemFolder::ProgramFilesFolder,
+                )
+                .unwrap();
+            let cursor = std::io::Cursor::new(Vec::new());
+            builder.build(cursor).unwrap().into_inner().unwrap().into_inner()
+        };
+
+        let first = build();
+        let second = build();
+        assert!(first == second, "Reproducible builds differ");
+
+        let package =
+            whimsi_msi::Package::open(std::io::Cursor::new(first)).unwrap();
+        let summary_info = package.summary_info();
+        assert_eq!(
+            summary_info.creation_time(),
+            Some(UNIX_EPOCH + Duration::from_secs(1_700_000_000))
+        );
+    }
+
+    #[test]
+    fn package_code_follows_contents() {
+        let temp_dir = TempDir::new().unwrap();
+        temp_dir.child("a.txt").write_str("first").unwrap();
+
+        let package_code = |contents: &str| {
+            temp_dir.child("b.txt").write_str(contents).unwrap();
+            let meta = MetaInformation::new(
+                whimsi_msi::PackageType::Installer,
+                "Reproducible".to_string(),
+            );
+            let reproducibility = Reproducibility::new(
+                "seed",
+                UNIX_EPOCH + Duration::from_secs(1_700_000_000),
+            );
+            let builder = MsiBuilder::default()
+                .with_meta(meta)
+                .with_reproducibility(reproducibility)
+                .with_path_contents(
+                    temp_dir.path(),
+                    SystemFolder::ProgramFilesFolder,
+                )
+                .unwrap();
+            let cursor = std::io::Cursor::new(Vec::new());
+            let data = builder
+                .build(cursor)
+                .unwrap()
+                .into_inner()
+                .unwrap()
+                .into_inner();
+            let package =
+                whimsi_msi::Package::open(std::io::Cursor::new(data)).unwrap();
+            package.summary_info().uuid().unwrap()
+        };
+
+        assert_eq!(package_code("second"), package_code("second"));
+        assert_ne!(package_code("second"), package_code("changed"));
+    }
+
+    #[test]
+    fn build_with_version_4_sectors() {
+        let temp_dir = TempDir::new().unwrap();
+        temp_dir.child("file.txt").write_str("contents").unwrap();
+        let meta = MetaInformation::new(
+            whimsi_msi::PackageType::Installer,
+            "Large".to_string(),
+        )
+        .with_cfb_version(whimsi_msi::CfbVersion::V4);
+        let builder = MsiBuilder::default()
+            .with_meta(meta)
+            .with_path_contents(
+                temp_dir.path(),
+                SystemFolder::ProgramFilesFolder,
+            )
+            .unwrap();
+        let cursor = std::io::Cursor::new(Vec::new());
+        let data =
+            builder.build(cursor).unwrap().into_inner().unwrap().into_inner();
+
+        let mut package =
+            whimsi_msi::Package::open(std::io::Cursor::new(data)).unwrap();
+        assert_eq!(package.cfb_version(), whimsi_msi::CfbVersion::V4);
+        assert_eq!(package.package_type(), whimsi_msi::PackageType::Installer);
+        let files = package
+            .select_rows(whimsi_msi::Select::table("File"))
+            .unwrap()
+            .count();
+        assert_eq!(files, 1);
+        assert!(package.has_table("InstallExecuteSequence"));
+    }
+
+    #[test_case("[#Missing.exe]", false; "missing file")]
+    #[test_case("[$MissingComponent]", false; "missing component")]
+    #[test_case("[TARGETDIR", false; "unclosed bracket")]
+    #[test_case("[TARGETDIR]{[~]}", true; "directory")]
+    #[test_case("[SOME_RUNTIME_PROPERTY]", true; "unknown property")]
+    fn formatted_references_are_validated(value: &str, valid: bool) {
+        let meta = MetaInformation::new(
+            whimsi_msi::PackageType::Installer,
+            "Formatted".to_string(),
+        );
+        let mut builder = MsiBuilder::default().with_meta(meta);
+        builder
+            .add_registry_value(
+                RegistryRoot::LocalMachine,
+                r"Software\Whimsi",
+                Some("Path"),
+                RegistryValue::String(value.to_string()),
+            )
+            .unwrap();
+        let cursor = std::io::Cursor::new(Vec::new());
+        assert_eq!(builder.build(cursor).is_ok(), valid);
+    }
+
+    #[test]
+    fn registry_value_has_registry_keypath() {
+        let meta = MetaInformation::new(
+            whimsi_msi::PackageType::Installer,
+            "Registry".to_string(),
+        );
+        let mut builder = MsiBuilder::default().with_meta(meta);
+        let registry_id = builder
+            .add_registry_value(
+                RegistryRoot::LocalMachine,
+                r"Software\Whimsi",
+                Some("Version"),
+                RegistryValue::Dword(1),
+            )
+            .unwrap();
+        let cursor = std::io::Cursor::new(Vec::new());
+        let data =
+            builder.build(cursor).unwrap().into_inner().unwrap().into_inner();
+
+        let mut package =
+            whimsi_msi::Package::open(std::io::Cursor::new(data)).unwrap();
+        let registry = package
+            .get_row("Registry", &[registry_id.to_string().into()])
+            .unwrap()
+            .unwrap();
+        assert_eq!(registry["Root"], whimsi_msi::Value::Int(2));
+        assert_eq!(
+            registry["Key"],
+            whimsi_msi::Value::from(r"Software\Whimsi")
+        );
+        assert_eq!(registry["Value"], whimsi_msi::Value::from("#1"));
+
+        let component_id = registry["Component_"].clone();
+        let component =
+            package.get_row("Component", &[component_id]).unwrap().unwrap();
+        assert_eq!(component["Attributes"], whimsi_msi::Value::Int(4));
+        assert_eq!(
+            component["KeyPath"],
+            whimsi_msi::Value::from(registry_id.to_string())
+        );
+        for action in ["RemoveRegistryValues", "WriteRegistryValues"] {
+            let row = package
+                .get_row(
+                    "InstallExecuteSequence",
+                    &[whimsi_msi::Value::from(action)],
+                )
+                .unwrap();
+            assert!(row.is_some(), "{action} isn't scheduled");
+        }
+    }
+
+    #[test]
+    fn reg_file_is_imported() {
+        let temp_dir = TempDir::new().unwrap();
+        let reg_file = temp_dir.child("settings.reg");
+        reg_file
+            .write_str(concat!(
+                "Windows Registry Editor Version 5.00\r\n",
+                "\r\n",
+                "[HKEY_CURRENT_USER\\Software\\Whimsi]\r\n",
+                "\"Theme\"=\"Dark\"\r\n",
+                "\"Legacy\"=-\r\n",
+                "\r\n",
+                "[-HKEY_CURRENT_USER\\Software\\OldWhimsi]\r\n",
+            ))
+            .unwrap();
+        let meta = MetaInformation::new(
+            whimsi_msi::PackageType::Installer,
+            "Registry".to_string(),
+        );
+        let mut builder = MsiBuilder::default().with_meta(meta);
+        let component_ids = builder.add_reg_file(reg_file.path()).unwrap();
+        assert_eq!(component_ids.len(), 1);
+        let cursor = std::io::Cursor::new(Vec::new());
+        let data =
+            builder.build(cursor).unwrap().into_inner().unwrap().into_inner();
+
+        let mut package =
+            whimsi_msi::Package::open(std::io::Cursor::new(data)).unwrap();
+        let registry = package
+            .select_rows(whimsi_msi::Select::table("Registry"))
+            .unwrap()
+            .collect::<Vec<_>>();
+        assert_eq!(registry.len(), 1);
+        assert_eq!(registry[0]["Root"], whimsi_msi::Value::Int(1));
+        assert_eq!(registry[0]["Value"], whimsi_msi::Value::from("Dark"));
+        let removals = package
+            .select_rows(whimsi_msi::Select::table("RemoveRegistry"))
+            .unwrap()
+            .map(|row| row["Name"].clone())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            removals,
+            vec![
+                whimsi_msi::Value::from("Legacy"),
+                whimsi_msi::Value::from("-")
+            ]
+        );
+        let component = package
+            .get_row("Component", &[component_ids[0].to_string().into()])
+            .unwrap()
+            .unwrap();
+        assert_eq!(component["KeyPath"], registry[0]["Registry"]);
+        let write_registry = package
+            .get_row(
+                "InstallExecuteSequence",
+                &[whimsi_msi::Value::from("WriteRegistryValues")],
+            )
+            .unwrap();
+        assert!(write_registry.is_some());
+    }
+
+    #[test]
+    fn reg_file_roots_get_their_own_components() {
+        let temp_dir = TempDir::new().unwrap();
+        let reg_file = temp_dir.child("settings.reg");
+        reg_file
+            .write_str(concat!(
+                "Windows Registry Editor Version 5.00\r\n",
+                "\r\n",
+                "[HKEY_CURRENT_USER\\Software\\Whimsi]\r\n",
+                "\"Theme\"=\"[Dark]\"\r\n",
+                "\r\n",
+                "[HKEY_LOCAL_MACHINE\\Software\\Whimsi]\r\n",
+                "\"Install{Dir}\"=\"C:\\\\a[b\"\r\n",
+            ))
+            .unwrap();
+        let meta = MetaInformation::new(
+            whimsi_msi::PackageType::Installer,
+            "Registry".to_string(),
+        );
+        let mut builder = MsiBuilder::default().with_meta(meta);
+        let component_ids = builder.add_reg_file(reg_file.path()).unwrap();
+        assert_eq!(component_ids.len(), 2);
+        let cursor = std::io::Cursor::new(Vec::new());
+        let data =
+            builder.build(cursor).unwrap().into_inner().unwrap().into_inner();
+
+        let mut package =
+            whimsi_msi::Package::open(std::io::Cursor::new(data)).unwrap();
+        let registry = package
+            .select_rows(whimsi_msi::Select::table("Registry"))
+            .unwrap()
+            .map(|row| {
+                (
+                    row["Root"].as_int().unwrap(),
+                    row["Name"].as_str().unwrap().to_string(),
+                    row["Value"].as_str().unwrap().to_string(),
+                    row["Component_"].as_str().unwrap().to_string(),
+                )
+            })
+            .sorted()
+            .collect::<Vec<_>>();
+        assert_eq!(
+            registry,
+            vec![
+                (
+                    1,
+                    "Theme".to_string(),
+                    r"[\[]Dark[\]]".to_string(),
+                    component_ids[0].to_string()
+                ),
+                (
+                    2,
+                    r"Install[\{]Dir[\}]".to_string(),
+                    r"C:\a[\[]b".to_string(),
+                    component_ids[1].to_string()
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn registry_cleanup_is_written() {
+        let meta = MetaInformation::new(
+            whimsi_msi::PackageType::Installer,
+            "Registry".to_string(),
+        );
+        let mut builder = MsiBuilder::default().with_meta(meta);
+        builder
+            .add_registry_key(
+                RegistryRoot::LocalMachine,
+                r"Software\Whimsi",
+                RegistryKeyAction::DeleteOnUninstall,
+            )
+            .unwrap();
+        let key_removal = builder
+            .add_registry_key_removal(
+                RegistryRoot::LocalMachine,
+                r"Software\Old",
+                None,
+            )
+            .unwrap();
+        let cleanup = MsiBuilderList::entries(&builder.remove_registry)
+            .iter()
+            .find(|dao| *dao.remove_registry() == key_removal)
+            .unwrap()
+            .component()
+            .clone();
+        builder
+            .add_registry_value_removal(
+                RegistryRoot::LocalMachine,
+                r"Software\Whimsi",
+                Some("Legacy"),
+                Some(cleanup.clone()),
+            )
+            .unwrap();
+        assert!(
+            builder
+                .add_registry_value_removal(
+                    RegistryRoot::LocalMachine,
+                    r"Software\Whimsi",
+                    Some("-"),
+                    None,
+                )
+                .is_err()
+        );
+        let cursor = std::io::Cursor::new(Vec::new());
+        let data =
+            builder.build(cursor).unwrap().into_inner().unwrap().into_inner();
+
+        let mut package =
+            whimsi_msi::Package::open(std::io::Cursor::new(data)).unwrap();
+        let registry = package
+            .select_rows(whimsi_msi::Select::table("Registry"))
+            .unwrap()
+            .collect::<Vec<_>>();
+        assert_eq!(registry.len(), 1);
+        assert_eq!(registry[0]["Name"], whimsi_msi::Value::from("-"));
+        assert_eq!(registry[0]["Value"], whimsi_msi::Value::Null);
+        let removals = package
+            .select_rows(whimsi_msi::Select::table("RemoveRegistry"))
+            .unwrap()
+            .map(|row| {
+                (
+                    row["Key"].clone(),
+                    row["Name"].clone(),
+                    row["Component_"].clone(),
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            removals,
+            vec![
+                (
+                    whimsi_msi::Value::from(r"Software\Old"),
+                    whimsi_msi::Value::from("-"),
+                    whimsi_msi::Value::from(cleanup.to_string())
+                ),
+                (
+                    whimsi_msi::Value::from(r"Software\Whimsi"),
+                    whimsi_msi::Value::from("Legacy"),
+                    whimsi_msi::Value::from(cleanup.to_string())
+                ),
+            ]
+        );
+        let remove_registry = package
+            .get_row(
+                "InstallExecuteSequence",
+                &[whimsi_msi::Value::from("RemoveRegistryValues")],
+            )
+            .unwrap();
+        assert!(remove_registry.is_some());
+    }
+
+    #[test]
+    fn custom_action_is_scheduled() {
+        let meta = MetaInformation::new(
+            whimsi_msi::PackageType::Installer,
+            "CustomAction".to_string(),
+        );
+        let mut builder = MsiBuilder::default().with_meta(meta);
+        let deferred = CustomActionType::new(CustomActionKind::VbScriptText)
+            .with_execution(CustomActionExecution::Deferred)
+            .with_impersonate(false);
+        builder
+            .add_custom_action(
+                "RunScript",
+                deferred,
+                None,
+                Some("MsgBox \"Hello\"".to_string().into()),
+                Some(Condition::from_str("NOT Installed").unwrap()),
+                ActionPlacement::Sequence(4001),
+            )
+            .unwrap();
+        assert!(
+            builder
+                .add_custom_action(
+                    "Late",
+                    deferred,
+                    None,
+                    None,
+                    None,
+                    ActionPlacement::Sequence(6700),
+                )
+                .is_err()
+        );
+        assert!(
+            builder
+                .add_custom_action(
+                    "InstallFiles",
+                    CustomActionType::new(CustomActionKind::JScriptText),
+                    None,
+                    None,
+                    None,
+                    ActionPlacement::Sequence(4002),
+                )
+                .is_err()
+        );
+        let cursor = std::io::Cursor::new(Vec::new());
+        let data =
+            builder.build(cursor).unwrap().into_inner().unwrap().into_inner();
+
+        let mut package =
+            whimsi_msi::Package::open(std::io::Cursor::new(data)).unwrap();
+        let custom_action = package
+            .get_row("CustomAction", &[whimsi_msi::Value::from("RunScript")])
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            custom_action["Type"],
+            whimsi_msi::Value::Int(38 | 0x400 | 0x800)
+        );
+        assert_eq!(custom_action["Source"], whimsi_msi::Value::Null);
+        let sequence = package
+            .get_row(
+                "InstallExecuteSequence",
+                &[whimsi_msi::Value::from("RunScript")],
+            )
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            sequence["Condition"],
+            whimsi_msi::Value::from("NOT Installed")
+        );
+        assert_eq!(sequence["Sequence"], whimsi_msi::Value::Int(4001));
+    }
+
+    #[test]
+    fn relative_actions_are_resolved() {
+        let meta = MetaInformation::new(
+            whimsi_msi::PackageType::Installer,
+            "Scheduling".to_string(),
+        );
+        let mut builder = MsiBuilder::default().with_meta(meta);
+        let deferred = CustomActionType::new(CustomActionKind::VbScriptText)
+            .with_execution(CustomActionExecution::Deferred);
+        // Placed relative to an action that is only added afterwards.
+        builder
+            .add_custom_action(
+                "Second",
+                deferred,
+                None,
+                None,
+                None,
+                ActionPlacement::After(ActionIdentifier::CustomAction(
+                    Identifier::from_str("First").unwrap(),
+                )),
+            )
+            .unwrap();
+        builder
+            .add_custom_action(
+                "First",
+                deferred,
+                None,
+                None,
+                None,
+                ActionPlacement::Before(StandardAction::InstallFinalize.into()),
+            )
+            .unwrap();
+        builder
+            .schedule_action(
+                SequenceTable::InstallExecute,
+                StandardAction::RemoveExistingProducts,
+                None,
+                ActionPlacement::After(StandardAction::InstallValidate.into()),
+            )
+            .unwrap();
+        assert!(
+            builder
+                .schedule_action(
+                    SequenceTable::InstallExecute,
+                    StandardAction::InstallFinalize,
+                    None,
+                    ActionPlacement::Sequence(7000),
+                )
+                .is_err()
+        );
+        let cursor = std::io::Cursor::new(Vec::new());
+        let data =
+            builder.build(cursor).unwrap().into_inner().unwrap().into_inner();
+
+        let mut package =
+            whimsi_msi::Package::open(std::io::Cursor::new(data)).unwrap();
+        let mut sequence_of = |action: &str| {
+            package
+                .get_row(
+                    "InstallExecuteSequence",
+                    &[whimsi_msi::Value::from(action)],
+                )
+                .unwrap()
+                .unwrap()["Sequence"]
+                .clone()
+        };
+        assert_eq!(sequence_of("First"), whimsi_msi::Value::Int(6598));
+        assert_eq!(sequence_of("Second"), whimsi_msi::Value::Int(6599));
+        assert_eq!(
+            sequence_of("RemoveExistingProducts"),
+            whimsi_msi::Value::Int(1401)
+        );
+    }
+
+    #[test]
+    fn relative_cycles_fail_the_build() {
+        let meta = MetaInformation::new(
+            whimsi_msi::PackageType::Installer,
+            "Cycle".to_string(),
+        );
+        let mut builder = MsiBuilder::default().with_meta(meta);
+        builder
+            .schedule_action(
+                SequenceTable::InstallUi,
+                StandardAction::ResolveSource,
+                None,
+                ActionPlacement::After(StandardAction::ScheduleReboot.into()),
+            )
+            .unwrap();
+        builder
+            .schedule_action(
+                SequenceTable::InstallUi,
+                StandardAction::ScheduleReboot,
+                None,
+                ActionPlacement::Before(StandardAction::ResolveSource.into()),
+            )
+            .unwrap();
+        let cursor = std::io::Cursor::new(Vec::new());
+        assert!(builder.build(cursor).is_err());
+    }
+
+    #[test]
+    fn binaries_are_embedded() {
+        let temp_dir = TempDir::new().unwrap();
+        let script = temp_dir.child("action.vbs");
+        script.write_str("MsgBox \"Hello\"").unwrap();
+        let meta = MetaInformation::new(
+            whimsi_msi::PackageType::Installer,
+            "Binary".to_string(),
+        );
+        let mut builder = MsiBuilder::default().with_meta(meta);
+        let dll_id = builder.add_binary(vec![0x4D, 0x5A, 0x90, 0x00]).unwrap();
+        let script_id = builder.add_binary(script.path()).unwrap();
+        assert!(
+            builder.add_binary(temp_dir.child("missing.dll").path()).is_err()
+        );
+        builder
+            .add_custom_action(
+                "CallDll",
+                CustomActionType::new(CustomActionKind::BinaryDll),
+                Some(CustomSource::Binary(dll_id.clone())),
+                Some("EntryPoint".to_string().into()),
+                None,
+                ActionPlacement::Sequence(1001),
+            )
+            .unwrap();
+        let cursor = std::io::Cursor::new(Vec::new());
+        let data =
+            builder.build(cursor).unwrap().into_inner().unwrap().into_inner();
+
+        let mut package =
+            whimsi_msi::Package::open(std::io::Cursor::new(data)).unwrap();
+        let binaries = package
+            .select_rows(whimsi_msi::Select::table("Binary"))
+            .unwrap()
+            .count();
+        assert_eq!(binaries, 2);
+        let read_stream = |package: &mut whimsi_msi::Package<_>, id: String| {
+            let mut data = Vec::new();
+            package
+                .read_stream(&format!("Binary.{id}"))
+                .unwrap()
+                .read_to_end(&mut data)
+                .unwrap();
+            data
+        };
+        assert_eq!(
+            read_stream(&mut package, dll_id.to_string()),
+            vec![0x4D, 0x5A, 0x90, 0x00]
+        );
+        assert_eq!(
+            read_stream(&mut package, script_id.to_string()),
+            b"MsgBox \"Hello\"".to_vec()
+        );
+        let custom_action = package
+            .get_row("CustomAction", &[whimsi_msi::Value::from("CallDll")])
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            custom_action["Source"],
+            whimsi_msi::Value::from(dll_id.to_string())
+        );
+    }
+
+    #[test]
+    fn components_are_added_to_the_current_feature() {
+        let temp_dir = TempDir::new().unwrap();
+        let readme = temp_dir.child("README.txt");
+        readme.write_str("docs").unwrap();
+        let meta = MetaInformation::new(
+            whimsi_msi::PackageType::Installer,
+            "Features".to_string(),
+        );
+        let mut builder = MsiBuilder::default().with_meta(meta);
+        let docs_dir = DirectoryIdentifier::from_str("DOCSDIR").unwrap();
+        builder
+            .add_directory_dao(DirectoryDao::new(
+                Filename::from_str("Docs").unwrap(),
+                docs_dir.clone(),
+                SystemFolder::ProgramFilesFolder,
+            ))
+            .unwrap();
+        let default = builder.current_feature().clone();
+        let docs = builder
+            .add_feature(
+                Some(&default),
+                "Documentation",
+                Some("Manuals"),
+                3,
+                Some(5),
+                FeatureAttributes::UI_DISALLOW_ABSENT,
+                Some(docs_dir.clone()),
+            )
+            .unwrap();
+        assert!(
+            builder
+                .add_feature(
+                    None,
+                    "Elsewhere",
+                    None,
+                    1,
+                    None,
+                    FeatureAttributes::default(),
+                    Some(DirectoryIdentifier::from_str("MISSINGDIR").unwrap()),
+                )
+                .is_err()
+        );
+        let missing = FeatureIdentifier::from_str("Missing").unwrap();
+        assert!(builder.set_current_feature(&missing).is_err());
+        assert!(
+            builder
+                .add_feature(
+                    Some(&missing),
+                    "Orphan",
+                    None,
+                    1,
+                    None,
+                    FeatureAttributes::default(),
+                    None,
+                )
+                .is_err()
+        );
+        builder.set_current_feature(&docs).unwrap();
+        builder
+            .add_file_path(readme.path(), SystemFolder::ProgramFilesFolder)
+            .unwrap();
+        builder
+            .add_feature_condition(
+                &docs,
+                0,
+                Condition::from_str("NOT INSTALL_DOCS").unwrap(),
+            )
+            .unwrap();
+        let cursor = std::io::Cursor::new(Vec::new());
+        let data =
+            builder.build(cursor).unwrap().into_inner().unwrap().into_inner();
+
+        let mut package =
+            whimsi_msi::Package::open(std::io::Cursor::new(data)).unwrap();
+        let feature = package
+            .get_row(
+                "Feature",
+                &[whimsi_msi::Value::from(docs.to_string().as_str())],
+            )
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            feature["Feature_Parent"],
+            whimsi_msi::Value::from(default.to_string().as_str())
+        );
+        assert_eq!(feature["Title"], whimsi_msi::Value::from("Documentation"));
+        assert_eq!(feature["Level"], whimsi_msi::Value::Int(3));
+        assert_eq!(feature["Display"], whimsi_msi::Value::Int(5));
+        assert_eq!(feature["Attributes"], whimsi_msi::Value::Int(16));
+        assert_eq!(feature["Directory_"], whimsi_msi::Value::from("DOCSDIR"));
+        let features = package
+            .select_rows(whimsi_msi::Select::table("FeatureComponents"))
+            .unwrap()
+            .map(|row| row["Feature_"].clone())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            features,
+            vec![whimsi_msi::Value::from(docs.to_string().as_str())]
+        );
+        let conditions = package
+            .select_rows(whimsi_msi::Select::table("Condition"))
+            .unwrap()
+            .collect::<Vec<_>>();
+        assert_eq!(conditions.len(), 1);
+        assert_eq!(conditions[0]["Level"], whimsi_msi::Value::Int(0));
+        assert_eq!(
+            conditions[0]["Condition"],
+            whimsi_msi::Value::from("NOT INSTALL_DOCS")
+        );
+    }
+
+    #[test]
+    fn component_guids_are_derived_from_targets() {
+        let temp_dir = TempDir::new().unwrap();
+        temp_dir.child("app.exe").write_str("app").unwrap();
+        temp_dir.child("sub/readme.txt").write_str("readme").unwrap();
+        let upgrade_code = "{B3E4E0A8-2F1C-4C43-9A57-0C5E7D0F3C11}";
+        let pinned =
+            Guid::from_str("{6F9619FF-8B86-D011-B42D-00C04FC964FF}").unwrap();
+
+        let build = |pin: Option<&str>| {
+            let meta = MetaInformation::new(
+                whimsi_msi::PackageType::Installer,
+                "Components".to_string(),
+            );
+            let mut builder = MsiBuilder::default()
+                .with_meta(meta)
+                .with_property("UpgradeCode", upgrade_code)
+                .unwrap()
+                .with_path_contents(
+                    temp_dir.path(),
+                    SystemFolder::ProgramFilesFolder,
+                )
+                .unwrap();
+            if let Some(pin) = pin {
+                builder.pin_component_guid(pin, pinned.clone());
             }
-            Dao::Feature(feature_dao) => {
-                IdGeneratorBuilderList::add(&mut self.feature, feature_dao)
+            let cursor = std::io::Cursor::new(Vec::new());
+            let data = builder
+                .build(cursor)
+                .map(|package| package.into_inner().unwrap().into_inner())?;
+            let mut package =
+                whimsi_msi::Package::open(std::io::Cursor::new(data)).unwrap();
+            let guids = package
+                .select_rows(whimsi_msi::Select::table("Component"))
+                .unwrap()
+                .map(|row| row["ComponentId"].clone())
+                .collect::<Vec<_>>();
+            anyhow::Ok(guids)
+        };
+
+        let first = build(None).unwrap();
+        assert_eq!(first, build(None).unwrap());
+        let namespace = uuid::Uuid::parse_str(upgrade_code).unwrap();
+        let expected = Guid::from(uuid::Uuid::new_v5(
+            &namespace,
+            b"[programfilesfolder]app.exe",
+        ));
+        assert!(first.contains(&whimsi_msi::Value::from(expected.to_string())));
+
+        let pinned_build =
+            build(Some("[ProgramFilesFolder]Sub\\README.txt")).unwrap();
+        assert!(
+            pinned_build.contains(&whimsi_msi::Value::from(pinned.to_string()))
+        );
+        assert!(
+            pinned_build
+                .contains(&whimsi_msi::Value::from(expected.to_string()))
+        );
+        assert!(build(Some("[ProgramFilesFolder]missing.txt")).is_err());
+    }
+
+    #[test]
+    fn registry_key_paths_are_validated_and_scheduled() {
+        let temp_dir = TempDir::new().unwrap();
+        temp_dir.child("a.txt").write_str("a").unwrap();
+
+        let build = |value: &str| {
+            let meta = MetaInformation::new(
+                whimsi_msi::PackageType::Installer,
+                "KeyPaths".to_string(),
+            );
+            let mut builder = MsiBuilder::default()
+                .with_meta(meta)
+                .with_component_grouping(ComponentGrouping::PerDirectory);
+            builder
+                .add_file_path(
+                    temp_dir.child("a.txt").path(),
+                    SystemFolder::ProgramFilesFolder,
+                )
+                .unwrap();
+            builder.set_component_key_path(
+                SystemFolder::ProgramFilesFolder,
+                ComponentKeyPath::Registry {
+                    root: RegistryRoot::CurrentUser,
+                    key: r"Software\Whimsi".to_string(),
+                    name: Some("Installed".to_string()),
+                    value: RegistryValue::String(value.to_string()),
+                },
+            );
+            builder
+                .add_custom_action(
+                    "AfterRegistry",
+                    CustomActionType::new(CustomActionKind::VbScriptText)
+                        .with_execution(CustomActionExecution::Deferred),
+                    None,
+                    Some("MsgBox \"Hello\"".to_string().into()),
+                    None,
+                    ActionPlacement::After(
+                        StandardAction::WriteRegistryValues.into(),
+                    ),
+                )
+                .unwrap();
+            let cursor = std::io::Cursor::new(Vec::new());
+            builder
+                .build(cursor)
+                .map(|package| package.into_inner().unwrap().into_inner())
+        };
+
+        let data = build("1").unwrap();
+        let mut package =
+            whimsi_msi::Package::open(std::io::Cursor::new(data)).unwrap();
+        let sequence = |package: &mut whimsi_msi::Package<_>, action: &str| {
+            package
+                .get_row(
+                    "InstallExecuteSequence",
+                    &[whimsi_msi::Value::from(action)],
+                )
+                .unwrap()
+                .unwrap()["Sequence"]
+                .as_int()
+                .unwrap()
+        };
+        assert!(
+            sequence(&mut package, "AfterRegistry")
+                > sequence(&mut package, "WriteRegistryValues")
+        );
+        assert!(build("[Broken").is_err());
+    }
+
+    #[test]
+    fn keypathless_component_guids_follow_contents() {
+        let upgrade_code = "{B3E4E0A8-2F1C-4C43-9A57-0C5E7D0F3C11}";
+        let build = |environment_first: bool| {
+            let meta = MetaInformation::new(
+                whimsi_msi::PackageType::Installer,
+                "Components".to_string(),
+            );
+            let mut builder = MsiBuilder::default()
+                .with_meta(meta)
+                .with_property("UpgradeCode", upgrade_code)
+                .unwrap();
+            let mut add_environment = |builder: &mut MsiBuilder| {
+                builder
+                    .add_environment_variable(EnvironmentVariable::new(
+                        "PATH",
+                        "[TARGETDIR]bin",
+                    ))
+                    .unwrap();
+            };
+            if environment_first {
+                add_environment(&mut builder);
             }
-            Dao::Shortcut(shortcut_dao) => {
-                IdGeneratorBuilderList::add(&mut self.shortcut, shortcut_dao)
+            builder
+                .add_registry_key_removal(
+                    RegistryRoot::LocalMachine,
+                    r"Software\Old",
+                    None,
+                )
+                .unwrap();
+            if !environment_first {
+                add_environment(&mut builder);
             }
-            Dao::ServiceInstall(dao) => {
-                IdGeneratorBuilderList::add(&mut self.service_install, dao)
+            let cursor = std::io::Cursor::new(Vec::new());
+            let data = builder
+                .build(cursor)
+                .unwrap()
+                .into_inner()
+                .unwrap()
+                .into_inner();
+            let mut package =
+                whimsi_msi::Package::open(std::io::Cursor::new(data)).unwrap();
+            package
+                .select_rows(whimsi_msi::Select::table("Component"))
+                .unwrap()
+                .map(|row| row["ComponentId"].clone())
+                .sorted()
+                .collect::<Vec<_>>()
+        };
+
+        let guids = build(true);
+        assert_eq!(guids, build(false));
+        let namespace = uuid::Uuid::parse_str(upgrade_code).unwrap();
+        for target in [&b"%path%"[..], &b"hklm\\software\\old\\-"[..]] {
+            let expected =
+                Guid::from(uuid::Uuid::new_v5(&namespace, target)).to_string();
+            assert!(guids.contains(&whimsi_msi::Value::from(expected)));
+        }
+    }
+
+    #[test]
+    fn files_are_grouped_per_directory() {
+        let temp_dir = TempDir::new().unwrap();
+        temp_dir.child("app.exe").write_str("app").unwrap();
+        temp_dir.child("a.txt").write_str("a").unwrap();
+        temp_dir.child("b.txt").write_str("b").unwrap();
+        let guide = temp_dir.child("guide.txt");
+        guide.write_str("guide").unwrap();
+
+        let builder = |key_path: ComponentKeyPath| {
+            let meta = MetaInformation::new(
+                whimsi_msi::PackageType::Installer,
+                "Grouping".to_string(),
+            );
+            let mut builder = MsiBuilder::default()
+                .with_meta(meta)
+                .with_component_grouping(ComponentGrouping::PerDirectory);
+            for name in ["app.exe", "a.txt", "b.txt"] {
+                builder
+                    .add_file_path(
+                        temp_dir.child(name).path(),
+                        SystemFolder::ProgramFilesFolder,
+                    )
+                    .unwrap();
             }
-            Dao::ServiceControl(dao) => {
-                IdGeneratorBuilderList::add(&mut self.service_control, dao)
+            let docs = builder
+                .add_directory("docs", SystemFolder::ProgramFilesFolder)
+                .unwrap();
+            builder.add_file_path(guide.path(), docs.clone()).unwrap();
+            builder.set_component_key_path(
+                SystemFolder::ProgramFilesFolder,
+                key_path,
+            );
+            builder.set_component_key_path(docs, ComponentKeyPath::Directory);
+            builder
+        };
+
+        let cursor = std::io::Cursor::new(Vec::new());
+        let data = builder(ComponentKeyPath::File("B.TXT".to_string()))
+            .build(cursor)
+            .unwrap()
+            .into_inner()
+            .unwrap()
+            .into_inner();
+        let mut package =
+            whimsi_msi::Package::open(std::io::Cursor::new(data)).unwrap();
+        let files = package
+            .select_rows(whimsi_msi::Select::table("File"))
+            .unwrap()
+            .map(|row| {
+                let name = row["FileName"].as_str().unwrap().to_string();
+                let name = name.split('|').last().unwrap().to_string();
+                (name, row["File"].clone(), row["Component_"].clone())
+            })
+            .collect::<Vec<_>>();
+        let file = |name: &str| {
+            files.iter().find(|(file, _, _)| file == name).unwrap().clone()
+        };
+        // The versioned file has its own component, the rest of the
+        // directory shares one.
+        assert_ne!(file("app.exe").2, file("a.txt").2);
+        assert_eq!(file("a.txt").2, file("b.txt").2);
+        let components = package
+            .select_rows(whimsi_msi::Select::table("Component"))
+            .unwrap()
+            .map(|row| (row["Component"].clone(), row["KeyPath"].clone()))
+            .collect::<Vec<_>>();
+        assert_eq!(components.len(), 3);
+        let key_path = |component: &whimsi_msi::Value| {
+            components.iter().find(|(id, _)| id == component).unwrap().1.clone()
+        };
+        assert_eq!(key_path(&file("app.exe").2), file("app.exe").1);
+        assert_eq!(key_path(&file("b.txt").2), file("b.txt").1);
+        assert_eq!(key_path(&file("guide.txt").2), whimsi_msi::Value::Null);
+        let create_folders = package
+            .select_rows(whimsi_msi::Select::table("CreateFolder"))
+            .unwrap()
+            .map(|row| row["Component_"].clone())
+            .collect::<Vec<_>>();
+        assert_eq!(create_folders, vec![file("guide.txt").2]);
+
+        let cursor = std::io::Cursor::new(Vec::new());
+        assert!(
+            builder(ComponentKeyPath::File("app.exe".to_string()))
+                .build(cursor)
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn major_upgrades_replace_older_versions() {
+        let upgrade_code =
+            Guid::from_str("{B3E4E0A8-2F1C-4C43-9A57-0C5E7D0F3C11}").unwrap();
+        let meta = MetaInformation::new(
+            whimsi_msi::PackageType::Installer,
+            "Upgrade".to_string(),
+        );
+        let builder = MsiBuilder::default()
+            .with_meta(meta)
+            .with_property("ProductVersion", "1.2.0")
+            .unwrap()
+            .with_property("SecureCustomProperties", "MYPROPERTY")
+            .unwrap()
+            .with_major_upgrade(
+                upgrade_code.clone(),
+                MajorUpgradeOptions::default(),
+            )
+            .unwrap();
+        assert!(
+            builder
+                .clone()
+                .with_major_upgrade(
+                    upgrade_code.clone(),
+                    MajorUpgradeOptions::default()
+                )
+                .is_err()
+        );
+        let cursor = std::io::Cursor::new(Vec::new());
+        let data =
+            builder.build(cursor).unwrap().into_inner().unwrap().into_inner();
+
+        let mut package =
+            whimsi_msi::Package::open(std::io::Cursor::new(data)).unwrap();
+        let upgrades = package
+            .select_rows(whimsi_msi::Select::table("Upgrade"))
+            .unwrap()
+            .map(|row| {
+                (
+                    row["VersionMin"].clone(),
+                    row["VersionMax"].clone(),
+                    row["Attributes"].clone(),
+                    row["ActionProperty"].clone(),
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            upgrades,
+            vec![
+                (
+                    whimsi_msi::Value::Null,
+                    whimsi_msi::Value::from("1.2.0"),
+                    whimsi_msi::Value::Int(1),
+                    whimsi_msi::Value::from("OLDERVERSIONDETECTED"),
+                ),
+                (
+                    whimsi_msi::Value::from("1.2.0"),
+                    whimsi_msi::Value::Null,
+                    whimsi_msi::Value::Int(2),
+                    whimsi_msi::Value::from("NEWERVERSIONDETECTED"),
+                ),
+            ]
+        );
+        let mut property = |name: &str| {
+            package
+                .get_row("Property", &[whimsi_msi::Value::from(name)])
+                .unwrap()
+                .unwrap()["Value"]
+                .clone()
+        };
+        assert_eq!(
+            property("UpgradeCode"),
+            whimsi_msi::Value::from(upgrade_code.to_string())
+        );
+        assert_eq!(
+            property("SecureCustomProperties"),
+            whimsi_msi::Value::from(
+                "MYPROPERTY;OLDERVERSIONDETECTED;NEWERVERSIONDETECTED"
+            )
+        );
+        let conditions = package
+            .select_rows(whimsi_msi::Select::table("LaunchCondition"))
+            .unwrap()
+            .map(|row| row["Condition"].clone())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            conditions,
+            vec![whimsi_msi::Value::from("NOT NEWERVERSIONDETECTED")]
+        );
+        for table in ["InstallUISequence", "InstallExecuteSequence"] {
+            for action in ["FindRelatedProducts", "LaunchConditions"] {
+                assert!(
+                    package
+                        .get_row(table, &[whimsi_msi::Value::from(action)])
+                        .unwrap()
+                        .is_some(),
+                    "{action} is not in {table}"
+                );
             }
-            Dao::Icon(dao) => IdGeneratorBuilderList::add(&mut self.icon, dao),
-            Dao::Property(dao) => self.property.add(dao),
-            Dao::Media(dao) => self.media.add(dao),
-            Dao::MsiFileHash(dao) => self.msi_file_hash.add(dao),
-            Dao::FeatureComponents(dao) => self.feature_components.add(dao),
-            Dao::LockPermissions(dao) => self.lock_permissions.add(dao),
         }
+        let remove = package
+            .get_row(
+                "InstallExecuteSequence",
+                &[whimsi_msi::Value::from("RemoveExistingProducts")],
+            )
+            .unwrap()
+            .unwrap();
+        assert_eq!(remove["Sequence"], whimsi_msi::Value::Int(1401));
     }
-}
 
-impl Default for MsiBuilder {
-    fn default() -> Self {
-        let empty_entries = Rc::new(RefCell::new(Vec::new()));
-        Self {
-            meta: None,
+    #[test]
+    fn major_upgrades_need_matching_upgrade_codes() {
+        let upgrade_code =
+            Guid::from_str("{B3E4E0A8-2F1C-4C43-9A57-0C5E7D0F3C11}").unwrap();
+        let mut builder = MsiBuilder::default()
+            .with_property(
+                "UpgradeCode",
+                "{6F9619FF-8B86-D011-B42D-00C04FC964FF}",
+            )
+            .unwrap();
+        assert!(
+            builder
+                .add_major_upgrade(upgrade_code, MajorUpgradeOptions::default())
+                .is_err()
+        );
+    }
 
-            // Non-table trackers
-            icon_information: Default::default(),
+    #[test]
+    fn launch_conditions_are_written() {
+        let meta = MetaInformation::new(
+            whimsi_msi::PackageType::Installer,
+            "Launch conditions".to_string(),
+        );
+        let mut builder = MsiBuilder::default().with_meta(meta);
+        builder
+            .require_minimum_windows_version(603, "Windows 8.1 is required.")
+            .unwrap();
+        builder.require_64_bit_windows("64-bit Windows is required.").unwrap();
+        builder
+            .require_admin_privileges("Administrator rights are required.")
+            .unwrap();
+        assert!(
+            builder
+                .add_launch_condition(
+                    Condition::from_str("Privileged").unwrap(),
+                    "Duplicate",
+                )
+                .is_err()
+        );
+        let cursor = std::io::Cursor::new(Vec::new());
+        let data =
+            builder.build(cursor).unwrap().into_inner().unwrap().into_inner();
 
-            // Non-tables that need access to all or generate entity IDs.
-            identifiers: empty_entries.clone(),
-            cabinets: Cabinets::new(empty_entries.clone()),
+        let mut package =
+            whimsi_msi::Package::open(std::io::Cursor::new(data)).unwrap();
+        let conditions = package
+            .select_rows(whimsi_msi::Select::table("LaunchCondition"))
+            .unwrap()
+            .map(|row| row["Condition"].as_str().unwrap().to_string())
+            .sorted()
+            .collect::<Vec<_>>();
+        assert_eq!(
+            conditions,
+            vec![
+                "Installed OR VersionNT >= 603",
+                "Installed OR VersionNT64",
+                "Privileged",
+            ]
+        );
+        for table in ["InstallUISequence", "InstallExecuteSequence"] {
+            let launch_conditions = package
+                .get_row(table, &[whimsi_msi::Value::from("LaunchConditions")])
+                .unwrap()
+                .unwrap();
+            assert_eq!(
+                launch_conditions["Sequence"],
+                whimsi_msi::Value::Int(100)
+            );
+        }
+    }
 
-            // Tables that don't have IDs for their entries.
-            property: Default::default(),
-            media: Default::default(),
-            feature_components: Default::default(),
-            msi_file_hash: Default::default(),
-            admin_execute_sequence: Default::default(),
-            admin_ui_sequence: Default::default(),
-            advt_execute_sequence: Default::default(),
-            install_execute_sequence: Default::default(),
-            install_ui_sequence: Default::default(),
-            signature: Default::default(),
-            launch_condition: Default::default(),
-            binary: Default::default(),
-            reg_locator: Default::default(),
-            app_search: Default::default(),
-            custom_action: Default::default(),
-            lock_permissions: Default::default(),
+    #[test]
+    fn searches_are_linked_through_app_search() {
+        let meta = MetaInformation::new(
+            whimsi_msi::PackageType::Installer,
+            "Searches".to_string(),
+        );
+        let mut builder = MsiBuilder::default().with_meta(meta);
+        let install_dir = builder
+            .add_search(
+                "PREVIOUSDIR",
+                SystemSearch::Registry {
+                    root: RegistryRoot::LocalMachine,
+                    key: "Software\\Example".to_string(),
+                    name: Some("InstallDir".to_string()),
+                    result: SearchResult::Directory,
+                    win64: true,
+                },
+            )
+            .unwrap();
+        builder
+            .add_search(
+                "EXAMPLEEXE",
+                SystemSearch::Directory {
+                    parent: Some(install_dir.clone()),
+                    path: "bin".to_string(),
+                    depth: 1,
+                    file: Some(
+                        FileSignature::new("example.exe")
+                            .with_min_version(Some(
+                                Version::from_str("2.0").unwrap(),
+                            ))
+                            .with_min_size(Some(1024)),
+                    ),
+                },
+            )
+            .unwrap();
+        builder
+            .add_search(
+                "RUNTIMEDIR",
+                SystemSearch::Component {
+                    component_id: Guid::from_str(
+                        "{B3E4E0A8-2F1C-4C43-9A57-0C5E7D0F3C11}",
+                    )
+                    .unwrap(),
+                    result: SearchResult::Directory,
+                },
+            )
+            .unwrap();
+        assert!(builder.add_ccp_launch_condition("Upgrade only").is_err());
+        builder
+            .add_ccp_search(SystemSearch::Ini {
+                file_name: "example.ini".to_string(),
+                section: "Install".to_string(),
+                key: "Version".to_string(),
+                field: 0,
+                result: SearchResult::Raw,
+            })
+            .unwrap();
+        builder.add_ccp_launch_condition("Upgrade only").unwrap();
+        assert!(
+            builder
+                .add_search(
+                    "lowercase",
+                    SystemSearch::Component {
+                        component_id: Guid::from_str(
+                            "{B3E4E0A8-2F1C-4C43-9A57-0C5E7D0F3C11}"
+                        )
+                        .unwrap(),
+                        result: SearchResult::Directory,
+                    }
+                )
+                .is_err()
+        );
+        assert!(
+            builder
+                .add_search(
+                    "MISSINGPARENT",
+                    SystemSearch::Directory {
+                        parent: Some("MISSING".parse().unwrap()),
+                        path: "bin".to_string(),
+                        depth: 0,
+                        file: None,
+                    }
+                )
+                .is_err()
+        );
+        let cursor = std::io::Cursor::new(Vec::new());
+        let data =
+            builder.build(cursor).unwrap().into_inner().unwrap().into_inner();
 
-            // Tables that can generate IDs for their entries and the IDs must
-            // be uniqe across the MSI.
-            component: ComponentTable::new(empty_entries.clone()),
-            directory: DirectoryTable::new(empty_entries.clone()),
-            feature: FeatureTable::new(empty_entries.clone()),
-            file: FileTable::new(empty_entries.clone()),
-            registry: RegistryTable::new(empty_entries.clone()),
-            service_install: ServiceInstallTable::new(empty_entries.clone()),
-            service_control: ServiceControlTable::new(empty_entries.clone()),
-            shortcut: ShortcutTable::new(empty_entries.clone()),
-            icon: IconTable::new(empty_entries.clone()),
+        let mut package =
+            whimsi_msi::Package::open(std::io::Cursor::new(data)).unwrap();
+        let properties = package
+            .select_rows(whimsi_msi::Select::table("AppSearch"))
+            .unwrap()
+            .map(|row| row["Property"].as_str().unwrap().to_string())
+            .sorted()
+            .collect::<Vec<_>>();
+        assert_eq!(properties, vec!["EXAMPLEEXE", "PREVIOUSDIR", "RUNTIMEDIR"]);
+        let reg_locator = package
+            .get_row(
+                "RegLocator",
+                &[whimsi_msi::Value::from(install_dir.to_string())],
+            )
+            .unwrap()
+            .unwrap();
+        assert_eq!(reg_locator["Type"], whimsi_msi::Value::Int(0x10));
+        let signatures = package
+            .select_rows(whimsi_msi::Select::table("Signature"))
+            .unwrap()
+            .collect::<Vec<_>>();
+        assert_eq!(signatures.len(), 1);
+        assert_eq!(signatures[0]["MinVersion"].as_str(), Some("2.0"));
+        for (table, rows) in [
+            ("DrLocator", 1),
+            ("CompLocator", 1),
+            ("IniLocator", 1),
+            ("CCPSearch", 1),
+        ] {
+            assert_eq!(
+                package
+                    .select_rows(whimsi_msi::Select::table(table))
+                    .unwrap()
+                    .count(),
+                rows,
+                "{table}"
+            );
+        }
+        let secured = package
+            .get_row(
+                "Property",
+                &[whimsi_msi::Value::from("SecureCustomProperties")],
+            )
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            secured["Value"].as_str(),
+            Some("PREVIOUSDIR;EXAMPLEEXE;RUNTIMEDIR")
+        );
+        for (table, searches) in [
+            (
+                "InstallUISequence",
+                vec![
+                    ("AppSearch", 50, None),
+                    ("CCPSearch", 500, Some("NOT Installed")),
+                    (
+                        "RMCCPSearch",
+                        600,
+                        Some("NOT Installed AND NOT CCP_SUCCESS AND CCP_DRIVE"),
+                    ),
+                ],
+            ),
+            (
+                "InstallExecuteSequence",
+                vec![
+                    ("AppSearch", 50, None),
+                    ("CCPSearch", 500, Some("NOT Installed")),
+                ],
+            ),
+        ] {
+            let rows = package
+                .select_rows(whimsi_msi::Select::table(table))
+                .unwrap()
+                .map(|row| {
+                    (
+                        row["Action"].as_str().unwrap().to_string(),
+                        row["Sequence"].as_int().unwrap(),
+                        row["Condition"].as_str().map(str::to_string),
+                    )
+                })
+                .collect::<Vec<_>>();
+            let last_search = searches.last().unwrap().1;
+            for (action, sequence, condition) in searches {
+                assert!(
+                    rows.contains(&(
+                        action.to_string(),
+                        sequence,
+                        condition.map(str::to_string)
+                    )),
+                    "{action} in {table}"
+                );
+            }
+            // Removable media can only be searched with a user interface.
+            assert_eq!(
+                rows.iter().any(|(action, _, _)| action == "RMCCPSearch"),
+                table == "InstallUISequence"
+            );
+            let (_, launch_conditions, _) = rows
+                .iter()
+                .find(|(action, _, _)| action == "LaunchConditions")
+                .unwrap();
+            assert!(*launch_conditions > last_search, "{table}");
         }
+        let launch_condition = package
+            .get_row(
+                "LaunchCondition",
+                &[whimsi_msi::Value::from("Installed OR CCP_SUCCESS")],
+            )
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            launch_condition["Description"].as_str(),
+            Some("Upgrade only")
+        );
     }
-}
 
-#[derive(Debug, thiserror::Error, PartialEq)]
-pub enum MsiBuilderError {
-    #[error(
-        "Property with identifier {identifier} not found in Property table"
-    )]
-    InvalidTargetDirChild { identifier: Identifier },
-    #[error("TARGETDIR cannot be a subdirectory")]
-    SubRootDirectory,
-    #[error(
-        "Directory with identifier {identifier} not found in Directory table"
-    )]
-    DirectoryNotFound { identifier: Identifier },
-    #[error("Directory with ID {identifier} already exists in Directory Table")]
-    DirectoryIdentifierConflict { identifier: Identifier },
-    #[error(
-        "Identifier {identifier} already exists for MSI. Identifiers must be unique."
-    )]
-    IdentifierConflict { identifier: Identifier },
-    #[error("No directory name could be found for path [{path}]")]
-    NoDirectoryName { path: PathBuf },
-    #[error("Invalid directory name found for path [{path}]")]
-    InvalidDirectoryName { path: PathBuf },
+    #[test]
+    fn environment_variables_are_written() {
+        let meta = MetaInformation::new(
+            whimsi_msi::PackageType::Installer,
+            "Environment".to_string(),
+        );
+        let mut builder = MsiBuilder::default().with_meta(meta);
+        builder
+            .add_environment_variable(
+                EnvironmentVariable::new("PATH", "[TARGETDIR]bin")
+                    .with_placement(EnvironmentValuePlacement::Append)
+                    .with_scope(EnvironmentScope::System),
+            )
+            .unwrap();
+        builder
+            .add_environment_variable(EnvironmentVariable::removal("OLD_HOME"))
+            .unwrap();
+        assert!(
+            builder
+                .add_environment_variable(EnvironmentVariable::new(
+                    "=BAD", "value"
+                ))
+                .is_err()
+        );
+        let cursor = std::io::Cursor::new(Vec::new());
+        let data =
+            builder.build(cursor).unwrap().into_inner().unwrap().into_inner();
+
+        let mut package =
+            whimsi_msi::Package::open(std::io::Cursor::new(data)).unwrap();
+        let variables = package
+            .select_rows(whimsi_msi::Select::table("Environment"))
+            .unwrap()
+            .map(|row| {
+                (
+                    row["Name"].as_str().unwrap().to_string(),
+                    row["Value"].as_str().map(str::to_string),
+                )
+            })
+            .sorted()
+            .collect::<Vec<_>>();
+        assert_eq!(
+            variables,
+            vec![
+                ("!OLD_HOME".to_string(), None),
+                ("=-*PATH".to_string(), Some("[~];[TARGETDIR]bin".to_string())),
+            ]
+        );
+        for (action, sequence) in [
+            ("RemoveEnvironmentStrings", 3300),
+            ("WriteEnvironmentStrings", 5200),
+        ] {
+            let row = package
+                .get_row(
+                    "InstallExecuteSequence",
+                    &[whimsi_msi::Value::from(action)],
+                )
+                .unwrap()
+                .unwrap();
+            assert_eq!(row["Sequence"], whimsi_msi::Value::Int(sequence));
+        }
+    }
+
+    #[test]
+    fn ini_entries_are_written() {
+        let meta = MetaInformation::new(
+            whimsi_msi::PackageType::Installer,
+            "INI files".to_string(),
+        );
+        let mut builder = MsiBuilder::default().with_meta(meta);
+        let entry = |key: &str| {
+            IniEntry::new(
+                Some(SystemFolder::TARGETDIR.to_identifier().into()),
+                "legacy.ini".to_string(),
+                "Settings".to_string(),
+                key.to_string(),
+            )
+        };
+        let ini_file_id = builder
+            .add_ini_entry(
+                entry("Home"),
+                "[TARGETDIR]",
+                IniFileAction::AddLine,
+                None,
+            )
+            .unwrap();
+        let component_id =
+            MsiBuilderList::entries(&builder.ini_file)[0].component().clone();
+        builder
+            .add_ini_entry(
+                entry("Plugins"),
+                "whimsi",
+                IniFileAction::AddTag,
+                Some(component_id.clone()),
+            )
+            .unwrap();
+        builder
+            .add_ini_entry_removal(
+                entry("Obsolete"),
+                RemoveIniFileAction::RemoveLine,
+                None,
+                Some(component_id.clone()),
+            )
+            .unwrap();
+        assert!(
+            builder
+                .add_ini_entry_removal(
+                    entry("Plugins"),
+                    RemoveIniFileAction::RemoveTag,
+                    None,
+                    None,
+                )
+                .is_err()
+        );
+        assert!(
+            builder
+                .add_ini_entry(
+                    entry("Home"),
+                    "value",
+                    IniFileAction::AddLine,
+                    Some("MISSING".parse().unwrap()),
+                )
+                .is_err()
+        );
+        let cursor = std::io::Cursor::new(Vec::new());
+        let data =
+            builder.build(cursor).unwrap().into_inner().unwrap().into_inner();
+
+        let mut package =
+            whimsi_msi::Package::open(std::io::Cursor::new(data)).unwrap();
+        let home = package
+            .get_row(
+                "IniFile",
+                &[whimsi_msi::Value::from(ini_file_id.to_string())],
+            )
+            .unwrap()
+            .unwrap();
+        assert_eq!(home["DirProperty"].as_str(), Some("TARGETDIR"));
+        assert_eq!(home["Value"].as_str(), Some("[TARGETDIR]"));
+        assert_eq!(home["Action"], whimsi_msi::Value::Int(0));
+        let mut components = Vec::new();
+        for table in ["IniFile", "RemoveIniFile"] {
+            components.extend(
+                package
+                    .select_rows(whimsi_msi::Select::table(table))
+                    .unwrap()
+                    .map(|row| row["Component_"].as_str().unwrap().to_string()),
+            );
+        }
+        assert_eq!(components, vec![component_id.to_string(); 3]);
+        for (action, sequence) in
+            [("RemoveIniValues", 3100), ("WriteIniValues", 5100)]
+        {
+            let row = package
+                .get_row(
+                    "InstallExecuteSequence",
+                    &[whimsi_msi::Value::from(action)],
+                )
+                .unwrap()
+                .unwrap();
+            assert_eq!(row["Sequence"], whimsi_msi::Value::Int(sequence));
+        }
+    }
+
+    #[test]
+    fn empty_and_cleanup_directories_are_written() {
+        let temp_dir = TempDir::new().unwrap();
+        temp_dir.child("app.exe").write_str("app").unwrap();
+        temp_dir.child("data").create_dir_all().unwrap();
+
+        let meta = MetaInformation::new(
+            whimsi_msi::PackageType::Installer,
+            "Folders".to_string(),
+        );
+        let mut builder = MsiBuilder::default().with_meta(meta);
+        builder
+            .add_path_contents(
+                temp_dir.path(),
+                SystemFolder::ProgramFilesFolder,
+            )
+            .unwrap();
+        let data = builder
+            .directory()
+            .entry_with_name(&DefaultDir::Filename("data".parse().unwrap()))
+            .unwrap()
+            .directory()
+            .clone();
+        let logs = builder
+            .add_directory("logs", SystemFolder::ProgramFilesFolder)
+            .unwrap();
+        let cleanup = builder.add_directory_cleanup(logs.clone()).unwrap();
+        builder
+            .add_file_removal(
+                SystemFolder::ProgramFilesFolder,
+                Some("*.tmp"),
+                RemoveFileInstallMode::OnBoth,
+                Some(cleanup.clone()),
+            )
+            .unwrap();
+        builder
+            .add_lock_permissions(
+                LockObject::CreateFolder(logs.clone()),
+                "Users".to_string().into(),
+                LockPermissions::WRITE,
+            )
+            .unwrap();
+        assert!(
+            builder
+                .add_file_removal(
+                    logs.clone(),
+                    Some("logs/*.log"),
+                    RemoveFileInstallMode::OnUninstall,
+                    None,
+                )
+                .is_err()
+        );
+        let cursor = std::io::Cursor::new(Vec::new());
+        let msi =
+            builder.build(cursor).unwrap().into_inner().unwrap().into_inner();
+
+        let mut package =
+            whimsi_msi::Package::open(std::io::Cursor::new(msi)).unwrap();
+        let folders = package
+            .select_rows(whimsi_msi::Select::table("CreateFolder"))
+            .unwrap()
+            .map(|row| row["Directory_"].as_str().unwrap().to_string())
+            .sorted()
+            .collect::<Vec<_>>();
+        let mut expected = vec![data.to_string(), logs.to_string()];
+        expected.sort();
+        assert_eq!(folders, expected);
+        let removals = package
+            .select_rows(whimsi_msi::Select::table("RemoveFile"))
+            .unwrap()
+            .map(|row| {
+                (
+                    row["DirProperty"].as_str().unwrap().to_string(),
+                    row["FileName"].as_str().map(str::to_string),
+                    row["InstallMode"].as_int().unwrap(),
+                    row["Component_"].as_str().unwrap().to_string(),
+                )
+            })
+            .sorted()
+            .collect::<Vec<_>>();
+        let mut expected = vec![
+            (logs.to_string(), None, 2, cleanup.to_string()),
+            (logs.to_string(), Some("*".to_string()), 2, cleanup.to_string()),
+            (
+                "ProgramFilesFolder".to_string(),
+                Some("*.tmp".to_string()),
+                3,
+                cleanup.to_string(),
+            ),
+        ];
+        expected.sort();
+        assert_eq!(removals, expected);
+        for (action, sequence) in [
+            ("RemoveFiles", 3500),
+            ("RemoveFolders", 3600),
+            ("CreateFolders", 3700),
+        ] {
+            let row = package
+                .get_row(
+                    "InstallExecuteSequence",
+                    &[whimsi_msi::Value::from(action)],
+                )
+                .unwrap()
+                .unwrap();
+            assert_eq!(row["Sequence"], whimsi_msi::Value::Int(sequence));
+        }
+        let permissions = package
+            .select_rows(whimsi_msi::Select::table("LockPermissions"))
+            .unwrap()
+            .map(|row| {
+                (
+                    row["LockObject"].as_str().unwrap().to_string(),
+                    row["Table"].as_str().unwrap().to_string(),
+                    row["User"].as_str().unwrap().to_string(),
+                    row["Permission"].as_int(),
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            permissions,
+            vec![(
+                logs.to_string(),
+                "CreateFolder".to_string(),
+                "Users".to_string(),
+                Some(LockPermissions::WRITE as i32),
+            )]
+        );
+    }
 }
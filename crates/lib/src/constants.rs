@@ -22,6 +22,27 @@ pub const REGISTRY_VALUE_MAX_LEN: usize = 0;
 pub const REGISTRY_NAME_MAX_LEN: usize = 255;
 pub const REGPATH_MAX_LEN: usize = 255;
 
+// Found here: https://learn.microsoft.com/en-us/windows/win32/msi/signature-table
+pub const SIGNATURE_VERSION_MAX_LEN: usize = 20;
+
+// Found here: https://learn.microsoft.com/en-us/windows/win32/msi/inilocator-table
+pub const INI_SECTION_MAX_LEN: usize = 96;
+pub const INI_KEY_MAX_LEN: usize = 128;
+
+// Found here: https://learn.microsoft.com/en-us/windows/win32/msi/inifile-table
+pub const INI_VALUE_MAX_LEN: usize = 255;
+
+// Found here: https://learn.microsoft.com/en-us/windows/win32/msi/environment-table
+pub const ENVIRONMENT_NAME_MAX_LEN: usize = 255;
+pub const ENVIRONMENT_VALUE_MAX_LEN: usize = 255;
+pub const DEFAULT_ENVIRONMENT_SEPARATOR: char = ';';
+
+// Found here: https://learn.microsoft.com/en-us/windows/win32/msi/reglocator-table
+pub const REG_LOCATOR_TYPE_64_BIT: i16 = 0x10;
+
+// Found here: https://learn.microsoft.com/en-us/windows/win32/msi/removeregistry-table
+pub const REMOVE_REGISTRY_KEY_NAME: &str = "-";
+
 // Found here: https://learn.microsoft.com/en-us/windows/win32/msi/filename
 pub const SHORT_FILENAME_MAX_LEN: usize = 8;
 // TODO: Move filename invalid character array list here.
@@ -45,6 +66,11 @@ pub const FILE_IDENTIFIER_PREFIX: &str = "FILE_";
 pub const MEDIA_IDENTIFIER_PREFIX: &str = "MEDIA_";
 pub const PROPERTY_IDENTIFIER_PREFIX: &str = "PROPERTY_";
 pub const REGISTRY_IDENTIFIER_PREFIX: &str = "REGISTRY_";
+pub const REMOVEREGISTRY_IDENTIFIER_PREFIX: &str = "REMOVEREG_";
+pub const ENVIRONMENT_IDENTIFIER_PREFIX: &str = "ENVIRONMENT_";
+pub const INIFILE_IDENTIFIER_PREFIX: &str = "INIFILE_";
+pub const REMOVEINIFILE_IDENTIFIER_PREFIX: &str = "REMOVEINI_";
+pub const REMOVEFILE_IDENTIFIER_PREFIX: &str = "REMOVEFILE_";
 pub const SIGNATURE_IDENTIFIER_PREFIX: &str = "SIGNATURE_";
 pub const BINARY_IDENTIFIER_PREFIX: &str = "BINARY_";
 pub const SERVICEINSTALL_IDENTIFIER_PREFIX: &str = "SERVICEINST_";
@@ -52,6 +78,36 @@ pub const SERVICECONTROL_IDENTIFIER_PREFIX: &str = "SERVICECTRL_";
 pub const SHORTCUT_IDENTIFIER_PREFIX: &str = "SHORTCUT_";
 pub const ICON_IDENTIFIER_PREFIX: &str = "ICON_";
 
+// Found here: https://reproducible-builds.org/specs/source-date-epoch/
+pub const SOURCE_DATE_EPOCH_VARIABLE: &str = "SOURCE_DATE_EPOCH";
+// Seconds since the Unix epoch for 1980-01-01 00:00:00 UTC, the earliest date
+// that can be stored in a cabinet file. Used for reproducible builds when no
+// `SOURCE_DATE_EPOCH` is given.
+pub const DEFAULT_REPRODUCIBLE_TIMESTAMP: u64 = 315_532_800;
+// Names that reproducible GUIDs are derived from.
+pub const PACKAGE_CODE_GUID_NAME: &str = "PackageCode";
+pub const PRODUCT_CODE_GUID_NAME: &str = "ProductCode";
+pub const COMPONENT_ID_GUID_NAME: &str = "ComponentId";
+// Component GUIDs are derived from this property when it is set.
+pub const UPGRADE_CODE_PROPERTY: &str = "UpgradeCode";
+pub const PRODUCT_VERSION_PROPERTY: &str = "ProductVersion";
+pub const SECURE_CUSTOM_PROPERTIES_PROPERTY: &str = "SecureCustomProperties";
+// Set by `FindRelatedProducts` to the installed versions a major upgrade
+// replaces, or the newer versions that block it.
+pub const OLDER_VERSION_DETECTED_PROPERTY: &str = "OLDERVERSIONDETECTED";
+pub const NEWER_VERSION_DETECTED_PROPERTY: &str = "NEWERVERSIONDETECTED";
+pub const DEFAULT_DOWNGRADE_ERROR_MESSAGE: &str =
+    "A newer version of [ProductName] is already installed.";
+// Set by `CCPSearch` and `RMCCPSearch` when a qualifying product is found.
+// `RMCCPSearch` searches the removable drive in `CCP_DRIVE`.
+pub const CCP_SUCCESS_PROPERTY: &str = "CCP_SUCCESS";
+pub const CCP_DRIVE_PROPERTY: &str = "CCP_DRIVE";
+
+// Extensions of files that carry a version resource. Each of these is the
+// key path of its own component.
+pub const VERSIONED_FILE_EXTENSIONS: &[&str] =
+    &["cpl", "dll", "drv", "exe", "ocx", "scr", "sys"];
+
 // Default identifiers
 pub const DEFAULT_CABINET_IDENTIFIER: &str = "DEFAULT_CABINET";
 pub const DEFAULT_FEATURE_IDENTIFIER: &str = "DEFAULT_FEATURE";
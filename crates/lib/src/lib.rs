@@ -23,6 +23,9 @@
 
 use types::column::identifier::Identifier;
 
+// Lets code generated by `whimsi_macros` refer to this crate by name.
+extern crate self as whimsi_lib;
+
 pub mod builder;
 pub mod constants;
 pub mod tables;
@@ -1,3 +1,5 @@
+use getset::Getters;
+
 use crate::constants::*;
 use crate::constants::{self};
 use crate::tables::builder_list_entry::MsiBuilderListEntry;
@@ -9,13 +11,24 @@ use crate::types::helpers::to_msi_value::ToMsiOptionalValue;
 use crate::types::helpers::to_unique_msi_identifier::ToUniqueMsiIdentifier;
 use crate::types::standard_action::AdvtAction;
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Getters)]
+#[getset(get = "pub")]
 pub struct AdvtExecuteSequenceDao {
     action: AdvtAction,
     condition: Option<Condition>,
     sequence: Option<i16>,
 }
 
+impl AdvtExecuteSequenceDao {
+    pub fn new(
+        action: AdvtAction,
+        condition: Option<Condition>,
+        sequence: Option<i16>,
+    ) -> AdvtExecuteSequenceDao {
+        AdvtExecuteSequenceDao { action, condition, sequence }
+    }
+}
+
 impl IsDao for AdvtExecuteSequenceDao {
     fn to_row(&self) -> Vec<whimsi_msi::Value> {
         vec![
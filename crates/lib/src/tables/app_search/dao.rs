@@ -1,26 +1,30 @@
-use crate::{
-    tables::{
-        builder_list_entry::MsiBuilderListEntry, dao::IsDao,
-        signature::table::SignatureIdentifier,
-    },
-    types::{
-        column::identifier::Identifier,
-        helpers::to_unique_msi_identifier::ToUniqueMsiIdentifier,
-    },
-};
+use getset::Getters;
 
-#[derive(Debug, Clone, PartialEq)]
+use crate::constants::DEFAULT_IDENTIFIER_MAX_LEN;
+use crate::tables::builder_list_entry::MsiBuilderListEntry;
+use crate::tables::signature::table::SignatureIdentifier;
+use crate::types::column::identifier::Identifier;
+use crate::types::helpers::to_unique_msi_identifier::ToUniqueMsiIdentifier;
+
+/// Sets `property` to the location found by the search with `signature`.
+///
+/// [*Reference*](https://learn.microsoft.com/en-us/windows/win32/msi/appsearch-table)
+#[derive(
+    Debug,
+    Clone,
+    Getters,
+    PartialEq,
+    derive_more::Constructor,
+    whimsi_macros::MsiRow,
+)]
+#[getset(get = "pub")]
 pub struct AppSearchDao {
+    #[msi(primary_key, category = "Identifier", max_len = DEFAULT_IDENTIFIER_MAX_LEN)]
     property: Identifier,
+    #[msi(column = "Signature_", primary_key, category = "Identifier", max_len = DEFAULT_IDENTIFIER_MAX_LEN)]
     signature: SignatureIdentifier,
 }
 
-impl IsDao for AppSearchDao {
-    fn to_row(&self) -> Vec<whimsi_msi::Value> {
-        vec![self.property.clone().into(), self.signature.clone().into()]
-    }
-}
-
 impl ToUniqueMsiIdentifier for AppSearchDao {
     fn to_unique_msi_identifier(&self) -> Option<Identifier> {
         None
@@ -29,6 +33,6 @@ impl ToUniqueMsiIdentifier for AppSearchDao {
 
 impl MsiBuilderListEntry for AppSearchDao {
     fn conflicts(&self, other: &Self) -> bool {
-        self.property == other.property
+        self.property == other.property && self.signature == other.signature
     }
 }
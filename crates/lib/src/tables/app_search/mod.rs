@@ -1,2 +1,3 @@
 pub mod dao;
+pub mod system_search;
 pub mod table;
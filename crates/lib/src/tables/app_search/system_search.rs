@@ -0,0 +1,182 @@
+use anyhow::ensure;
+use getset::Getters;
+use getset::WithSetters;
+use time::PrimitiveDateTime;
+
+use crate::tables::registry::registry_root::RegistryRoot;
+use crate::tables::signature::dao::SignatureDao;
+use crate::tables::signature::table::SignatureIdentifier;
+use crate::types::column::guid::Guid;
+use crate::types::column::version::Version;
+
+/// Where a search looks for the value of its property.
+///
+/// [*Reference*](https://learn.microsoft.com/en-us/windows/win32/msi/searching-for-existing-applications-files-registry-keys-and-ini-file-entries)
+#[derive(Clone, Debug, PartialEq)]
+pub enum SystemSearch {
+    /// The registry value `name` under `key`, or the key's default value
+    /// when `name` is `None`.
+    Registry {
+        root: RegistryRoot,
+        key: String,
+        name: Option<String>,
+        result: SearchResult,
+        /// Read the 64-bit view of the registry instead of the 32-bit one.
+        win64: bool,
+    },
+    /// The directory at `path`, looking up to `depth` levels of
+    /// subdirectories below it. `path` is relative to the directory found by
+    /// the `parent` search when one is given. When `file` is given the search
+    /// is for that file inside the directory instead.
+    Directory {
+        parent: Option<SignatureIdentifier>,
+        path: String,
+        depth: i16,
+        file: Option<FileSignature>,
+    },
+    /// The key path of a component installed by any product. Only
+    /// [`SearchResult::Directory`] and [`SearchResult::File`] are allowed.
+    Component { component_id: Guid, result: SearchResult },
+    /// The `key` entry of `section` in the INI file `file_name`, found in the
+    /// Windows folder. `field` picks a comma separated field of the value,
+    /// with `0` meaning the whole value.
+    Ini {
+        file_name: String,
+        section: String,
+        key: String,
+        field: i16,
+        result: SearchResult,
+    },
+}
+
+/// What a search expects to find at the location it reads.
+#[derive(Clone, Debug, PartialEq)]
+pub enum SearchResult {
+    /// A directory that exists on the system.
+    Directory,
+    /// A file that exists on the system and matches the signature.
+    File(FileSignature),
+    /// The value as it is written, without checking the system for it.
+    Raw,
+}
+
+impl SearchResult {
+    /// The `Type` column value of the locator tables.
+    pub(crate) fn locator_type(&self) -> i16 {
+        match self {
+            SearchResult::Directory => 0,
+            SearchResult::File(_) => 1,
+            SearchResult::Raw => 2,
+        }
+    }
+
+    pub(crate) fn file(&self) -> Option<&FileSignature> {
+        match self {
+            SearchResult::File(file) => Some(file),
+            _ => None,
+        }
+    }
+}
+
+/// The file a search must find. Any limits that are given must also be met
+/// by the file.
+///
+/// [*Reference*](https://learn.microsoft.com/en-us/windows/win32/msi/signature-table)
+#[derive(Clone, Debug, PartialEq, Getters, WithSetters)]
+#[getset(get = "pub", set_with = "pub")]
+pub struct FileSignature {
+    /// The name of the file, optionally in the `short|long` form.
+    file_name: String,
+    min_version: Option<Version>,
+    max_version: Option<Version>,
+    /// The minimum size of the file in bytes.
+    min_size: Option<i32>,
+    /// The maximum size of the file in bytes.
+    max_size: Option<i32>,
+    /// The earliest modification time of the file.
+    min_date: Option<PrimitiveDateTime>,
+    /// The latest modification time of the file.
+    max_date: Option<PrimitiveDateTime>,
+    /// Comma separated language IDs the file must have one of.
+    languages: Option<String>,
+}
+
+impl FileSignature {
+    pub fn new(file_name: impl ToString) -> Self {
+        Self {
+            file_name: file_name.to_string(),
+            min_version: None,
+            max_version: None,
+            min_size: None,
+            max_size: None,
+            min_date: None,
+            max_date: None,
+            languages: None,
+        }
+    }
+
+    pub(crate) fn to_dao(
+        &self,
+        signature: SignatureIdentifier,
+    ) -> anyhow::Result<SignatureDao> {
+        Ok(SignatureDao::new(
+            signature,
+            self.file_name.clone(),
+            self.min_version.clone(),
+            self.max_version.clone(),
+            self.min_size,
+            self.max_size,
+            self.min_date.map(dos_date_time).transpose()?,
+            self.max_date.map(dos_date_time).transpose()?,
+            self.languages.clone(),
+        ))
+    }
+}
+
+/// Packs `date_time` into the DOS date and time format used by the
+/// `Signature` table, with the date in the high word.
+fn dos_date_time(date_time: PrimitiveDateTime) -> anyhow::Result<i32> {
+    let year = date_time.year();
+    ensure!(
+        (1980..=2107).contains(&year),
+        "Signature dates must be between 1980 and 2107, not {date_time}"
+    );
+    let date = ((year - 1980) as u32) << 9
+        | (u8::from(date_time.month()) as u32) << 5
+        | date_time.day() as u32;
+    let time = (date_time.hour() as u32) << 11
+        | (date_time.minute() as u32) << 5
+        | date_time.second() as u32 / 2;
+    Ok((date << 16 | time) as i32)
+}
+
+#[cfg(test)]
+mod test {
+    use time::Date;
+    use time::Month;
+    use time::PrimitiveDateTime;
+    use time::Time;
+
+    use super::dos_date_time;
+
+    #[test]
+    fn dates_are_packed_as_dos_date_times() {
+        let date_time = PrimitiveDateTime::new(
+            Date::from_calendar_date(2024, Month::March, 15).unwrap(),
+            Time::from_hms(13, 45, 30).unwrap(),
+        );
+        assert_eq!(
+            dos_date_time(date_time).unwrap(),
+            (44 << 25 | 3 << 21 | 15 << 16 | 13 << 11 | 45 << 5 | 15) as i32
+        );
+    }
+
+    #[test]
+    fn dates_before_1980_are_rejected() {
+        let date_time = PrimitiveDateTime::new(
+            Date::from_calendar_date(1979, Month::December, 31).unwrap(),
+            Time::MIDNIGHT,
+        );
+        assert!(dos_date_time(date_time).is_err());
+    }
+}
@@ -1,7 +1,9 @@
 use crate::{
-    constants::DEFAULT_IDENTIFIER_MAX_LEN,
     msi_list_boilerplate, msi_table_boilerplate,
-    tables::{app_search::dao::AppSearchDao, builder_table::MsiBuilderTable},
+    tables::{
+        app_search::dao::AppSearchDao, builder_table::MsiBuilderTable,
+        msi_row::MsiRow,
+    },
 };
 
 #[derive(Debug, Clone, PartialEq, Default)]
@@ -16,17 +18,10 @@ impl MsiBuilderTable for AppSearchTable {
     msi_table_boilerplate!();
 
     fn name(&self) -> &'static str {
-        "AppSearch"
+        AppSearchDao::TABLE_NAME
     }
 
     fn columns(&self) -> Vec<whimsi_msi::Column> {
-        vec![
-            whimsi_msi::Column::build("Property")
-                .primary_key()
-                .id_string(DEFAULT_IDENTIFIER_MAX_LEN),
-            whimsi_msi::Column::build("Signature_")
-                .primary_key()
-                .id_string(DEFAULT_IDENTIFIER_MAX_LEN),
-        ]
+        AppSearchDao::columns()
     }
 }
@@ -1,23 +1,36 @@
-use crate::{
-    tables::{
-        binary::table::BinaryIdentifier,
-        builder_list_entry::MsiBuilderListEntry, dao::IsDao,
-    },
-    types::{
-        column::identifier::Identifier,
-        helpers::to_unique_msi_identifier::ToUniqueMsiIdentifier,
-    },
-};
+use getset::Getters;
 
-#[derive(Debug, Clone, PartialEq)]
+use crate::tables::binary::table::BinaryIdentifier;
+use crate::tables::builder_list_entry::MsiBuilderListEntry;
+use crate::tables::dao::IsDao;
+use crate::types::column::binary::Binary;
+use crate::types::column::identifier::Identifier;
+use crate::types::column::identifier::ToIdentifier;
+use crate::types::helpers::to_unique_msi_identifier::ToUniqueMsiIdentifier;
+
+/// Binary data, such as custom action DLLs, EXEs and scripts or UI bitmaps,
+/// that is embedded in the MSI.
+///
+/// The data itself is written to its own `Binary.<Name>` stream when the MSI
+/// is built.
+///
+/// [*Reference*](https://learn.microsoft.com/en-us/windows/win32/msi/binary-table)
+#[derive(Debug, Clone, PartialEq, Getters)]
+#[getset(get = "pub")]
 pub struct BinaryDao {
     name: BinaryIdentifier,
-    data: Vec<u8>,
+    data: Binary,
+}
+
+impl BinaryDao {
+    pub fn new(name: BinaryIdentifier) -> BinaryDao {
+        BinaryDao { name, data: Default::default() }
+    }
 }
 
 impl ToUniqueMsiIdentifier for BinaryDao {
     fn to_unique_msi_identifier(&self) -> Option<Identifier> {
-        None
+        self.name.to_unique_msi_identifier()
     }
 }
 
@@ -29,11 +42,6 @@ impl MsiBuilderListEntry for BinaryDao {
 
 impl IsDao for BinaryDao {
     fn to_row(&self) -> Vec<whimsi_msi::Value> {
-        vec![
-            self.name.clone().into(),
-            // TODO: It appears there is literally no way to write binary data to a table in the
-            // current `msi` crate version.
-            todo!(),
-        ]
+        vec![self.name.to_identifier().into(), self.data.clone().into()]
     }
 }
@@ -1,7 +1,8 @@
 use crate::{
     constants::DEFAULT_IDENTIFIER_MAX_LEN, define_generator_table,
     define_identifier_generator, define_specific_identifier,
-    define_specific_identifier_parsing, msi_list_boilerplate,
+    define_specific_identifier_parsing, implement_id_generator_for_table,
+    implement_new_for_id_generator_table, msi_list_boilerplate,
     tables::binary::dao::BinaryDao,
 };
 
@@ -18,8 +19,5 @@ define_generator_table!(
         whimsi_msi::Column::build("Data").binary()
     ]
 );
-impl Default for BinaryTable {
-    fn default() -> Self {
-        Self { entries: Default::default(), generator: Default::default() }
-    }
-}
+implement_id_generator_for_table!(BinaryTable, BinaryIdGenerator);
+implement_new_for_id_generator_table!(BinaryTable, BinaryIdGenerator);
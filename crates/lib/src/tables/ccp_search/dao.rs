@@ -0,0 +1,38 @@
+use getset::Getters;
+
+use crate::constants::*;
+use crate::tables::builder_list_entry::MsiBuilderListEntry;
+use crate::tables::signature::table::SignatureIdentifier;
+use crate::types::column::identifier::Identifier;
+use crate::types::helpers::to_unique_msi_identifier::ToUniqueMsiIdentifier;
+
+/// A search that must succeed for the `CCPSearch` action to find a
+/// qualifying product on the system.
+///
+/// [*Reference*](https://learn.microsoft.com/en-us/windows/win32/msi/ccpsearch-table)
+#[derive(
+    Debug,
+    Clone,
+    Getters,
+    PartialEq,
+    derive_more::Constructor,
+    whimsi_macros::MsiRow,
+)]
+#[getset(get = "pub")]
+#[msi(table = "CCPSearch")]
+pub struct CcpSearchDao {
+    #[msi(column = "Signature_", primary_key, category = "Identifier", max_len = DEFAULT_IDENTIFIER_MAX_LEN)]
+    signature: SignatureIdentifier,
+}
+
+impl MsiBuilderListEntry for CcpSearchDao {
+    fn conflicts(&self, other: &Self) -> bool {
+        self.signature == other.signature
+    }
+}
+
+impl ToUniqueMsiIdentifier for CcpSearchDao {
+    fn to_unique_msi_identifier(&self) -> Option<Identifier> {
+        None
+    }
+}
@@ -0,0 +1,27 @@
+use crate::msi_list_boilerplate;
+use crate::msi_table_boilerplate;
+use crate::tables::builder_list::MsiBuilderList;
+use crate::tables::builder_table::MsiBuilderTable;
+use crate::tables::ccp_search::dao::CcpSearchDao;
+use crate::tables::msi_row::MsiRow;
+
+#[derive(Debug, Clone, Default)]
+pub struct CcpSearchTable {
+    entries: Vec<CcpSearchDao>,
+}
+
+impl MsiBuilderTable for CcpSearchTable {
+    type TableValue = CcpSearchDao;
+
+    msi_table_boilerplate!();
+
+    fn name(&self) -> &'static str {
+        CcpSearchDao::TABLE_NAME
+    }
+
+    fn columns(&self) -> Vec<whimsi_msi::Column> {
+        CcpSearchDao::columns()
+    }
+}
+
+msi_list_boilerplate!(CcpSearchTable, CcpSearchDao);
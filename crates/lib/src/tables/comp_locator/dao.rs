@@ -0,0 +1,43 @@
+use getset::Getters;
+
+use crate::constants::*;
+use crate::tables::builder_list_entry::MsiBuilderListEntry;
+use crate::tables::signature::table::SignatureIdentifier;
+use crate::types::column::guid::Guid;
+use crate::types::column::identifier::Identifier;
+use crate::types::helpers::to_unique_msi_identifier::ToUniqueMsiIdentifier;
+
+/// Searches for the key path of the component `component_id`, installed by
+/// any product. `typ` chooses whether the key path file or its directory is
+/// found.
+///
+/// [*Reference*](https://learn.microsoft.com/en-us/windows/win32/msi/complocator-table)
+#[derive(
+    Debug,
+    Clone,
+    Getters,
+    PartialEq,
+    derive_more::Constructor,
+    whimsi_macros::MsiRow,
+)]
+#[getset(get = "pub")]
+pub struct CompLocatorDao {
+    #[msi(column = "Signature_", primary_key, category = "Identifier", max_len = DEFAULT_IDENTIFIER_MAX_LEN)]
+    signature: SignatureIdentifier,
+    #[msi(category = "Guid", max_len = GUID_MAX_LEN)]
+    component_id: Guid,
+    #[msi(column = "Type")]
+    typ: Option<i16>,
+}
+
+impl MsiBuilderListEntry for CompLocatorDao {
+    fn conflicts(&self, other: &Self) -> bool {
+        self.signature == other.signature
+    }
+}
+
+impl ToUniqueMsiIdentifier for CompLocatorDao {
+    fn to_unique_msi_identifier(&self) -> Option<Identifier> {
+        None
+    }
+}
@@ -0,0 +1,27 @@
+use crate::msi_list_boilerplate;
+use crate::msi_table_boilerplate;
+use crate::tables::builder_list::MsiBuilderList;
+use crate::tables::builder_table::MsiBuilderTable;
+use crate::tables::comp_locator::dao::CompLocatorDao;
+use crate::tables::msi_row::MsiRow;
+
+#[derive(Debug, Clone, Default)]
+pub struct CompLocatorTable {
+    entries: Vec<CompLocatorDao>,
+}
+
+impl MsiBuilderTable for CompLocatorTable {
+    type TableValue = CompLocatorDao;
+
+    msi_table_boilerplate!();
+
+    fn name(&self) -> &'static str {
+        CompLocatorDao::TABLE_NAME
+    }
+
+    fn columns(&self) -> Vec<whimsi_msi::Column> {
+        CompLocatorDao::columns()
+    }
+}
+
+msi_list_boilerplate!(CompLocatorTable, CompLocatorDao);
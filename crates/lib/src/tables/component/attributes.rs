@@ -1,9 +1,23 @@
 use bitmask_enum::bitmask;
 
+/// Remote execution option and other flags for a component.
+///
+/// [*Reference*](https://learn.microsoft.com/en-us/windows/win32/msi/component-table)
 #[bitmask(i16)]
 pub enum ComponentAttributes {
-    LocalOnly  = 0x0000,
+    LocalOnly = 0x0000,
     SourceOnly = 0x0001,
-    Optional   = 0x0002,
-    // TODO: The rest
+    Optional = 0x0002,
+    /// The KeyPath column is a key into the Registry table.
+    RegistryKeyPath = 0x0004,
+    SharedDllRefCount = 0x0008,
+    Permanent = 0x0010,
+    /// The KeyPath column is a key into the ODBCDataSource table.
+    OdbcDataSource = 0x0020,
+    Transitive = 0x0040,
+    NeverOverwrite = 0x0080,
+    Win64 = 0x0100,
+    DisableRegistryReflection = 0x0200,
+    UninstallOnSupersedence = 0x0400,
+    Shared = 0x0800,
 }
@@ -2,6 +2,7 @@ use anyhow::Context;
 use getset::Getters;
 
 use crate::tables::builder_list_entry::MsiBuilderListEntry;
+use crate::tables::component::attributes::ComponentAttributes;
 use crate::tables::component::table::ComponentIdentifier;
 use crate::tables::dao::IsDao;
 use crate::tables::directory::directory_identifier::DirectoryIdentifier;
@@ -44,6 +45,18 @@ impl ComponentDao {
         self.key_path = Some(key_path);
         self
     }
+
+    /// Pin the GUID of the component instead of deriving it from its target
+    /// when the package is built.
+    pub fn with_component_id(mut self, component_id: Guid) -> Self {
+        self.component_id = Some(component_id);
+        self
+    }
+
+    pub fn with_attributes(mut self, attributes: ComponentAttributes) -> Self {
+        self.attributes = attributes.bits();
+        self
+    }
 }
 
 impl IsDao for ComponentDao {
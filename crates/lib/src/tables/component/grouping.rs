@@ -0,0 +1,63 @@
+use crate::constants::VERSIONED_FILE_EXTENSIONS;
+use crate::tables::registry::registry_root::RegistryRoot;
+use crate::tables::registry::registry_value::RegistryValue;
+
+/// How the builder puts installed files into components.
+///
+/// Versioned files, such as executables and libraries, are always the key
+/// path of their own component. The installer compares the version of the
+/// key path to decide whether to overwrite a component, so a versioned file
+/// that isn't a key path may never be updated.
+///
+/// [*Reference*](https://learn.microsoft.com/en-us/windows/win32/msi/organizing-applications-into-components)
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, serde::Deserialize)]
+pub enum ComponentGrouping {
+    /// Every file is its own component and key path.
+    #[default]
+    PerFile,
+    /// Unversioned files in the same directory and feature share a
+    /// component.
+    PerDirectory,
+}
+
+/// What a component shared by the files of a directory uses as its key
+/// path. Without one the first file added to the directory is used.
+#[derive(Clone, Debug, PartialEq)]
+pub enum ComponentKeyPath {
+    /// The file with this name.
+    File(String),
+    /// A registry value written by the component. Components installed for
+    /// a single user should use one of these instead of a file.
+    Registry {
+        root: RegistryRoot,
+        key: String,
+        name: Option<String>,
+        value: RegistryValue,
+    },
+    /// The directory itself, which is then created even if it ends up empty.
+    Directory,
+}
+
+/// Whether the installer treats a file with this name as versioned.
+pub(crate) fn is_versioned(name: &str) -> bool {
+    name.rsplit_once('.').is_some_and(|(_, extension)| {
+        VERSIONED_FILE_EXTENSIONS
+            .iter()
+            .any(|versioned| versioned.eq_ignore_ascii_case(extension))
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use test_case::test_case;
+
+    use super::is_versioned;
+
+    #[test_case("app.exe", true; "executable")]
+    #[test_case("Library.DLL", true; "upper case library")]
+    #[test_case("readme.txt", false; "text")]
+    #[test_case("exe", false; "no extension")]
+    fn versioned_files(name: &str, expected: bool) {
+        assert_eq!(is_versioned(name), expected);
+    }
+}
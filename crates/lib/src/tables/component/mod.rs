@@ -1,3 +1,4 @@
 pub mod attributes;
 pub(crate) mod dao;
+pub mod grouping;
 pub mod table;
@@ -0,0 +1,61 @@
+use getset::Getters;
+
+use crate::constants::*;
+use crate::tables::builder_list_entry::MsiBuilderListEntry;
+use crate::tables::feature::identifier::FeatureIdentifier;
+use crate::types::column::condition::Condition;
+use crate::types::column::identifier::Identifier;
+use crate::types::helpers::to_unique_msi_identifier::ToUniqueMsiIdentifier;
+
+/// Sets the install level of a feature to `level` when `condition` is true.
+///
+/// [*Reference*](https://learn.microsoft.com/en-us/windows/win32/msi/condition-table)
+#[derive(
+    Debug,
+    Clone,
+    Getters,
+    PartialEq,
+    derive_more::Constructor,
+    whimsi_macros::MsiRow,
+)]
+#[getset(get = "pub")]
+pub struct ConditionDao {
+    #[msi(column = "Feature_", primary_key, category = "Identifier", max_len = FEATURE_IDENTIFIER_MAX_LEN)]
+    feature: FeatureIdentifier,
+    #[msi(primary_key)]
+    level: i16,
+    #[msi(category = "Condition", max_len = CONDITION_MAX_LEN)]
+    condition: Option<Condition>,
+}
+
+impl MsiBuilderListEntry for ConditionDao {
+    fn conflicts(&self, other: &Self) -> bool {
+        self.feature == other.feature && self.level == other.level
+    }
+}
+
+impl ToUniqueMsiIdentifier for ConditionDao {
+    fn to_unique_msi_identifier(&self) -> Option<Identifier> {
+        None
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::str::FromStr;
+
+    use super::ConditionDao;
+    use crate::tables::feature::identifier::FeatureIdentifier;
+    use crate::tables::msi_row::assert_round_trip;
+    use crate::types::column::condition::Condition;
+
+    #[test]
+    fn round_trip() {
+        let dao = ConditionDao::new(
+            FeatureIdentifier::from_str("Docs").unwrap(),
+            0,
+            Some(Condition::from_str("NOT INSTALL_DOCS").unwrap()),
+        );
+        assert_round_trip(dao);
+    }
+}
@@ -0,0 +1,27 @@
+use crate::msi_list_boilerplate;
+use crate::msi_table_boilerplate;
+use crate::tables::builder_list::MsiBuilderList;
+use crate::tables::builder_table::MsiBuilderTable;
+use crate::tables::condition::dao::ConditionDao;
+use crate::tables::msi_row::MsiRow;
+
+#[derive(Debug, Clone, Default)]
+pub struct ConditionTable {
+    entries: Vec<ConditionDao>,
+}
+
+impl MsiBuilderTable for ConditionTable {
+    type TableValue = ConditionDao;
+
+    msi_table_boilerplate!();
+
+    fn name(&self) -> &'static str {
+        ConditionDao::TABLE_NAME
+    }
+
+    fn columns(&self) -> Vec<whimsi_msi::Column> {
+        ConditionDao::columns()
+    }
+}
+
+msi_list_boilerplate!(ConditionTable, ConditionDao);
@@ -0,0 +1,42 @@
+use getset::Getters;
+
+use crate::constants::*;
+use crate::tables::builder_list_entry::MsiBuilderListEntry;
+use crate::tables::component::table::ComponentIdentifier;
+use crate::tables::directory::directory_identifier::DirectoryIdentifier;
+use crate::types::column::identifier::Identifier;
+use crate::types::column::identifier::ToIdentifier;
+use crate::types::helpers::to_unique_msi_identifier::ToUniqueMsiIdentifier;
+
+/// Creates `directory` when `component` is installed, even if nothing is
+/// installed into it.
+///
+/// [*Reference*](https://learn.microsoft.com/en-us/windows/win32/msi/createfolder-table)
+#[derive(
+    Debug,
+    Clone,
+    Getters,
+    PartialEq,
+    derive_more::Constructor,
+    whimsi_macros::MsiRow,
+)]
+#[getset(get = "pub")]
+pub struct CreateFolderDao {
+    #[msi(column = "Directory_", primary_key, category = "Identifier", max_len = DEFAULT_IDENTIFIER_MAX_LEN)]
+    directory: DirectoryIdentifier,
+    #[msi(column = "Component_", primary_key, category = "Identifier", max_len = DEFAULT_IDENTIFIER_MAX_LEN)]
+    component: ComponentIdentifier,
+}
+
+impl MsiBuilderListEntry for CreateFolderDao {
+    fn conflicts(&self, other: &Self) -> bool {
+        self.directory.to_identifier() == other.directory.to_identifier()
+            && self.component == other.component
+    }
+}
+
+impl ToUniqueMsiIdentifier for CreateFolderDao {
+    fn to_unique_msi_identifier(&self) -> Option<Identifier> {
+        None
+    }
+}
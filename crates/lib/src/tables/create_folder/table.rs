@@ -0,0 +1,27 @@
+use crate::msi_list_boilerplate;
+use crate::msi_table_boilerplate;
+use crate::tables::builder_list::MsiBuilderList;
+use crate::tables::builder_table::MsiBuilderTable;
+use crate::tables::create_folder::dao::CreateFolderDao;
+use crate::tables::msi_row::MsiRow;
+
+#[derive(Debug, Clone, Default)]
+pub struct CreateFolderTable {
+    entries: Vec<CreateFolderDao>,
+}
+
+impl MsiBuilderTable for CreateFolderTable {
+    type TableValue = CreateFolderDao;
+
+    msi_table_boilerplate!();
+
+    fn name(&self) -> &'static str {
+        CreateFolderDao::TABLE_NAME
+    }
+
+    fn columns(&self) -> Vec<whimsi_msi::Column> {
+        CreateFolderDao::columns()
+    }
+}
+
+msi_list_boilerplate!(CreateFolderTable, CreateFolderDao);
@@ -0,0 +1,294 @@
+use anyhow::bail;
+use anyhow::ensure;
+use getset::Getters;
+use getset::WithSetters;
+
+use crate::types::column::custom_source::CustomSource;
+
+// Return processing options for the `Type` column.
+const CONTINUE: i16 = 0x40;
+const ASYNC: i16 = 0x80;
+// In-script execution options for the `Type` column.
+const IN_SCRIPT: i16 = 0x400;
+const ROLLBACK: i16 = 0x100;
+const COMMIT: i16 = 0x200;
+const NO_IMPERSONATE: i16 = 0x800;
+
+/// What a custom action runs and where the `Source` and `Target` columns
+/// point to.
+///
+/// [*Reference*](https://learn.microsoft.com/en-us/windows/win32/msi/summary-list-of-all-custom-action-types)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CustomActionKind {
+    /// Calls the `Target` entry point of a DLL stored in the Binary table.
+    BinaryDll = 1,
+    /// Runs an EXE stored in the Binary table with `Target` as its command
+    /// line.
+    BinaryExe = 2,
+    /// Calls the `Target` function of a JScript file stored in the Binary
+    /// table.
+    BinaryJScript = 5,
+    /// Calls the `Target` function of a VBScript file stored in the Binary
+    /// table.
+    BinaryVbScript = 6,
+    /// Calls the `Target` entry point of a DLL installed with the product.
+    FileDll = 17,
+    /// Runs an EXE installed with the product with `Target` as its command
+    /// line.
+    FileExe = 18,
+    /// Displays `Target` as an error message and ends the installation.
+    Error = 19,
+    /// Calls the `Target` function of a JScript file installed with the
+    /// product.
+    FileJScript = 21,
+    /// Calls the `Target` function of a VBScript file installed with the
+    /// product.
+    FileVbScript = 22,
+    /// Runs the `Target` command line with the `Source` directory as the
+    /// working directory.
+    DirectoryExe = 34,
+    /// Sets the `Source` directory to the `Target` path.
+    SetDirectory = 35,
+    /// Runs `Target` as JScript text.
+    JScriptText = 37,
+    /// Runs `Target` as VBScript text.
+    VbScriptText = 38,
+    /// Runs the EXE whose path is in the `Source` property with `Target` as
+    /// its command line.
+    PropertyExe = 50,
+    /// Sets the `Source` property to `Target`.
+    SetProperty = 51,
+    /// Runs the JScript text stored in the `Source` property, calling the
+    /// `Target` function if given.
+    PropertyJScript = 53,
+    /// Runs the VBScript text stored in the `Source` property, calling the
+    /// `Target` function if given.
+    PropertyVbScript = 54,
+}
+
+impl CustomActionKind {
+    fn is_exe(&self) -> bool {
+        matches!(
+            self,
+            CustomActionKind::BinaryExe
+                | CustomActionKind::FileExe
+                | CustomActionKind::DirectoryExe
+                | CustomActionKind::PropertyExe
+        )
+    }
+
+    /// Checks that `source` references the table this kind of custom action
+    /// reads its `Source` column from.
+    fn validate_source(
+        &self,
+        source: Option<&CustomSource>,
+    ) -> anyhow::Result<()> {
+        let valid = match self {
+            CustomActionKind::BinaryDll
+            | CustomActionKind::BinaryExe
+            | CustomActionKind::BinaryJScript
+            | CustomActionKind::BinaryVbScript => {
+                matches!(source, Some(CustomSource::Binary(_)))
+            }
+            CustomActionKind::FileDll
+            | CustomActionKind::FileExe
+            | CustomActionKind::FileJScript
+            | CustomActionKind::FileVbScript => {
+                matches!(source, Some(CustomSource::File(_)))
+            }
+            CustomActionKind::DirectoryExe | CustomActionKind::SetDirectory => {
+                matches!(source, Some(CustomSource::Directory(_)))
+            }
+            CustomActionKind::PropertyExe
+            | CustomActionKind::SetProperty
+            | CustomActionKind::PropertyJScript
+            | CustomActionKind::PropertyVbScript => {
+                matches!(source, Some(CustomSource::Property(_)))
+            }
+            CustomActionKind::Error
+            | CustomActionKind::JScriptText
+            | CustomActionKind::VbScriptText => source.is_none(),
+        };
+        ensure!(
+            valid,
+            "Custom action of kind {self:?} cannot have source {source:?}"
+        );
+        Ok(())
+    }
+}
+
+/// When a custom action runs relative to the installation script.
+///
+/// [*Reference*](https://learn.microsoft.com/en-us/windows/win32/msi/custom-action-in-script-execution-options)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum CustomActionExecution {
+    /// Runs as soon as it is reached in the sequence table.
+    #[default]
+    Immediate,
+    /// Written to the installation script and run during the script.
+    Deferred,
+    /// Run if the installation fails and is rolled back.
+    Rollback,
+    /// Run once the installation script has completed successfully.
+    Commit,
+}
+
+/// The `Type` column of the CustomAction table.
+///
+/// [*Reference*](https://learn.microsoft.com/en-us/windows/win32/msi/customaction-table)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Getters, WithSetters)]
+#[getset(get = "pub", set_with = "pub")]
+pub struct CustomActionType {
+    kind: CustomActionKind,
+    execution: CustomActionExecution,
+    /// Run deferred, rollback and commit actions in the user's context
+    /// instead of with elevated privileges.
+    impersonate: bool,
+    /// Continue the installation even if the action fails.
+    ignore_return: bool,
+    /// Run the action alongside the installation. Combined with
+    /// `ignore_return` the installer doesn't wait for it to finish at all.
+    asynchronous: bool,
+}
+
+impl CustomActionType {
+    /// An immediate, synchronous action whose return code is checked.
+    pub fn new(kind: CustomActionKind) -> Self {
+        CustomActionType {
+            kind,
+            execution: CustomActionExecution::Immediate,
+            impersonate: true,
+            ignore_return: false,
+            asynchronous: false,
+        }
+    }
+
+    /// Checks that the options are a valid combination and that `source`
+    /// matches the kind of custom action.
+    pub fn validate(
+        &self,
+        source: Option<&CustomSource>,
+    ) -> anyhow::Result<()> {
+        self.kind.validate_source(source)?;
+        if !self.impersonate
+            && self.execution == CustomActionExecution::Immediate
+        {
+            bail!(
+                "Only deferred, rollback and commit actions can run without impersonation"
+            );
+        }
+        if self.asynchronous && self.ignore_return {
+            ensure!(
+                self.kind.is_exe(),
+                "Only EXE custom actions can run asynchronously without waiting"
+            );
+        }
+        Ok(())
+    }
+
+    /// Whether the action runs as part of the installation script, which
+    /// restricts where it can be sequenced.
+    pub fn is_in_script(&self) -> bool {
+        self.execution != CustomActionExecution::Immediate
+    }
+}
+
+impl From<CustomActionType> for i16 {
+    fn from(value: CustomActionType) -> i16 {
+        let mut bits = value.kind as i16;
+        if value.ignore_return {
+            bits |= CONTINUE;
+        }
+        if value.asynchronous {
+            bits |= ASYNC;
+        }
+        bits |= match value.execution {
+            CustomActionExecution::Immediate => 0,
+            CustomActionExecution::Deferred => IN_SCRIPT,
+            CustomActionExecution::Rollback => IN_SCRIPT | ROLLBACK,
+            CustomActionExecution::Commit => IN_SCRIPT | COMMIT,
+        };
+        if !value.impersonate {
+            bits |= NO_IMPERSONATE;
+        }
+        bits
+    }
+}
+
+impl From<CustomActionType> for whimsi_msi::Value {
+    fn from(value: CustomActionType) -> whimsi_msi::Value {
+        whimsi_msi::Value::Int(i16::from(value).into())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::str::FromStr;
+
+    use test_case::test_case;
+
+    use super::CustomActionExecution;
+    use super::CustomActionKind;
+    use super::CustomActionType;
+    use crate::tables::binary::table::BinaryIdentifier;
+    use crate::types::column::custom_source::CustomSource;
+    use crate::types::column::identifier::Identifier;
+
+    #[test_case(CustomActionType::new(CustomActionKind::SetProperty), 51; "immediate")]
+    #[test_case(
+        CustomActionType::new(CustomActionKind::FileExe)
+            .with_execution(CustomActionExecution::Deferred)
+            .with_impersonate(false),
+        18 | 0x400 | 0x800;
+        "deferred without impersonation"
+    )]
+    #[test_case(
+        CustomActionType::new(CustomActionKind::BinaryDll)
+            .with_execution(CustomActionExecution::Rollback),
+        1 | 0x500;
+        "rollback"
+    )]
+    #[test_case(
+        CustomActionType::new(CustomActionKind::BinaryExe)
+            .with_execution(CustomActionExecution::Commit)
+            .with_ignore_return(true)
+            .with_asynchronous(true),
+        2 | 0x600 | 0xC0;
+        "commit async no wait"
+    )]
+    fn to_bits(typ: CustomActionType, expected: i16) {
+        assert_eq!(i16::from(typ), expected);
+    }
+
+    #[test]
+    fn validates_source() {
+        let property =
+            CustomSource::Property(Identifier::from_str("MYPROP").unwrap());
+        let binary = CustomSource::Binary(
+            BinaryIdentifier::from_str("MyBinary").unwrap(),
+        );
+        let set_property = CustomActionType::new(CustomActionKind::SetProperty);
+        assert!(set_property.validate(Some(&property)).is_ok());
+        assert!(set_property.validate(Some(&binary)).is_err());
+        assert!(set_property.validate(None).is_err());
+
+        let script = CustomActionType::new(CustomActionKind::VbScriptText);
+        assert!(script.validate(None).is_ok());
+        assert!(script.validate(Some(&property)).is_err());
+    }
+
+    #[test]
+    fn validates_options() {
+        let immediate = CustomActionType::new(CustomActionKind::JScriptText)
+            .with_impersonate(false);
+        assert!(immediate.validate(None).is_err());
+
+        let no_wait_dll = CustomActionType::new(CustomActionKind::BinaryDll)
+            .with_ignore_return(true)
+            .with_asynchronous(true);
+        let binary = CustomSource::Binary(
+            BinaryIdentifier::from_str("MyBinary").unwrap(),
+        );
+        assert!(no_wait_dll.validate(Some(&binary)).is_err());
+    }
+}
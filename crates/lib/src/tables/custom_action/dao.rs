@@ -1,8 +1,17 @@
+use getset::Getters;
+
 use crate::{
-    tables::{builder_list_entry::MsiBuilderListEntry, dao::IsDao},
+    tables::{
+        builder_list_entry::MsiBuilderListEntry,
+        custom_action::custom_action_type::{
+            CustomActionKind, CustomActionType,
+        },
+        dao::IsDao,
+    },
     types::{
         column::{
-            custom_source::CustomSource, formatted::Formatted,
+            custom_source::CustomSource,
+            formatted::{Formatted, FormattedColumns},
             identifier::Identifier,
         },
         helpers::{
@@ -12,13 +21,25 @@ use crate::{
     },
 };
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Getters)]
+#[getset(get = "pub")]
 pub struct CustomActionDao {
     action: Identifier,
-    typ: i16,
+    typ: CustomActionType,
     source: Option<CustomSource>,
     target: Option<Formatted>,
-    extended_type: i32,
+    extended_type: Option<i32>,
+}
+
+impl CustomActionDao {
+    pub fn new(
+        action: Identifier,
+        typ: CustomActionType,
+        source: Option<CustomSource>,
+        target: Option<Formatted>,
+    ) -> CustomActionDao {
+        CustomActionDao { action, typ, source, target, extended_type: None }
+    }
 }
 
 impl IsDao for CustomActionDao {
@@ -28,7 +49,7 @@ impl IsDao for CustomActionDao {
             self.typ.into(),
             self.source.to_optional_value(),
             self.target.to_optional_value(),
-            self.extended_type.into(),
+            self.extended_type.to_optional_value(),
         ]
     }
 }
@@ -46,3 +67,15 @@ impl ToUniqueMsiIdentifier for CustomActionDao {
         None
     }
 }
+
+impl FormattedColumns for CustomActionDao {
+    fn formatted_columns(&self) -> Vec<&Formatted> {
+        // Script text is run as is rather than being formatted.
+        match self.typ.kind() {
+            CustomActionKind::JScriptText | CustomActionKind::VbScriptText => {
+                Vec::new()
+            }
+            _ => self.target.iter().collect(),
+        }
+    }
+}
@@ -1,2 +1,3 @@
+pub mod custom_action_type;
 pub mod dao;
 pub mod table;
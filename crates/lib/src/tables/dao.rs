@@ -1,17 +1,36 @@
+use crate::tables::app_search::dao::AppSearchDao;
+use crate::tables::binary::dao::BinaryDao;
+use crate::tables::ccp_search::dao::CcpSearchDao;
+use crate::tables::comp_locator::dao::CompLocatorDao;
 use crate::tables::component::dao::ComponentDao;
+use crate::tables::condition::dao::ConditionDao;
+use crate::tables::create_folder::dao::CreateFolderDao;
+use crate::tables::custom_action::dao::CustomActionDao;
 use crate::tables::directory::dao::DirectoryDao;
+use crate::tables::dr_locator::dao::DrLocatorDao;
+use crate::tables::environment::dao::EnvironmentDao;
 use crate::tables::feature::dao::FeatureDao;
 use crate::tables::feature_components::dao::FeatureComponentsDao;
 use crate::tables::file::dao::FileDao;
 use crate::tables::icon::dao::IconDao;
+use crate::tables::ini_file::dao::IniFileDao;
+use crate::tables::ini_locator::dao::IniLocatorDao;
+use crate::tables::launch_condition::dao::LaunchConditionDao;
 use crate::tables::lock_permissions::dao::LockPermissionsDao;
 use crate::tables::media::dao::MediaDao;
 use crate::tables::msi_file_hash::dao::MsiFileHashDao;
+use crate::tables::msi_row::MsiRow;
 use crate::tables::property::dao::PropertyDao;
+use crate::tables::reg_locator::dao::RegLocatorDao;
 use crate::tables::registry::dao::RegistryDao;
+use crate::tables::remove_file::dao::RemoveFileDao;
+use crate::tables::remove_ini_file::dao::RemoveIniFileDao;
+use crate::tables::remove_registry::dao::RemoveRegistryDao;
 use crate::tables::service_control::dao::ServiceControlDao;
 use crate::tables::service_install::dao::ServiceInstallDao;
 use crate::tables::shortcut::dao::ShortcutDao;
+use crate::tables::signature::dao::SignatureDao;
+use crate::tables::upgrade::dao::UpgradeDao;
 
 #[derive(Debug, Clone, derive_more::From)]
 pub(crate) enum Dao {
@@ -22,15 +41,39 @@ pub(crate) enum Dao {
     Property(PropertyDao),
     MsiFileHash(MsiFileHashDao),
     Registry(RegistryDao),
+    RemoveRegistry(RemoveRegistryDao),
+    Environment(EnvironmentDao),
+    IniFile(IniFileDao),
+    RemoveIniFile(RemoveIniFileDao),
+    RemoveFile(RemoveFileDao),
     Feature(FeatureDao),
     FeatureComponents(FeatureComponentsDao),
+    Condition(ConditionDao),
+    CreateFolder(CreateFolderDao),
+    LaunchCondition(LaunchConditionDao),
+    Upgrade(UpgradeDao),
     Shortcut(ShortcutDao),
     ServiceInstall(ServiceInstallDao),
     ServiceControl(ServiceControlDao),
     LockPermissions(LockPermissionsDao),
     Icon(IconDao),
+    CustomAction(CustomActionDao),
+    Binary(BinaryDao),
+    Signature(SignatureDao),
+    RegLocator(RegLocatorDao),
+    DrLocator(DrLocatorDao),
+    CompLocator(CompLocatorDao),
+    IniLocator(IniLocatorDao),
+    AppSearch(AppSearchDao),
+    CcpSearch(CcpSearchDao),
 }
 
 pub(crate) trait IsDao {
     fn to_row(&self) -> Vec<whimsi_msi::Value>;
 }
+
+impl<T: MsiRow> IsDao for T {
+    fn to_row(&self) -> Vec<whimsi_msi::Value> {
+        MsiRow::to_row(self)
+    }
+}
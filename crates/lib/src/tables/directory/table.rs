@@ -19,6 +19,7 @@ use crate::tables::id_generator_builder_list::IdGeneratorBuilderList;
 use crate::types::column::default_dir::DefaultDir;
 use crate::types::column::identifier::Identifier;
 use crate::types::column::identifier::ToIdentifier;
+use crate::types::helpers::id_generator::SharedIdentifiers;
 use crate::types::properties::system_folder::SystemFolder;
 
 define_identifier_generator!(Directory);
@@ -39,9 +40,7 @@ define_generator_table!(
 );
 
 impl DirectoryTable {
-    pub fn new(
-        identifiers: std::rc::Rc<std::cell::RefCell<Vec<Identifier>>>,
-    ) -> Self {
+    pub fn new(identifiers: SharedIdentifiers) -> Self {
         let entries = Vec::new();
         let generator = DirectoryIdGenerator::from(identifiers);
         let mut table = Self { entries, generator };
@@ -132,9 +131,7 @@ pub enum DirectoryTableError {
 
 #[cfg(test)]
 mod test {
-    use std::cell::RefCell;
     use std::io::Cursor;
-    use std::rc::Rc;
     use std::str::FromStr;
 
     use whimsi_msi::PackageType;
@@ -147,6 +144,7 @@ mod test {
     use crate::types::column::default_dir::DefaultDir;
     use crate::types::column::filename::Filename;
     use crate::types::column::identifier::Identifier;
+    use crate::types::helpers::id_generator::SharedIdentifiers;
     use crate::types::properties::system_folder::SystemFolder;
 
     #[test]
@@ -156,7 +154,7 @@ mod test {
             Cursor::new(Vec::new()),
         )
         .unwrap();
-        let mut table = DirectoryTable::new(Rc::new(RefCell::new(Vec::new())));
+        let mut table = DirectoryTable::new(SharedIdentifiers::default());
         let parent = SystemFolder::ProgramFilesFolder;
         table.add(parent.into());
         table.add(DirectoryDao::new(
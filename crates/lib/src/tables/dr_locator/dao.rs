@@ -0,0 +1,68 @@
+use getset::Getters;
+
+use crate::constants::*;
+use crate::tables::builder_list_entry::MsiBuilderListEntry;
+use crate::tables::signature::table::SignatureIdentifier;
+use crate::types::column::identifier::Identifier;
+use crate::types::helpers::to_unique_msi_identifier::ToUniqueMsiIdentifier;
+
+/// Searches for the directory at `path`, looking up to `depth` levels of
+/// subdirectories below it. `path` is relative to the directory found by the
+/// `parent` search when one is given.
+///
+/// When `signature` is also in the `Signature` table, the search is for that
+/// file inside the directory instead.
+///
+/// [*Reference*](https://learn.microsoft.com/en-us/windows/win32/msi/drlocator-table)
+#[derive(
+    Debug,
+    Clone,
+    Getters,
+    PartialEq,
+    derive_more::Constructor,
+    whimsi_macros::MsiRow,
+)]
+#[getset(get = "pub")]
+pub struct DrLocatorDao {
+    #[msi(column = "Signature_", primary_key, category = "Identifier", max_len = DEFAULT_IDENTIFIER_MAX_LEN)]
+    signature: SignatureIdentifier,
+    #[msi(primary_key, category = "Identifier", max_len = DEFAULT_IDENTIFIER_MAX_LEN)]
+    parent: Option<SignatureIdentifier>,
+    #[msi(primary_key, category = "AnyPath", max_len = 255)]
+    path: Option<String>,
+    depth: Option<i16>,
+}
+
+impl MsiBuilderListEntry for DrLocatorDao {
+    fn conflicts(&self, other: &Self) -> bool {
+        self.signature == other.signature
+            && self.parent == other.parent
+            && self.path == other.path
+    }
+}
+
+impl ToUniqueMsiIdentifier for DrLocatorDao {
+    fn to_unique_msi_identifier(&self) -> Option<Identifier> {
+        None
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::str::FromStr;
+
+    use super::DrLocatorDao;
+    use crate::tables::msi_row::assert_round_trip;
+    use crate::tables::signature::table::SignatureIdentifier;
+
+    #[test]
+    fn round_trip() {
+        let dao = DrLocatorDao::new(
+            SignatureIdentifier::from_str("TOOLS").unwrap(),
+            Some(SignatureIdentifier::from_str("INSTALLROOT").unwrap()),
+            Some("bin".to_string()),
+            Some(2),
+        );
+        assert_round_trip(dao);
+    }
+}
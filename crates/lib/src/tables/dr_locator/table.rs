@@ -0,0 +1,27 @@
+use crate::msi_list_boilerplate;
+use crate::msi_table_boilerplate;
+use crate::tables::builder_list::MsiBuilderList;
+use crate::tables::builder_table::MsiBuilderTable;
+use crate::tables::dr_locator::dao::DrLocatorDao;
+use crate::tables::msi_row::MsiRow;
+
+#[derive(Debug, Clone, Default)]
+pub struct DrLocatorTable {
+    entries: Vec<DrLocatorDao>,
+}
+
+impl MsiBuilderTable for DrLocatorTable {
+    type TableValue = DrLocatorDao;
+
+    msi_table_boilerplate!();
+
+    fn name(&self) -> &'static str {
+        DrLocatorDao::TABLE_NAME
+    }
+
+    fn columns(&self) -> Vec<whimsi_msi::Column> {
+        DrLocatorDao::columns()
+    }
+}
+
+msi_list_boilerplate!(DrLocatorTable, DrLocatorDao);
@@ -0,0 +1,79 @@
+use getset::Getters;
+
+use crate::constants::*;
+use crate::define_identifier_generator;
+use crate::define_specific_identifier;
+use crate::define_specific_identifier_parsing;
+use crate::tables::builder_list_entry::MsiBuilderListEntry;
+use crate::tables::component::table::ComponentIdentifier;
+use crate::types::column::formatted::Formatted;
+use crate::types::column::formatted::FormattedColumns;
+use crate::types::column::identifier::Identifier;
+use crate::types::helpers::to_unique_msi_identifier::ToUniqueMsiIdentifier;
+
+define_specific_identifier!(Environment);
+define_specific_identifier_parsing!(Environment);
+define_identifier_generator!(Environment);
+
+/// An environment variable that is set or removed when the owning component
+/// is installed. Prefixes on `name` choose what happens to the variable.
+///
+/// [*Reference*](https://learn.microsoft.com/en-us/windows/win32/msi/environment-table)
+#[derive(
+    Debug,
+    Clone,
+    PartialEq,
+    Getters,
+    derive_more::Constructor,
+    whimsi_macros::MsiRow,
+)]
+#[getset(get = "pub")]
+pub struct EnvironmentDao {
+    #[msi(primary_key, category = "Identifier", max_len = DEFAULT_IDENTIFIER_MAX_LEN)]
+    environment: EnvironmentIdentifier,
+    #[msi(localizable, category = "Text", max_len = ENVIRONMENT_NAME_MAX_LEN)]
+    name: String,
+    #[msi(localizable, category = "Formatted", max_len = ENVIRONMENT_VALUE_MAX_LEN)]
+    value: Option<Formatted>,
+    #[msi(column = "Component_", category = "Identifier", max_len = DEFAULT_IDENTIFIER_MAX_LEN)]
+    component: ComponentIdentifier,
+}
+
+impl ToUniqueMsiIdentifier for EnvironmentDao {
+    fn to_unique_msi_identifier(&self) -> Option<Identifier> {
+        self.environment.to_unique_msi_identifier()
+    }
+}
+
+impl MsiBuilderListEntry for EnvironmentDao {
+    fn conflicts(&self, other: &Self) -> bool {
+        self.environment == other.environment
+    }
+}
+
+impl FormattedColumns for EnvironmentDao {
+    fn formatted_columns(&self) -> Vec<&Formatted> {
+        self.value.iter().collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::str::FromStr;
+
+    use super::EnvironmentDao;
+    use super::EnvironmentIdentifier;
+    use crate::tables::component::table::ComponentIdentifier;
+    use crate::tables::msi_row::assert_round_trip;
+
+    #[test]
+    fn round_trip() {
+        let dao = EnvironmentDao::new(
+            EnvironmentIdentifier::from_str("env_test").unwrap(),
+            "=-*PATH".to_string(),
+            Some("[~];[INSTALLDIR]bin".to_string().into()),
+            ComponentIdentifier::from_str("comp_test").unwrap(),
+        );
+        assert_round_trip(dao);
+    }
+}
@@ -0,0 +1,186 @@
+use anyhow::bail;
+use anyhow::ensure;
+use getset::Getters;
+use getset::WithSetters;
+
+use crate::constants::DEFAULT_ENVIRONMENT_SEPARATOR;
+
+/// What happens to an environment variable when its component is installed.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, serde::Deserialize)]
+pub enum EnvironmentAction {
+    /// Create the variable, or change it if it already exists.
+    #[default]
+    Set,
+    /// Create the variable only if it does not exist yet.
+    Create,
+    /// Remove the variable. When a value is given the variable is only
+    /// removed if it has that value.
+    Remove,
+}
+
+/// How the value is combined with the existing value of the variable.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, serde::Deserialize)]
+pub enum EnvironmentValuePlacement {
+    /// Replace the existing value.
+    #[default]
+    Replace,
+    /// Put the value in front of the existing value, like a directory that
+    /// should be searched first in `PATH`.
+    Prepend,
+    /// Put the value after the existing value.
+    Append,
+}
+
+/// Whether the variable belongs to the installing user or the whole system.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, serde::Deserialize)]
+pub enum EnvironmentScope {
+    #[default]
+    User,
+    /// A system variable, which requires a per-machine installation.
+    System,
+}
+
+/// A change to an environment variable made by the installation.
+///
+/// ## Example
+///
+/// ```
+/// # use whimsi_lib::tables::environment::environment_variable::EnvironmentScope;
+/// # use whimsi_lib::tables::environment::environment_variable::EnvironmentValuePlacement;
+/// # use whimsi_lib::tables::environment::environment_variable::EnvironmentVariable;
+/// let path = EnvironmentVariable::new("PATH", "[INSTALLDIR]bin")
+///     .with_placement(EnvironmentValuePlacement::Append)
+///     .with_scope(EnvironmentScope::System);
+/// ```
+///
+/// [*Reference*](https://learn.microsoft.com/en-us/windows/win32/msi/environment-table)
+#[derive(Clone, Debug, PartialEq, Getters, WithSetters)]
+#[getset(get = "pub", set_with = "pub")]
+pub struct EnvironmentVariable {
+    name: String,
+    /// Formatted text, so it can reference properties and directories such
+    /// as `[INSTALLDIR]`.
+    value: Option<String>,
+    action: EnvironmentAction,
+    placement: EnvironmentValuePlacement,
+    /// Put between the value and the existing value when prepending or
+    /// appending. Defaults to `;`.
+    separator: char,
+    scope: EnvironmentScope,
+    /// Undo the change when the component is uninstalled. Defaults to
+    /// `true`.
+    remove_on_uninstall: bool,
+}
+
+impl EnvironmentVariable {
+    /// Set `name` to `value`.
+    pub fn new(name: impl ToString, value: impl ToString) -> Self {
+        Self {
+            name: name.to_string(),
+            value: Some(value.to_string()),
+            action: EnvironmentAction::Set,
+            placement: EnvironmentValuePlacement::Replace,
+            separator: DEFAULT_ENVIRONMENT_SEPARATOR,
+            scope: EnvironmentScope::User,
+            remove_on_uninstall: true,
+        }
+    }
+
+    /// Remove `name` whatever its value is.
+    pub fn removal(name: impl ToString) -> Self {
+        Self {
+            value: None,
+            action: EnvironmentAction::Remove,
+            remove_on_uninstall: false,
+            ..Self::new(name, "")
+        }
+    }
+
+    /// The `Name` column, with the prefixes that select the action and
+    /// scope.
+    pub(crate) fn msi_name(&self) -> anyhow::Result<String> {
+        ensure!(
+            !self.name.is_empty(),
+            "Environment variable names cannot be empty"
+        );
+        ensure!(
+            !self.name.starts_with(['=', '+', '-', '!', '*'])
+                && !self.name.contains('='),
+            "Environment variable name [{}] cannot start with a prefix or contain `=`",
+            self.name
+        );
+        let mut name = match self.action {
+            EnvironmentAction::Set => "=",
+            EnvironmentAction::Create => "+",
+            EnvironmentAction::Remove => "!",
+        }
+        .to_string();
+        if self.remove_on_uninstall {
+            name.push('-');
+        }
+        if self.scope == EnvironmentScope::System {
+            name.push('*');
+        }
+        name.push_str(&self.name);
+        Ok(name)
+    }
+
+    /// The `Value` column, with `[~]` standing for the existing value when
+    /// prepending or appending.
+    pub(crate) fn msi_value(&self) -> anyhow::Result<Option<String>> {
+        let separator = self.separator;
+        let value = match (&self.value, self.placement) {
+            (value, EnvironmentValuePlacement::Replace) => value.clone(),
+            (None, placement) => {
+                bail!(
+                    "Environment variable [{}] needs a value to {placement:?}",
+                    self.name
+                )
+            }
+            (Some(_), _) if self.action == EnvironmentAction::Remove => {
+                bail!(
+                    "Environment variable [{}] cannot be partially removed",
+                    self.name
+                )
+            }
+            (Some(value), EnvironmentValuePlacement::Prepend) => {
+                Some(format!("{value}{separator}[~]"))
+            }
+            (Some(value), EnvironmentValuePlacement::Append) => {
+                Some(format!("[~]{separator}{value}"))
+            }
+        };
+        Ok(value)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use test_case::test_case;
+
+    use super::EnvironmentAction;
+    use super::EnvironmentScope;
+    use super::EnvironmentValuePlacement;
+    use super::EnvironmentVariable;
+
+    #[test_case(EnvironmentVariable::new("APP_HOME", "[INSTALLDIR]"), "=-APP_HOME", Some("[INSTALLDIR]"); "set")]
+    #[test_case(EnvironmentVariable::new("PATH", "[INSTALLDIR]bin")
+        .with_placement(EnvironmentValuePlacement::Append)
+        .with_scope(EnvironmentScope::System), "=-*PATH", Some("[~];[INSTALLDIR]bin"); "append system")]
+    #[test_case(EnvironmentVariable::new("PATH", "[INSTALLDIR]bin")
+        .with_placement(EnvironmentValuePlacement::Prepend)
+        .with_separator(':'), "=-PATH", Some("[INSTALLDIR]bin:[~]"); "prepend")]
+    #[test_case(EnvironmentVariable::new("APP_MODE", "fast")
+        .with_action(EnvironmentAction::Create)
+        .with_remove_on_uninstall(false), "+APP_MODE", Some("fast"); "create permanent")]
+    #[test_case(EnvironmentVariable::removal("OLD_HOME"), "!OLD_HOME", None; "remove")]
+    fn columns(variable: EnvironmentVariable, name: &str, value: Option<&str>) {
+        assert_eq!(variable.msi_name().unwrap(), name);
+        assert_eq!(variable.msi_value().unwrap().as_deref(), value);
+    }
+
+    #[test]
+    fn prefixed_names_are_rejected() {
+        assert!(EnvironmentVariable::new("*PATH", "x").msi_name().is_err());
+    }
+}
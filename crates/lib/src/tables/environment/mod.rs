@@ -0,0 +1,3 @@
+pub mod dao;
+pub mod environment_variable;
+pub mod table;
@@ -0,0 +1,15 @@
+use crate::define_generator_table;
+use crate::implement_id_generator_for_table;
+use crate::implement_new_for_id_generator_table;
+use crate::msi_list_boilerplate;
+use crate::msi_table_boilerplate;
+use crate::tables::builder_list::MsiBuilderList;
+use crate::tables::builder_table::MsiBuilderTable;
+use crate::tables::environment::dao::EnvironmentDao;
+use crate::tables::environment::dao::EnvironmentIdGenerator;
+
+define_generator_table!(Environment);
+
+msi_list_boilerplate!(EnvironmentTable, EnvironmentDao);
+implement_id_generator_for_table!(EnvironmentTable, EnvironmentIdGenerator);
+implement_new_for_id_generator_table!(EnvironmentTable, EnvironmentIdGenerator);
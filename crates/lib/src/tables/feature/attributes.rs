@@ -0,0 +1,24 @@
+use bitflags::bitflags;
+
+bitflags! {
+    /// Options for how a feature is installed and shown in the selection
+    /// tree. Without any flags set the feature is installed locally.
+    ///
+    /// [*Reference*](https://learn.microsoft.com/en-us/windows/win32/msi/feature-table)
+    #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+    pub struct FeatureAttributes: i16 {
+        /// Components of the feature are run from the source instead of
+        /// being installed locally.
+        const FAVOR_SOURCE = 1 << 0;
+        /// The feature takes the install state of its parent.
+        const FOLLOW_PARENT = 1 << 1;
+        /// The feature is advertised instead of installed.
+        const FAVOR_ADVERTISE = 1 << 2;
+        /// The feature cannot be advertised.
+        const DISALLOW_ADVERTISE = 1 << 3;
+        /// The user cannot choose to leave the feature absent.
+        const UI_DISALLOW_ABSENT = 1 << 4;
+        /// The feature is only advertised if the system supports it.
+        const NO_UNSUPPORTED_ADVERTISE = 1 << 5;
+    }
+}
@@ -1,23 +1,29 @@
 use getset::Getters;
+use getset::WithSetters;
 
 use crate::constants::*;
 use crate::tables::builder_list_entry::MsiBuilderListEntry;
-use crate::tables::dao::IsDao;
 use crate::tables::feature::identifier::FeatureIdentifier;
 use crate::types::column::identifier::Identifier;
 use crate::types::column::identifier::ToIdentifier;
-use crate::types::helpers::to_msi_value::ToMsiOptionalValue;
 use crate::types::helpers::to_unique_msi_identifier::ToUniqueMsiIdentifier;
 
-#[derive(Clone, Debug, PartialEq, Getters)]
-#[getset(get = "pub")]
+#[derive(
+    Clone, Debug, PartialEq, Getters, WithSetters, whimsi_macros::MsiRow,
+)]
+#[getset(get = "pub", set_with = "pub")]
 pub struct FeatureDao {
+    #[msi(primary_key, category = "Identifier", max_len = FEATURE_IDENTIFIER_MAX_LEN)]
     feature: FeatureIdentifier,
+    #[msi(column = "Feature_Parent", category = "Identifier", max_len = FEATURE_IDENTIFIER_MAX_LEN)]
     feature_parent: Option<FeatureIdentifier>,
+    #[msi(localizable, max_len = TITLE_MAX_LEN)]
     title: Option<String>,
+    #[msi(localizable, max_len = DESCRIPTION_MAX_LEN)]
     description: Option<String>,
     display: Option<i16>,
     level: i16,
+    #[msi(column = "Directory_", category = "UpperCase", max_len = DEFAULT_IDENTIFIER_MAX_LEN)]
     directory: Option<Identifier>,
     attributes: i16,
 }
@@ -29,26 +35,12 @@ impl FeatureDao {
     ) -> FeatureDao {
         Self {
             feature: feature_identifier,
-            title: Some("Default Feature".to_owned()),
+            title: Some(title.to_string()),
             ..Default::default()
         }
     }
 }
 
-impl IsDao for FeatureDao {
-    fn to_row(&self) -> Vec<whimsi_msi::Value> {
-        vec![
-            self.feature.clone().into(),
-            self.feature_parent.to_optional_value(),
-            self.title.to_optional_value(),
-            self.description.to_optional_value(),
-            self.display.to_optional_value(),
-            self.level.into(),
-            self.directory.to_optional_value(),
-            self.attributes.into(),
-        ]
-    }
-}
 impl MsiBuilderListEntry for FeatureDao {
     fn conflicts(&self, other: &Self) -> bool {
         self.feature == other.feature
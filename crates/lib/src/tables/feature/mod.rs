@@ -1,3 +1,4 @@
+pub mod attributes;
 pub mod dao;
 pub mod identifier;
 pub mod table;
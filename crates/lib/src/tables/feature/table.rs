@@ -1,6 +1,3 @@
-use std::cell::RefCell;
-use std::rc::Rc;
-
 use crate::constants::*;
 use crate::define_generator_table;
 use crate::define_identifier_generator;
@@ -16,33 +13,10 @@ use crate::tables::file::dao::FileDao;
 use crate::types::column::identifier::Identifier;
 use crate::types::column::identifier::{self};
 use crate::types::helpers::id_generator::IdGenerator;
+use crate::types::helpers::id_generator::SharedIdentifiers;
 
 define_identifier_generator!(feature);
-define_generator_table!(
-    Feature,
-    vec![
-        whimsi_msi::Column::build("Feature")
-            .primary_key()
-            .id_string(FEATURE_IDENTIFIER_MAX_LEN),
-        whimsi_msi::Column::build("Feature_Parent")
-            .nullable()
-            .id_string(FEATURE_IDENTIFIER_MAX_LEN),
-        whimsi_msi::Column::build("Title")
-            .nullable()
-            .localizable()
-            .string(TITLE_MAX_LEN),
-        whimsi_msi::Column::build("Description")
-            .nullable()
-            .localizable()
-            .string(DESCRIPTION_MAX_LEN),
-        whimsi_msi::Column::build("Display").nullable().int16(),
-        whimsi_msi::Column::build("Level").int16(),
-        whimsi_msi::Column::build("Directory_")
-            .nullable()
-            .id_string(DEFAULT_IDENTIFIER_MAX_LEN),
-        whimsi_msi::Column::build("Attributes").int16(),
-    ]
-);
+define_generator_table!(Feature);
 
 impl FeatureTable {
     pub fn get_default_feature(&self) -> Option<&FeatureDao> {
@@ -51,7 +25,7 @@ impl FeatureTable {
         })
     }
 
-    pub fn new(identifiers: Rc<RefCell<Vec<Identifier>>>) -> Self {
+    pub fn new(identifiers: SharedIdentifiers) -> Self {
         // Adds the default feature by default.
         let entries = vec![FeatureDao::default()];
         let generator = FeatureIdGenerator::from(identifiers);
@@ -1,25 +1,28 @@
 use getset::Getters;
 
+use crate::constants::*;
 use crate::tables::builder_list_entry::MsiBuilderListEntry;
 use crate::tables::component::table::ComponentIdentifier;
-use crate::tables::dao::IsDao;
 use crate::tables::feature::identifier::FeatureIdentifier;
 use crate::types::column::identifier::Identifier;
 use crate::types::helpers::to_unique_msi_identifier::ToUniqueMsiIdentifier;
 
-#[derive(Debug, Clone, Getters, PartialEq, derive_more::Constructor)]
+#[derive(
+    Debug,
+    Clone,
+    Getters,
+    PartialEq,
+    derive_more::Constructor,
+    whimsi_macros::MsiRow,
+)]
 #[getset(get = "pub")]
 pub struct FeatureComponentsDao {
+    #[msi(column = "Feature_", primary_key, category = "Identifier", max_len = FEATURE_IDENTIFIER_MAX_LEN)]
     feature: FeatureIdentifier,
+    #[msi(column = "Component_", primary_key, category = "Identifier", max_len = DEFAULT_IDENTIFIER_MAX_LEN)]
     component: ComponentIdentifier,
 }
 
-impl IsDao for FeatureComponentsDao {
-    fn to_row(&self) -> Vec<whimsi_msi::Value> {
-        vec![self.feature.clone().into(), self.component.clone().into()]
-    }
-}
-
 impl MsiBuilderListEntry for FeatureComponentsDao {
     fn conflicts(&self, other: &Self) -> bool {
         self == other
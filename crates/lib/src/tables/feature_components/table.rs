@@ -1,9 +1,9 @@
-use crate::constants::*;
 use crate::msi_list_boilerplate;
 use crate::msi_table_boilerplate;
 use crate::tables::builder_list::MsiBuilderList;
 use crate::tables::builder_table::MsiBuilderTable;
 use crate::tables::feature_components::dao::FeatureComponentsDao;
+use crate::tables::msi_row::MsiRow;
 
 #[derive(Debug, Clone, Default)]
 pub struct FeatureComponentsTable {
@@ -16,18 +16,11 @@ impl MsiBuilderTable for FeatureComponentsTable {
     msi_table_boilerplate!();
 
     fn name(&self) -> &'static str {
-        "FeatureComponents"
+        FeatureComponentsDao::TABLE_NAME
     }
 
     fn columns(&self) -> Vec<whimsi_msi::Column> {
-        vec![
-            whimsi_msi::Column::build("Feature_")
-                .primary_key()
-                .id_string(FEATURE_IDENTIFIER_MAX_LEN),
-            whimsi_msi::Column::build("Component_")
-                .primary_key()
-                .id_string(DEFAULT_IDENTIFIER_MAX_LEN),
-        ]
+        FeatureComponentsDao::columns()
     }
 }
 
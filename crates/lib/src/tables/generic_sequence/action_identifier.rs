@@ -1,3 +1,4 @@
+use crate::types::column::identifier::Identifier;
 use crate::types::standard_action::StandardAction;
 
 #[derive(
@@ -10,5 +11,5 @@ use crate::types::standard_action::StandardAction;
 )]
 pub enum ActionIdentifier {
     StandardAction(StandardAction),
-    CustomAction,
+    CustomAction(Identifier),
 }
@@ -17,6 +17,16 @@ pub struct GenericSequenceDao {
     sequence: Option<i16>,
 }
 
+impl GenericSequenceDao {
+    pub fn new(
+        action: ActionIdentifier,
+        condition: Option<Condition>,
+        sequence: Option<i16>,
+    ) -> GenericSequenceDao {
+        GenericSequenceDao { action, condition, sequence }
+    }
+}
+
 impl IsDao for GenericSequenceDao {
     fn to_row(&self) -> Vec<whimsi_msi::Value> {
         vec![
@@ -44,7 +54,7 @@ impl From<StandardAction> for GenericSequenceDao {
         Self {
             action: value.into(),
             condition: None,
-            sequence: Some(value as i16),
+            sequence: value.suggested_sequence(),
         }
     }
 }
@@ -1,2 +1,3 @@
 pub mod action_identifier;
 pub mod dao;
+pub mod scheduling;
@@ -0,0 +1,314 @@
+use std::collections::HashMap;
+use std::str::FromStr;
+
+use anyhow::bail;
+use itertools::Itertools;
+use tracing::warn;
+
+use crate::tables::generic_sequence::action_identifier::ActionIdentifier;
+use crate::types::column::condition::Condition;
+use crate::types::standard_action::StandardAction;
+
+/// The tables that actions can be sequenced in.
+///
+/// [*Reference*](https://learn.microsoft.com/en-us/windows/win32/msi/sequence-tables)
+#[derive(
+    Clone, Copy, Debug, PartialEq, Eq, Hash, strum::Display, strum::EnumIter,
+)]
+pub enum SequenceTable {
+    #[strum(serialize = "AdminUISequence")]
+    AdminUi,
+    #[strum(serialize = "AdminExecuteSequence")]
+    AdminExecute,
+    #[strum(serialize = "AdvtExecuteSequence")]
+    AdvtExecute,
+    #[strum(serialize = "InstallUISequence")]
+    InstallUi,
+    #[strum(serialize = "InstallExecuteSequence")]
+    InstallExecute,
+}
+
+/// Where an action is placed in a sequence table.
+#[derive(Clone, Debug, PartialEq)]
+pub enum ActionPlacement {
+    /// An absolute sequence number.
+    Sequence(i16),
+    /// Directly before another action in the same table.
+    Before(ActionIdentifier),
+    /// Directly after another action in the same table.
+    After(ActionIdentifier),
+}
+
+/// An action that is given its sequence number when the package is built.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct RelativeAction {
+    pub(crate) table: SequenceTable,
+    pub(crate) action: ActionIdentifier,
+    pub(crate) condition: Option<Condition>,
+    pub(crate) placement: RelativePlacement,
+}
+
+/// An action placed relative to another, waiting for a sequence number.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct RelativePlacement {
+    pub(crate) action: String,
+    pub(crate) anchor: String,
+    pub(crate) before: bool,
+}
+
+/// Assigns sequence numbers to actions placed relative to others.
+///
+/// Actions placed relative to the same action keep the order they were
+/// given in and take the numbers directly next to it. Returns the sequence
+/// number of each entry of `relative`, in order.
+pub(crate) fn resolve_sequence_numbers(
+    table: SequenceTable,
+    sequenced: &[(String, i16)],
+    relative: &[RelativePlacement],
+) -> anyhow::Result<Vec<i16>> {
+    let sequence_of =
+        sequenced.iter().cloned().collect::<HashMap<String, i16>>();
+    let index_of = relative
+        .iter()
+        .enumerate()
+        .map(|(index, placement)| (placement.action.clone(), index))
+        .collect::<HashMap<String, usize>>();
+    for (index, placement) in relative.iter().enumerate() {
+        if sequence_of.contains_key(&placement.action)
+            || index_of[&placement.action] != index
+        {
+            bail!(
+                "Action [{}] is scheduled more than once in {table}",
+                placement.action
+            );
+        }
+    }
+
+    // Every chain of relative placements has to end at a sequenced action.
+    for placement in relative {
+        let mut chain = vec![placement.action.clone()];
+        let mut anchor = &placement.anchor;
+        while !sequence_of.contains_key(anchor) {
+            let Some(&index) = index_of.get(anchor) else {
+                bail!(
+                    "Action [{}] is scheduled relative to [{anchor}], which is not in {table}",
+                    chain.last().unwrap()
+                );
+            };
+            if chain.contains(anchor) {
+                bail!(
+                    "Actions [{}] in {table} are scheduled relative to each other in a cycle",
+                    chain.iter().join(", ")
+                );
+            }
+            chain.push(anchor.clone());
+            anchor = &relative[index].anchor;
+        }
+    }
+
+    let mut befores = HashMap::<&str, Vec<usize>>::new();
+    let mut afters = HashMap::<&str, Vec<usize>>::new();
+    for (index, placement) in relative.iter().enumerate() {
+        let children =
+            if placement.before { &mut befores } else { &mut afters };
+        children.entry(placement.anchor.as_str()).or_default().push(index);
+    }
+    // Orders an action with everything placed relative to it.
+    fn expand(
+        index: usize,
+        relative: &[RelativePlacement],
+        befores: &HashMap<&str, Vec<usize>>,
+        afters: &HashMap<&str, Vec<usize>>,
+        order: &mut Vec<usize>,
+    ) {
+        let name = relative[index].action.as_str();
+        for &before in befores.get(name).into_iter().flatten() {
+            expand(before, relative, befores, afters, order);
+        }
+        order.push(index);
+        for &after in afters.get(name).into_iter().flatten() {
+            expand(after, relative, befores, afters, order);
+        }
+    }
+
+    let mut numbers = vec![0; relative.len()];
+    for (anchor, sequence) in sequenced {
+        let sequence = i32::from(*sequence);
+        let mut before_order = Vec::new();
+        for &index in befores.get(anchor.as_str()).into_iter().flatten() {
+            expand(index, relative, &befores, &afters, &mut before_order);
+        }
+        let mut after_order = Vec::new();
+        for &index in afters.get(anchor.as_str()).into_iter().flatten() {
+            expand(index, relative, &befores, &afters, &mut after_order);
+        }
+        let first = sequence - before_order.len() as i32;
+        let last = sequence + after_order.len() as i32;
+        if first < 1 || last > i32::from(i16::MAX) {
+            bail!(
+                "There is no room to schedule actions around [{anchor}] in {table}"
+            );
+        }
+        for (offset, index) in before_order.into_iter().enumerate() {
+            numbers[index] = (first + offset as i32) as i16;
+        }
+        for (offset, index) in after_order.into_iter().enumerate() {
+            numbers[index] = (sequence + 1 + offset as i32) as i16;
+        }
+    }
+
+    let mut taken = HashMap::<i16, &str>::new();
+    let all =
+        sequenced
+            .iter()
+            .map(|(action, sequence)| (action.as_str(), *sequence))
+            .chain(relative.iter().zip(&numbers).map(|(placement, number)| {
+                (placement.action.as_str(), *number)
+            }))
+            // Negative numbers mark dialogs shown on exit and zero is never run,
+            // neither take part in the ordering.
+            .filter(|(_, sequence)| *sequence > 0);
+    for (action, sequence) in all {
+        if let Some(other) = taken.insert(sequence, action) {
+            bail!(
+                "Actions [{other}] and [{action}] in {table} both have sequence number {sequence}"
+            );
+        }
+    }
+
+    warn_about_standard_order(table, sequenced, relative, &numbers);
+    Ok(numbers)
+}
+
+/// Logs standard actions that were placed out of their suggested order.
+fn warn_about_standard_order(
+    table: SequenceTable,
+    sequenced: &[(String, i16)],
+    relative: &[RelativePlacement],
+    numbers: &[i16],
+) {
+    let suggested = |action: &str| {
+        StandardAction::from_str(action)
+            .ok()
+            .and_then(|action| action.suggested_sequence())
+    };
+    let standard = sequenced
+        .iter()
+        .filter_map(|(action, sequence)| {
+            Some((action, *sequence, suggested(action)?))
+        })
+        .collect_vec();
+    for (placement, number) in relative.iter().zip(numbers) {
+        let Some(expected) = suggested(&placement.action) else {
+            continue;
+        };
+        for (other, sequence, other_expected) in &standard {
+            if expected != *other_expected
+                && (expected < *other_expected) != (*number < *sequence)
+            {
+                warn!(
+                    "Standard action [{}] is scheduled out of its suggested order relative to [{other}] in {table}",
+                    placement.action
+                );
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::RelativePlacement;
+    use super::SequenceTable;
+    use super::resolve_sequence_numbers;
+
+    fn sequenced() -> Vec<(String, i16)> {
+        vec![
+            ("InstallInitialize".to_string(), 1500),
+            ("InstallFiles".to_string(), 4000),
+            ("InstallFinalize".to_string(), 6600),
+        ]
+    }
+
+    fn placement(
+        action: &str,
+        before: bool,
+        anchor: &str,
+    ) -> RelativePlacement {
+        RelativePlacement {
+            action: action.to_string(),
+            anchor: anchor.to_string(),
+            before,
+        }
+    }
+
+    #[test]
+    fn assigns_neighbouring_numbers() {
+        let relative = vec![
+            placement("A", false, "InstallFiles"),
+            placement("B", false, "InstallFiles"),
+            placement("C", true, "InstallFinalize"),
+            placement("D", false, "A"),
+            placement("E", true, "A"),
+        ];
+        let numbers = resolve_sequence_numbers(
+            SequenceTable::InstallExecute,
+            &sequenced(),
+            &relative,
+        )
+        .unwrap();
+        // InstallFiles, E, A, D, B
+        assert_eq!(numbers, vec![4002, 4004, 6599, 4003, 4001]);
+    }
+
+    #[test]
+    fn detects_cycles() {
+        let relative =
+            vec![placement("A", false, "B"), placement("B", true, "A")];
+        let error = resolve_sequence_numbers(
+            SequenceTable::InstallUi,
+            &sequenced(),
+            &relative,
+        )
+        .unwrap_err();
+        assert!(error.to_string().contains("cycle"));
+    }
+
+    #[test]
+    fn detects_missing_anchors() {
+        let relative = vec![placement("A", false, "Missing")];
+        assert!(
+            resolve_sequence_numbers(
+                SequenceTable::AdminUi,
+                &sequenced(),
+                &relative,
+            )
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn detects_conflicts() {
+        let mut sequenced = sequenced();
+        sequenced.push(("Existing".to_string(), 4001));
+        let relative = vec![placement("A", false, "InstallFiles")];
+        assert!(
+            resolve_sequence_numbers(
+                SequenceTable::InstallExecute,
+                &sequenced,
+                &relative,
+            )
+            .is_err()
+        );
+
+        let relative =
+            vec![placement("InstallFiles", false, "InstallFinalize")];
+        assert!(
+            resolve_sequence_numbers(
+                SequenceTable::InstallExecute,
+                &self::sequenced(),
+                &relative,
+            )
+            .is_err()
+        );
+    }
+}
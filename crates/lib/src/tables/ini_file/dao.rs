@@ -0,0 +1,69 @@
+use getset::Getters;
+
+use crate::constants::*;
+use crate::define_identifier_generator;
+use crate::define_specific_identifier;
+use crate::define_specific_identifier_parsing;
+use crate::tables::builder_list_entry::MsiBuilderListEntry;
+use crate::tables::component::table::ComponentIdentifier;
+use crate::tables::ini_file::ini_file_action::IniFileAction;
+use crate::types::column::filename::Filename;
+use crate::types::column::formatted::Formatted;
+use crate::types::column::formatted::FormattedColumns;
+use crate::types::column::identifier::Identifier;
+use crate::types::helpers::to_unique_msi_identifier::ToUniqueMsiIdentifier;
+
+define_specific_identifier!(IniFile);
+define_specific_identifier_parsing!(IniFile);
+define_identifier_generator!(IniFile);
+
+/// An entry that is written to an INI file when the owning component is
+/// installed. The file is in the directory that `dir_property` resolves to,
+/// or the Windows folder when it is `None`.
+///
+/// [*Reference*](https://learn.microsoft.com/en-us/windows/win32/msi/inifile-table)
+#[derive(
+    Debug,
+    Clone,
+    PartialEq,
+    Getters,
+    derive_more::Constructor,
+    whimsi_macros::MsiRow,
+)]
+#[getset(get = "pub")]
+pub struct IniFileDao {
+    #[msi(primary_key, category = "Identifier", max_len = DEFAULT_IDENTIFIER_MAX_LEN)]
+    ini_file: IniFileIdentifier,
+    #[msi(column = "FileName", localizable, category = "Filename", max_len = FILENAME_MAX_LEN)]
+    filename: Filename,
+    #[msi(category = "Identifier", max_len = DEFAULT_IDENTIFIER_MAX_LEN)]
+    dir_property: Option<Identifier>,
+    #[msi(localizable, category = "Formatted", max_len = INI_SECTION_MAX_LEN)]
+    section: Formatted,
+    #[msi(localizable, category = "Formatted", max_len = INI_KEY_MAX_LEN)]
+    key: Formatted,
+    #[msi(localizable, category = "Formatted", max_len = INI_VALUE_MAX_LEN)]
+    value: Formatted,
+    #[msi(int16)]
+    action: IniFileAction,
+    #[msi(column = "Component_", category = "Identifier", max_len = DEFAULT_IDENTIFIER_MAX_LEN)]
+    component: ComponentIdentifier,
+}
+
+impl ToUniqueMsiIdentifier for IniFileDao {
+    fn to_unique_msi_identifier(&self) -> Option<Identifier> {
+        self.ini_file.to_unique_msi_identifier()
+    }
+}
+
+impl MsiBuilderListEntry for IniFileDao {
+    fn conflicts(&self, other: &Self) -> bool {
+        self.ini_file == other.ini_file
+    }
+}
+
+impl FormattedColumns for IniFileDao {
+    fn formatted_columns(&self) -> Vec<&Formatted> {
+        vec![&self.section, &self.key, &self.value]
+    }
+}
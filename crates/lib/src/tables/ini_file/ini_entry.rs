@@ -0,0 +1,16 @@
+use getset::Getters;
+
+use crate::tables::directory::directory_identifier::DirectoryIdentifier;
+
+/// The location of an entry in an INI file.
+#[derive(Clone, Debug, PartialEq, Getters, derive_more::Constructor)]
+#[getset(get = "pub")]
+pub struct IniEntry {
+    /// The directory that holds the file, or the Windows folder when `None`.
+    directory: Option<DirectoryIdentifier>,
+    file_name: String,
+    /// Formatted text naming the section, without the brackets.
+    section: String,
+    /// Formatted text naming the key.
+    key: String,
+}
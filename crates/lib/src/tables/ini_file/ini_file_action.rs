@@ -0,0 +1,65 @@
+use std::str::FromStr;
+
+use anyhow::bail;
+
+/// How an entry in the `IniFile` table is written.
+///
+/// [*Reference*](https://learn.microsoft.com/en-us/windows/win32/msi/inifile-table)
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum IniFileAction {
+    /// Create the entry, or replace its value if it already exists.
+    AddLine = 0,
+    /// Create the entry only if it does not exist yet.
+    CreateLine = 1,
+    /// Create the entry, or append the value to it as a comma separated tag
+    /// if it already exists.
+    AddTag = 3,
+}
+
+impl From<IniFileAction> for whimsi_msi::Value {
+    fn from(value: IniFileAction) -> whimsi_msi::Value {
+        whimsi_msi::Value::Int(value as i32)
+    }
+}
+
+impl FromStr for IniFileAction {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> anyhow::Result<Self> {
+        Ok(match s.parse::<i32>() {
+            Ok(0) => IniFileAction::AddLine,
+            Ok(1) => IniFileAction::CreateLine,
+            Ok(3) => IniFileAction::AddTag,
+            _ => bail!("[{s}] is not a valid IniFile action"),
+        })
+    }
+}
+
+/// How an entry in the `RemoveIniFile` table is removed.
+///
+/// [*Reference*](https://learn.microsoft.com/en-us/windows/win32/msi/removeinifile-table)
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RemoveIniFileAction {
+    /// Remove the whole entry.
+    RemoveLine = 2,
+    /// Remove a single comma separated tag from the entry's value.
+    RemoveTag = 4,
+}
+
+impl From<RemoveIniFileAction> for whimsi_msi::Value {
+    fn from(value: RemoveIniFileAction) -> whimsi_msi::Value {
+        whimsi_msi::Value::Int(value as i32)
+    }
+}
+
+impl FromStr for RemoveIniFileAction {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> anyhow::Result<Self> {
+        Ok(match s.parse::<i32>() {
+            Ok(2) => RemoveIniFileAction::RemoveLine,
+            Ok(4) => RemoveIniFileAction::RemoveTag,
+            _ => bail!("[{s}] is not a valid RemoveIniFile action"),
+        })
+    }
+}
@@ -0,0 +1,4 @@
+pub mod dao;
+pub mod ini_entry;
+pub mod ini_file_action;
+pub mod table;
@@ -0,0 +1,22 @@
+use crate::define_generator_table;
+use crate::implement_id_generator_for_table;
+use crate::implement_new_for_id_generator_table;
+use crate::msi_list_boilerplate;
+use crate::msi_table_boilerplate;
+use crate::tables::builder_list::MsiBuilderList;
+use crate::tables::builder_table::MsiBuilderTable;
+use crate::tables::ini_file::dao::IniFileDao;
+use crate::tables::ini_file::dao::IniFileIdGenerator;
+use crate::tables::ini_file::dao::IniFileIdentifier;
+
+define_generator_table!(IniFile);
+
+msi_list_boilerplate!(IniFileTable, IniFileDao);
+implement_id_generator_for_table!(IniFileTable, IniFileIdGenerator);
+implement_new_for_id_generator_table!(IniFileTable, IniFileIdGenerator);
+
+impl IniFileTable {
+    pub fn entry_with_id(&self, id: &IniFileIdentifier) -> Option<&IniFileDao> {
+        self.entries.iter().find(|dao| dao.ini_file() == id)
+    }
+}
@@ -0,0 +1,47 @@
+use getset::Getters;
+
+use crate::constants::*;
+use crate::tables::builder_list_entry::MsiBuilderListEntry;
+use crate::tables::signature::table::SignatureIdentifier;
+use crate::types::column::identifier::Identifier;
+use crate::types::helpers::to_unique_msi_identifier::ToUniqueMsiIdentifier;
+
+/// Searches the `key` entry of `section` in the INI file `filename`, found in
+/// the Windows folder. `field` picks a comma separated field of the value,
+/// with `0` meaning the whole value.
+///
+/// [*Reference*](https://learn.microsoft.com/en-us/windows/win32/msi/inilocator-table)
+#[derive(
+    Debug,
+    Clone,
+    Getters,
+    PartialEq,
+    derive_more::Constructor,
+    whimsi_macros::MsiRow,
+)]
+#[getset(get = "pub")]
+pub struct IniLocatorDao {
+    #[msi(column = "Signature_", primary_key, category = "Identifier", max_len = DEFAULT_IDENTIFIER_MAX_LEN)]
+    signature: SignatureIdentifier,
+    #[msi(column = "FileName", category = "Filename", max_len = FILENAME_MAX_LEN)]
+    filename: String,
+    #[msi(category = "Text", max_len = INI_SECTION_MAX_LEN)]
+    section: String,
+    #[msi(category = "Text", max_len = INI_KEY_MAX_LEN)]
+    key: String,
+    field: Option<i16>,
+    #[msi(column = "Type")]
+    typ: Option<i16>,
+}
+
+impl MsiBuilderListEntry for IniLocatorDao {
+    fn conflicts(&self, other: &Self) -> bool {
+        self.signature == other.signature
+    }
+}
+
+impl ToUniqueMsiIdentifier for IniLocatorDao {
+    fn to_unique_msi_identifier(&self) -> Option<Identifier> {
+        None
+    }
+}
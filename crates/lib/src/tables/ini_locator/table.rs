@@ -0,0 +1,27 @@
+use crate::msi_list_boilerplate;
+use crate::msi_table_boilerplate;
+use crate::tables::builder_list::MsiBuilderList;
+use crate::tables::builder_table::MsiBuilderTable;
+use crate::tables::ini_locator::dao::IniLocatorDao;
+use crate::tables::msi_row::MsiRow;
+
+#[derive(Debug, Clone, Default)]
+pub struct IniLocatorTable {
+    entries: Vec<IniLocatorDao>,
+}
+
+impl MsiBuilderTable for IniLocatorTable {
+    type TableValue = IniLocatorDao;
+
+    msi_table_boilerplate!();
+
+    fn name(&self) -> &'static str {
+        IniLocatorDao::TABLE_NAME
+    }
+
+    fn columns(&self) -> Vec<whimsi_msi::Column> {
+        IniLocatorDao::columns()
+    }
+}
+
+msi_list_boilerplate!(IniLocatorTable, IniLocatorDao);
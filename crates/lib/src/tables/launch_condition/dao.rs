@@ -1,16 +1,22 @@
+use getset::Getters;
+
 use crate::{
     tables::{builder_list_entry::MsiBuilderListEntry, dao::IsDao},
     types::{
         column::{
             condition::{self, Condition},
-            formatted::Formatted,
+            formatted::{Formatted, FormattedColumns},
             identifier::Identifier,
         },
         helpers::to_unique_msi_identifier::ToUniqueMsiIdentifier,
     },
 };
 
-#[derive(Debug, Clone, PartialEq)]
+/// Stops the installation with `description` unless `condition` is true.
+///
+/// [*Reference*](https://learn.microsoft.com/en-us/windows/win32/msi/launchcondition-table)
+#[derive(Debug, Clone, PartialEq, Getters, derive_more::Constructor)]
+#[getset(get = "pub")]
 pub struct LaunchConditionDao {
     condition: Condition,
     description: Formatted,
@@ -18,7 +24,7 @@ pub struct LaunchConditionDao {
 
 impl MsiBuilderListEntry for LaunchConditionDao {
     fn conflicts(&self, other: &Self) -> bool {
-        self == other
+        self.condition == other.condition
     }
 }
 
@@ -33,3 +39,9 @@ impl IsDao for LaunchConditionDao {
         vec![self.condition.clone().into(), self.description.clone().into()]
     }
 }
+
+impl FormattedColumns for LaunchConditionDao {
+    fn formatted_columns(&self) -> Vec<&Formatted> {
+        vec![&self.description]
+    }
+}
@@ -1,3 +1,4 @@
+use crate::tables::directory::directory_identifier::DirectoryIdentifier;
 use crate::tables::file::table::FileIdentifier;
 use crate::tables::registry::dao::RegistryIdentifier;
 use crate::tables::service_install::table::ServiceInstallIdentifier;
@@ -17,7 +18,8 @@ use crate::types::column::identifier::ambassador_impl_ToIdentifier;
 pub enum LockObject {
     File(FileIdentifier),
     Registry(RegistryIdentifier),
-    // CreateFolder(CreateFolderIdentifier),
+    /// A directory in the `CreateFolder` table.
+    CreateFolder(DirectoryIdentifier),
     ServiceInstall(ServiceInstallIdentifier),
 }
 
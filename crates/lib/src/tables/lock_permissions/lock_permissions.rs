@@ -13,6 +13,6 @@ pub enum LockPermissions {
 
 impl From<LockPermissions> for Value {
     fn from(value: LockPermissions) -> Value {
-        (value as i16).into()
+        (value as i32).into()
     }
 }
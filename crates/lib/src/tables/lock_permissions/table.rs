@@ -28,12 +28,25 @@ impl MsiBuilderTable for LockPermissionsTable {
     fn columns(&self) -> Vec<whimsi_msi::Column> {
         vec![
             whimsi_msi::Column::build("LockObject").primary_key().id_string(72),
-            whimsi_msi::Column::build("Table").text_string(255),
+            // The reference schema predates `CreateFolder` and
+            // `ServiceInstall` entries, so the table is created from these
+            // columns instead.
+            whimsi_msi::Column::build("Table")
+                .primary_key()
+                .enum_values(&[
+                    "CreateFolder",
+                    "File",
+                    "Registry",
+                    "ServiceInstall",
+                ])
+                .id_string(32),
             whimsi_msi::Column::build("Domain")
+                .primary_key()
                 .nullable()
                 .category(whimsi_msi::Category::Formatted)
                 .string(255),
             whimsi_msi::Column::build("User")
+                .primary_key()
                 .category(whimsi_msi::Category::Formatted)
                 .string(255),
             whimsi_msi::Column::build("Permission").nullable().int32(),
@@ -1,5 +1,13 @@
 #[macro_export]
 macro_rules! define_generator_table {
+    ($var:ident) => {
+        pastey::paste! {
+            $crate::define_generator_table!(
+                $var,
+                <[<$var:camel Dao>] as $crate::tables::msi_row::MsiRow>::columns()
+            );
+        }
+    };
     ($var:ident, $columns:expr) => {
         pastey::paste! {
             #[derive(Debug, Clone, PartialEq, getset::Getters)]
@@ -1,6 +1,4 @@
-use std::cell::RefCell;
 use std::fmt::Display;
-use std::rc::Rc;
 use std::str::FromStr;
 
 use anyhow::bail;
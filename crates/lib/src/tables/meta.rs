@@ -4,6 +4,7 @@ use derivative::Derivative;
 use getset::Getters;
 use getset::Setters;
 use getset::WithSetters;
+use whimsi_msi::CfbVersion;
 use whimsi_msi::Language;
 use whimsi_msi::PackageType;
 
@@ -26,6 +27,10 @@ pub struct MetaInformation {
     /// `ReadOnlyRecommended` when the `PackageType` is `Installer` and
     /// `ReadOnlyEnforced` for `PackageType` `Transform` and `Patch`.
     security: Option<DocSecurity>,
+    /// Version of the compound file format the MSI is written in. Defaults to
+    /// `V3` (512-byte sectors), which is limited to roughly 2 GB. Use `V4`
+    /// (4096-byte sectors) for packages that embed larger cabinets.
+    cfb_version: CfbVersion,
 }
 
 impl MetaInformation {
@@ -39,6 +44,7 @@ impl MetaInformation {
             keywords: Vec::new(),
             languages: Vec::new(),
             security: None,
+            cfb_version: CfbVersion::V3,
         }
     }
 }
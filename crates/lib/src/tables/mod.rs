@@ -6,16 +6,24 @@ pub mod binary;
 pub mod builder_list;
 pub mod builder_list_entry;
 pub(crate) mod builder_table;
+pub mod ccp_search;
+pub mod comp_locator;
 pub mod component;
+pub mod condition;
+pub mod create_folder;
 pub mod custom_action;
 pub(crate) mod dao;
 pub mod directory;
+pub mod dr_locator;
+pub mod environment;
 pub mod feature;
 pub mod feature_components;
 pub mod file;
 pub mod generic_sequence;
 pub mod icon;
 pub(crate) mod id_generator_builder_list;
+pub mod ini_file;
+pub mod ini_locator;
 pub mod install_execute_sequence;
 pub mod install_ui_sequence;
 pub mod launch_condition;
@@ -24,14 +32,19 @@ pub(crate) mod macros;
 pub mod media;
 pub mod meta;
 pub mod msi_file_hash;
+pub mod msi_row;
 pub mod property;
 pub mod reg_locator;
 pub mod registry;
+pub mod remove_file;
+pub mod remove_ini_file;
+pub mod remove_registry;
 pub mod service_control;
 pub mod service_install;
 pub mod shortcut;
 pub mod signature;
 pub mod table_entry;
+pub mod upgrade;
 
 // TODO: Look at Directory to see the form that I eventually want to have implemented.
 #[derive(strum::EnumIter)]
@@ -6,20 +6,25 @@ use md5::Md5;
 use md5::digest::generic_array::GenericArray;
 use tracing::debug;
 
+use crate::constants::DEFAULT_IDENTIFIER_MAX_LEN;
 use crate::tables::builder_list_entry::MsiBuilderListEntry;
-use crate::tables::dao::IsDao;
 use crate::tables::file::table::FileIdentifier;
 use crate::tables::file::{self};
 use crate::types::column::identifier::Identifier;
 use crate::types::helpers::to_unique_msi_identifier::ToUniqueMsiIdentifier;
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, whimsi_macros::MsiRow)]
 pub struct MsiFileHashDao {
+    #[msi(column = "File_", primary_key, category = "Identifier", max_len = DEFAULT_IDENTIFIER_MAX_LEN)]
     file: FileIdentifier,
     options: i16,
+    #[msi(column = "HashPart1")]
     hash_part_1: i32,
+    #[msi(column = "HashPart2")]
     hash_part_2: i32,
+    #[msi(column = "HashPart3")]
     hash_part_3: i32,
+    #[msi(column = "HashPart4")]
     hash_part_4: i32,
 }
 
@@ -35,19 +40,6 @@ impl ToUniqueMsiIdentifier for MsiFileHashDao {
     }
 }
 
-impl IsDao for MsiFileHashDao {
-    fn to_row(&self) -> Vec<whimsi_msi::Value> {
-        vec![
-            self.file.clone().into(),
-            self.options.into(),
-            self.hash_part_1.into(),
-            self.hash_part_2.into(),
-            self.hash_part_3.into(),
-            self.hash_part_4.into(),
-        ]
-    }
-}
-
 impl MsiFileHashDao {
     pub fn from_path(
         file_id: FileIdentifier,
@@ -1,9 +1,9 @@
-use crate::constants::DEFAULT_IDENTIFIER_MAX_LEN;
 use crate::msi_list_boilerplate;
 use crate::msi_table_boilerplate;
 use crate::tables::builder_list::MsiBuilderList;
 use crate::tables::builder_table::MsiBuilderTable;
 use crate::tables::msi_file_hash::dao::MsiFileHashDao;
+use crate::tables::msi_row::MsiRow;
 
 #[derive(Debug, Clone, PartialEq, Default)]
 pub struct MsiFileHashTable {
@@ -16,20 +16,11 @@ impl MsiBuilderTable for MsiFileHashTable {
     msi_table_boilerplate!();
 
     fn name(&self) -> &'static str {
-        "MsiFileHash"
+        MsiFileHashDao::TABLE_NAME
     }
 
     fn columns(&self) -> Vec<whimsi_msi::Column> {
-        vec![
-            whimsi_msi::Column::build("File_")
-                .primary_key()
-                .id_string(DEFAULT_IDENTIFIER_MAX_LEN),
-            whimsi_msi::Column::build("Options").int16(),
-            whimsi_msi::Column::build("HashPart1").int32(),
-            whimsi_msi::Column::build("HashPart2").int32(),
-            whimsi_msi::Column::build("HashPart3").int32(),
-            whimsi_msi::Column::build("HashPart4").int32(),
-        ]
+        MsiFileHashDao::columns()
     }
 }
 
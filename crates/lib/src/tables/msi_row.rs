@@ -0,0 +1,86 @@
+use std::str::FromStr;
+
+use anyhow::Context;
+use anyhow::bail;
+
+/// A struct that maps one-to-one onto a row of an MSI table.
+///
+/// This is normally implemented with `#[derive(whimsi_macros::MsiRow)]`,
+/// which generates the column definitions and the conversions in both
+/// directions from the struct's fields so they cannot drift apart.
+pub trait MsiRow: Sized {
+    /// Name of the table that rows of this type are written to.
+    const TABLE_NAME: &'static str;
+
+    /// Column definitions for the table, in the same order as `to_row`.
+    fn columns() -> Vec<whimsi_msi::Column>;
+
+    fn to_row(&self) -> Vec<whimsi_msi::Value>;
+
+    fn from_row(row: &whimsi_msi::Row) -> anyhow::Result<Self>;
+}
+
+/// Reads a non-nullable column from `row` and parses it into `T`.
+pub fn read_column<T>(
+    row: &whimsi_msi::Row,
+    table: &str,
+    column: &str,
+) -> anyhow::Result<T>
+where
+    T: FromStr,
+    T::Err: Into<anyhow::Error>,
+{
+    match read_optional_column(row, table, column)? {
+        Some(value) => Ok(value),
+        None => bail!("Column {column} in table {table} is unexpectedly null"),
+    }
+}
+
+/// Reads a nullable column from `row`, returning `None` for null values.
+pub fn read_optional_column<T>(
+    row: &whimsi_msi::Row,
+    table: &str,
+    column: &str,
+) -> anyhow::Result<Option<T>>
+where
+    T: FromStr,
+    T::Err: Into<anyhow::Error>,
+{
+    if !row.has_column(column) {
+        bail!("Table {table} has no column named {column}");
+    }
+    let text = match &row[column] {
+        whimsi_msi::Value::Null => return Ok(None),
+        whimsi_msi::Value::Int(number) => number.to_string(),
+        whimsi_msi::Value::Str(string) => string.clone(),
+    };
+    let value = text.parse::<T>().map_err(Into::into).with_context(|| {
+        format!("Failed to parse column {column} in table {table}")
+    })?;
+    Ok(Some(value))
+}
+
+/// Writes `dao` to a fresh package and asserts that reading it back gives
+/// the same row.
+#[cfg(test)]
+pub(crate) fn assert_round_trip<T>(dao: T)
+where
+    T: MsiRow + PartialEq + std::fmt::Debug,
+{
+    let mut package = whimsi_msi::Package::create(
+        whimsi_msi::PackageType::Installer,
+        std::io::Cursor::new(Vec::new()),
+    )
+    .unwrap();
+    package.create_table(T::TABLE_NAME, T::columns()).unwrap();
+    package
+        .insert_rows(whimsi_msi::Insert::into(T::TABLE_NAME).row(dao.to_row()))
+        .unwrap();
+
+    let rows: Vec<_> = package
+        .select_rows(whimsi_msi::Select::table(T::TABLE_NAME))
+        .unwrap()
+        .collect();
+    assert_eq!(rows.len(), 1);
+    assert_eq!(T::from_row(&rows[0]).unwrap(), dao);
+}
@@ -1,10 +1,13 @@
+use getset::Getters;
+
 use crate::tables::builder_list_entry::MsiBuilderListEntry;
 use crate::tables::dao::IsDao;
 use crate::tables::property::property_text::PropertyText;
 use crate::types::column::identifier::Identifier;
 use crate::types::helpers::to_unique_msi_identifier::ToUniqueMsiIdentifier;
 
-#[derive(Clone, Debug, Default, derive_more::Constructor)]
+#[derive(Clone, Debug, Default, Getters, derive_more::Constructor)]
+#[getset(get = "pub(crate)")]
 pub struct PropertyDao {
     property: Identifier,
     value: PropertyText,
@@ -1,25 +1,37 @@
-use crate::{
-    tables::{
-        builder_list_entry::MsiBuilderListEntry, dao::IsDao,
-        signature::table::SignatureIdentifier,
-    },
-    types::{
-        column::{
-            formatted::Formatted, identifier::Identifier, reg_path::RegPath,
-        },
-        helpers::{
-            to_msi_value::ToMsiOptionalValue,
-            to_unique_msi_identifier::ToUniqueMsiIdentifier,
-        },
-    },
-};
+use getset::Getters;
 
-#[derive(Debug, Clone, PartialEq)]
+use crate::constants::*;
+use crate::tables::builder_list_entry::MsiBuilderListEntry;
+use crate::tables::registry::registry_root::RegistryRoot;
+use crate::tables::signature::table::SignatureIdentifier;
+use crate::types::column::formatted::Formatted;
+use crate::types::column::identifier::Identifier;
+use crate::types::column::reg_path::RegPath;
+use crate::types::helpers::to_unique_msi_identifier::ToUniqueMsiIdentifier;
+
+/// Searches the registry value `name` under `key` for a directory, a file
+/// or a raw value, depending on `typ`.
+///
+/// [*Reference*](https://learn.microsoft.com/en-us/windows/win32/msi/reglocator-table)
+#[derive(
+    Debug,
+    Clone,
+    Getters,
+    PartialEq,
+    derive_more::Constructor,
+    whimsi_macros::MsiRow,
+)]
+#[getset(get = "pub")]
 pub struct RegLocatorDao {
+    #[msi(column = "Signature_", primary_key, category = "Identifier", max_len = DEFAULT_IDENTIFIER_MAX_LEN)]
     signature: SignatureIdentifier,
-    root: i16,
+    #[msi(int16)]
+    root: RegistryRoot,
+    #[msi(category = "RegPath", max_len = REGPATH_MAX_LEN)]
     key: RegPath,
+    #[msi(category = "Formatted", max_len = REGISTRY_NAME_MAX_LEN)]
     name: Option<Formatted>,
+    #[msi(column = "Type")]
     typ: Option<i16>,
 }
 
@@ -29,18 +41,6 @@ impl ToUniqueMsiIdentifier for RegLocatorDao {
     }
 }
 
-impl IsDao for RegLocatorDao {
-    fn to_row(&self) -> Vec<whimsi_msi::Value> {
-        vec![
-            self.signature.clone().into(),
-            self.root.into(),
-            self.key.clone().into(),
-            self.name.to_optional_value(),
-            self.typ.to_optional_value(),
-        ]
-    }
-}
-
 impl MsiBuilderListEntry for RegLocatorDao {
     fn conflicts(&self, other: &Self) -> bool {
         self.signature == other.signature
@@ -1,11 +1,9 @@
 use crate::{
-    constants::{DEFAULT_IDENTIFIER_MAX_LEN, REGPATH_MAX_LEN},
     msi_list_boilerplate, msi_table_boilerplate,
     tables::{
-        builder_table::MsiBuilderTable, reg_locator::dao::RegLocatorDao,
-        signature::table::SignatureIdentifier,
+        builder_table::MsiBuilderTable, msi_row::MsiRow,
+        reg_locator::dao::RegLocatorDao,
     },
-    types::column::{formatted::Formatted, reg_path::RegPath},
 };
 
 #[derive(Debug, Clone, PartialEq, Default)]
@@ -20,20 +18,10 @@ impl MsiBuilderTable for RegLocatorTable {
     msi_table_boilerplate!();
 
     fn name(&self) -> &'static str {
-        "RegLocator"
+        RegLocatorDao::TABLE_NAME
     }
 
     fn columns(&self) -> Vec<whimsi_msi::Column> {
-        vec![
-            whimsi_msi::Column::build("Signature_")
-                .primary_key()
-                .id_string(DEFAULT_IDENTIFIER_MAX_LEN),
-            whimsi_msi::Column::build("Root").int16(),
-            whimsi_msi::Column::build("Key")
-                .category(whimsi_msi::Category::RegPath)
-                .string(REGPATH_MAX_LEN),
-            whimsi_msi::Column::build("Name").nullable().formatted_string(255),
-            whimsi_msi::Column::build("Type").nullable().int16(),
-        ]
+        RegLocatorDao::columns()
     }
 }
@@ -1,39 +1,57 @@
+use getset::Getters;
+
+use crate::constants::*;
 use crate::define_identifier_generator;
 use crate::define_specific_identifier;
 use crate::define_specific_identifier_parsing;
 use crate::tables::builder_list_entry::MsiBuilderListEntry;
 use crate::tables::component::table::ComponentIdentifier;
-use crate::tables::dao::IsDao;
+use crate::tables::registry::registry_root::RegistryRoot;
+use crate::tables::registry::registry_value::RegistryValue;
 use crate::types::column::formatted::Formatted;
+use crate::types::column::formatted::FormattedColumns;
 use crate::types::column::identifier::Identifier;
 use crate::types::column::reg_path::RegPath;
-use crate::types::helpers::to_msi_value::ToMsiOptionalValue;
 use crate::types::helpers::to_unique_msi_identifier::ToUniqueMsiIdentifier;
 
 define_specific_identifier!(registry);
 define_specific_identifier_parsing!(registry);
 define_identifier_generator!(registry);
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Getters, whimsi_macros::MsiRow)]
+#[getset(get = "pub")]
 pub struct RegistryDao {
+    #[msi(primary_key, category = "Identifier", max_len = DEFAULT_IDENTIFIER_MAX_LEN)]
     registry: RegistryIdentifier,
-    root: i16,
+    #[msi(int16)]
+    root: RegistryRoot,
+    #[msi(localizable, category = "RegPath", max_len = REGPATH_MAX_LEN)]
     key: RegPath,
+    #[msi(localizable, category = "Formatted", max_len = REGISTRY_NAME_MAX_LEN)]
     name: Option<Formatted>,
+    #[msi(localizable, category = "Formatted", max_len = REGISTRY_VALUE_MAX_LEN)]
     value: Option<Formatted>,
+    #[msi(column = "Component_", category = "Identifier", max_len = DEFAULT_IDENTIFIER_MAX_LEN)]
     component: ComponentIdentifier,
 }
 
-impl IsDao for RegistryDao {
-    fn to_row(&self) -> Vec<whimsi_msi::Value> {
-        vec![
-            self.registry.clone().into(),
-            self.root.into(),
-            self.key.clone().into(),
-            self.name.to_optional_value(),
-            self.value.to_optional_value(),
-            self.component.clone().into(),
-        ]
+impl RegistryDao {
+    pub fn new(
+        registry: RegistryIdentifier,
+        root: RegistryRoot,
+        key: RegPath,
+        name: Option<Formatted>,
+        value: Option<RegistryValue>,
+        component: ComponentIdentifier,
+    ) -> RegistryDao {
+        RegistryDao {
+            registry,
+            root,
+            key,
+            name,
+            value: value.map(Into::into),
+            component,
+        }
     }
 }
 
@@ -48,3 +66,37 @@ impl MsiBuilderListEntry for RegistryDao {
         self.registry == other.registry
     }
 }
+
+impl FormattedColumns for RegistryDao {
+    fn formatted_columns(&self) -> Vec<&Formatted> {
+        let mut columns = vec![self.key.formatted()];
+        columns.extend(self.name.iter());
+        columns.extend(self.value.iter());
+        columns
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::str::FromStr;
+
+    use super::RegistryDao;
+    use super::RegistryIdentifier;
+    use super::RegistryRoot;
+    use crate::tables::component::table::ComponentIdentifier;
+    use crate::tables::msi_row::assert_round_trip;
+    use crate::types::column::reg_path::RegPath;
+
+    #[test]
+    fn round_trip() {
+        let dao = RegistryDao {
+            registry: RegistryIdentifier::from_str("reg_test").unwrap(),
+            root: RegistryRoot::LocalMachine,
+            key: RegPath::from_str("Software\\Whimsi").unwrap(),
+            name: Some("Version".to_string().into()),
+            value: None,
+            component: ComponentIdentifier::from_str("comp_test").unwrap(),
+        };
+        assert_round_trip(dao);
+    }
+}
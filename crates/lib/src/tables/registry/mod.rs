@@ -1,2 +1,6 @@
 pub mod dao;
+pub mod reg_file;
+pub mod registry_key_action;
+pub mod registry_root;
+pub mod registry_value;
 pub mod table;
@@ -0,0 +1,373 @@
+use std::path::Path;
+use std::str::FromStr;
+
+use anyhow::Context;
+use anyhow::bail;
+use anyhow::ensure;
+use getset::Getters;
+use itertools::Itertools;
+
+use crate::tables::registry::registry_root::RegistryRoot;
+use crate::tables::registry::registry_value::RegistryValue;
+use crate::types::column::reg_path::RegPath;
+
+/// First line of every `.reg` file exported by the Windows Registry Editor.
+const REG_FILE_HEADER: &str = "Windows Registry Editor Version 5.00";
+
+/// The contents of a `.reg` file exported by the Windows Registry Editor, in
+/// the order they appear in the file.
+///
+/// [*Reference*](https://support.microsoft.com/en-us/topic/how-to-add-modify-or-delete-registry-subkeys-and-values-by-using-a-reg-file-9c7f37cf-a5e9-e1cd-c4fa-2a26218a1a23)
+#[derive(Debug, Clone, PartialEq, Getters)]
+#[getset(get = "pub")]
+pub struct RegFile {
+    entries: Vec<RegFileEntry>,
+}
+
+/// A single change described by a `.reg` file.
+#[derive(Debug, Clone, PartialEq)]
+pub enum RegFileEntry {
+    /// `[HKEY_...\Key]`: the key is created.
+    Key { root: RegistryRoot, key: RegPath },
+    /// `"Name"=...` or `@=...`: the value is written. A `name` of `None` is
+    /// the key's default value.
+    Value {
+        root: RegistryRoot,
+        key: RegPath,
+        name: Option<String>,
+        value: RegistryValue,
+    },
+    /// `[-HKEY_...\Key]`: the key and all of its subkeys are deleted.
+    DeleteKey { root: RegistryRoot, key: RegPath },
+    /// `"Name"=-` or `@=-`: the value is deleted.
+    DeleteValue { root: RegistryRoot, key: RegPath, name: Option<String> },
+}
+
+impl RegFileEntry {
+    /// The predefined registry key the entry is under.
+    pub fn root(&self) -> RegistryRoot {
+        match self {
+            RegFileEntry::Key { root, .. }
+            | RegFileEntry::Value { root, .. }
+            | RegFileEntry::DeleteKey { root, .. }
+            | RegFileEntry::DeleteValue { root, .. } => *root,
+        }
+    }
+}
+
+impl RegFile {
+    /// Reads and parses the `.reg` file at `path`. Files exported by the
+    /// Registry Editor are UTF-16 encoded, but UTF-8 files are accepted too.
+    pub fn from_path(path: impl AsRef<Path>) -> anyhow::Result<RegFile> {
+        let path = path.as_ref();
+        let bytes = std::fs::read(path)
+            .with_context(|| format!("Failed to read .reg file {path:?}"))?;
+        decode(&bytes)?
+            .parse()
+            .with_context(|| format!("Failed to parse .reg file {path:?}"))
+    }
+}
+
+impl FromStr for RegFile {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> anyhow::Result<Self> {
+        let mut lines =
+            logical_lines(s).skip_while(|(_, line)| line.is_empty());
+        match lines.next() {
+            Some((_, header)) if header == REG_FILE_HEADER => (),
+            _ => bail!(".reg file must start with `{REG_FILE_HEADER}`"),
+        }
+
+        let mut entries = Vec::new();
+        // The key that values are currently being added to.
+        let mut current_key = None;
+        for (line_number, line) in lines {
+            if line.is_empty() || line.starts_with(';') {
+                continue;
+            }
+            let entry = parse_line(&line, &current_key)
+                .with_context(|| format!("Invalid line {line_number}"))?;
+            current_key = match &entry {
+                RegFileEntry::Key { root, key } => Some((*root, key.clone())),
+                RegFileEntry::DeleteKey { .. } => None,
+                _ => current_key,
+            };
+            entries.push(entry);
+        }
+        Ok(RegFile { entries })
+    }
+}
+
+/// Decodes the raw bytes of a `.reg` file, handling the UTF-16 byte order mark
+/// written by the Registry Editor.
+fn decode(bytes: &[u8]) -> anyhow::Result<String> {
+    if let Some(utf16) = bytes.strip_prefix(&[0xFF, 0xFE]) {
+        return utf16_to_string(utf16);
+    }
+    let utf8 = bytes.strip_prefix(&[0xEF, 0xBB, 0xBF]).unwrap_or(bytes);
+    Ok(String::from_utf8(utf8.to_vec())?)
+}
+
+/// Splits the file into lines, joining hex values that are continued onto the
+/// next line with a trailing `\`. Each line is paired with its 1-based line
+/// number in the file.
+fn logical_lines(s: &str) -> impl Iterator<Item = (usize, String)> {
+    let mut lines = s.lines().enumerate();
+    std::iter::from_fn(move || {
+        let (index, line) = lines.next()?;
+        let mut logical = line.trim().to_string();
+        // String values always end in a quote, so only hex values can end in
+        // a continuation.
+        while logical.ends_with('\\')
+            && let Some((_, next)) = lines.next()
+        {
+            logical.pop();
+            logical.push_str(next.trim());
+        }
+        Some((index + 1, logical))
+    })
+}
+
+fn parse_line(
+    line: &str,
+    current_key: &Option<(RegistryRoot, RegPath)>,
+) -> anyhow::Result<RegFileEntry> {
+    if let Some(key) = line.strip_prefix('[') {
+        let Some(key) = key.strip_suffix(']') else {
+            bail!("Key [{line}] is missing a closing `]`");
+        };
+        return Ok(match key.strip_prefix('-') {
+            Some(key) => {
+                let (root, key) = parse_key(key)?;
+                RegFileEntry::DeleteKey { root, key }
+            }
+            None => {
+                let (root, key) = parse_key(key)?;
+                RegFileEntry::Key { root, key }
+            }
+        });
+    }
+
+    let Some((root, key)) = current_key.clone() else {
+        bail!("Value [{line}] does not belong to a key");
+    };
+    let (name, value) = parse_name(line)?;
+    let Some(value) = value.strip_prefix('=') else {
+        bail!("Value [{line}] is missing `=`");
+    };
+    let value = value.trim_start();
+    if value == "-" {
+        return Ok(RegFileEntry::DeleteValue { root, key, name });
+    }
+    Ok(RegFileEntry::Value { root, key, name, value: parse_value(value)? })
+}
+
+/// Splits `HKEY_...\Path\To\Key` into its root and path.
+fn parse_key(key: &str) -> anyhow::Result<(RegistryRoot, RegPath)> {
+    let Some((root, path)) = key.split_once('\\') else {
+        bail!("Key [{key}] must be a subkey of a registry root");
+    };
+    let root = RegistryRoot::from_str(root)?;
+    ensure!(!path.is_empty(), "Key [{key}] must name a subkey");
+    Ok((root, RegPath::from_str(path)?))
+}
+
+/// Parses the value name at the start of `line`, returning it along with the
+/// rest of the line. `@` is the default value and is returned as `None`.
+fn parse_name(line: &str) -> anyhow::Result<(Option<String>, &str)> {
+    if let Some(rest) = line.strip_prefix('@') {
+        return Ok((None, rest.trim_start()));
+    }
+    let (name, rest) = parse_quoted(line)?;
+    Ok((Some(name), rest.trim_start()))
+}
+
+/// Parses a quoted string with `\\` and `\"` escapes from the start of `s`,
+/// returning it along with the rest of `s`.
+fn parse_quoted(s: &str) -> anyhow::Result<(String, &str)> {
+    let Some(quoted) = s.strip_prefix('"') else {
+        bail!("Expected a quoted string at [{s}]");
+    };
+    let mut string = String::new();
+    let mut chars = quoted.char_indices();
+    while let Some((index, c)) = chars.next() {
+        match c {
+            '"' => return Ok((string, &quoted[index + 1..])),
+            '\\' => match chars.next() {
+                Some((_, escaped @ ('\\' | '"'))) => string.push(escaped),
+                _ => bail!("Invalid escape sequence in [{s}]"),
+            },
+            c => string.push(c),
+        }
+    }
+    bail!("Unterminated string [{s}]")
+}
+
+fn parse_value(value: &str) -> anyhow::Result<RegistryValue> {
+    if value.starts_with('"') {
+        let (string, rest) = parse_quoted(value)?;
+        ensure!(rest.trim().is_empty(), "Unexpected text after [{value}]");
+        return Ok(RegistryValue::String(string));
+    }
+    if let Some(dword) = value.strip_prefix("dword:") {
+        let dword = u32::from_str_radix(dword, 16)
+            .with_context(|| format!("Invalid DWORD [{dword}]"))?;
+        // DWORDs are unsigned but the Registry table stores them signed.
+        return Ok(RegistryValue::Dword(dword as i32));
+    }
+    let Some((kind, bytes)) = value.split_once(':') else {
+        bail!("Unrecognized value [{value}]");
+    };
+    let bytes = parse_hex(bytes)?;
+    Ok(match kind {
+        "hex" => RegistryValue::Binary(bytes),
+        "hex(1)" => RegistryValue::String(utf16_to_string(&bytes)?),
+        "hex(2)" => RegistryValue::ExpandableString(utf16_to_string(&bytes)?),
+        "hex(4)" => {
+            let Ok(bytes) = <[u8; 4]>::try_from(bytes.as_slice()) else {
+                bail!("DWORD [{value}] must be exactly 4 bytes");
+            };
+            RegistryValue::Dword(i32::from_le_bytes(bytes))
+        }
+        "hex(7)" => RegistryValue::MultiString(
+            utf16_to_string(&bytes)?
+                .split('\0')
+                .filter(|string| !string.is_empty())
+                .map(ToString::to_string)
+                .collect(),
+        ),
+        _ => bail!("Unsupported registry value type [{kind}]"),
+    })
+}
+
+/// Parses comma separated hex bytes such as `01,ab,ff`.
+fn parse_hex(bytes: &str) -> anyhow::Result<Vec<u8>> {
+    bytes
+        .split(',')
+        .map(str::trim)
+        .filter(|byte| !byte.is_empty())
+        .map(|byte| {
+            u8::from_str_radix(byte, 16)
+                .with_context(|| format!("Invalid hex byte [{byte}]"))
+        })
+        .collect()
+}
+
+/// Decodes little-endian UTF-16, dropping the trailing null terminator that
+/// registry strings are stored with.
+fn utf16_to_string(bytes: &[u8]) -> anyhow::Result<String> {
+    ensure!(bytes.len() % 2 == 0, "UTF-16 data has an odd number of bytes");
+    let units = bytes
+        .chunks_exact(2)
+        .map(|pair| u16::from_le_bytes([pair[0], pair[1]]))
+        .collect_vec();
+    let string = String::from_utf16(&units)?;
+    Ok(string.strip_suffix('\0').unwrap_or(&string).to_string())
+}
+
+#[cfg(test)]
+mod test {
+    use std::str::FromStr;
+
+    use test_case::test_case;
+
+    use super::RegFile;
+    use super::RegFileEntry;
+    use super::decode;
+    use super::parse_value;
+    use crate::tables::registry::registry_root::RegistryRoot;
+    use crate::tables::registry::registry_value::RegistryValue;
+    use crate::types::column::reg_path::RegPath;
+
+    const EXPORTED: &str = r#"Windows Registry Editor Version 5.00
+
+; Settings for Whimsi
+[HKEY_LOCAL_MACHINE\SOFTWARE\Whimsi]
+@="Default"
+"Path"="C:\\Program Files\\Whimsi"
+"Count"=dword:0000000a
+"Expanded"=hex(2):25,00,50,00,41,00,54,00,48,00,25,00,00,00
+"List"=hex(7):61,00,00,00,62,00,00,00,00,00
+"Data"=hex:01,02,\
+  03
+"Old"=-
+
+[-HKEY_CURRENT_USER\Software\Legacy]
+"#;
+
+    fn key(path: &str) -> RegPath {
+        RegPath::from_str(path).unwrap()
+    }
+
+    #[test]
+    fn parses_exported_file() {
+        let reg_file = RegFile::from_str(EXPORTED).unwrap();
+        let root = RegistryRoot::LocalMachine;
+        let value = |name: Option<&str>, value| RegFileEntry::Value {
+            root,
+            key: key(r"SOFTWARE\Whimsi"),
+            name: name.map(ToString::to_string),
+            value,
+        };
+        assert_eq!(
+            reg_file.entries(),
+            &vec![
+                RegFileEntry::Key { root, key: key(r"SOFTWARE\Whimsi") },
+                value(None, RegistryValue::String("Default".to_string())),
+                value(
+                    Some("Path"),
+                    RegistryValue::String(
+                        r"C:\Program Files\Whimsi".to_string()
+                    )
+                ),
+                value(Some("Count"), RegistryValue::Dword(10)),
+                value(
+                    Some("Expanded"),
+                    RegistryValue::ExpandableString("%PATH%".to_string())
+                ),
+                value(
+                    Some("List"),
+                    RegistryValue::MultiString(vec![
+                        "a".to_string(),
+                        "b".to_string()
+                    ])
+                ),
+                value(Some("Data"), RegistryValue::Binary(vec![1, 2, 3])),
+                RegFileEntry::DeleteValue {
+                    root,
+                    key: key(r"SOFTWARE\Whimsi"),
+                    name: Some("Old".to_string()),
+                },
+                RegFileEntry::DeleteKey {
+                    root: RegistryRoot::CurrentUser,
+                    key: key(r"Software\Legacy"),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn decodes_utf16() {
+        let bytes = [0xFF, 0xFE]
+            .into_iter()
+            .chain("Windows".encode_utf16().flat_map(u16::to_le_bytes))
+            .collect::<Vec<u8>>();
+        assert_eq!(decode(&bytes).unwrap(), "Windows");
+    }
+
+    #[test_case("dword:ffffffff", RegistryValue::Dword(-1); "negative dword")]
+    #[test_case("hex(4):0a,00,00,00", RegistryValue::Dword(10); "hex dword")]
+    #[test_case(r#""say \"hi\"""#, RegistryValue::String(r#"say "hi""#.to_string()); "escaped quotes")]
+    fn parses_value(value: &str, expected: RegistryValue) {
+        assert_eq!(parse_value(value).unwrap(), expected);
+    }
+
+    #[test_case("REGEDIT4\n"; "missing header")]
+    #[test_case("Windows Registry Editor Version 5.00\n\"x\"=\"y\"\n"; "value without key")]
+    #[test_case("Windows Registry Editor Version 5.00\n[HKEY_CURRENT_CONFIG\\x]\n"; "unsupported root")]
+    #[test_case("Windows Registry Editor Version 5.00\n[HKLM\\x]\n\"x\"=hex(b):00\n"; "unsupported type")]
+    fn rejects_invalid(contents: &str) {
+        assert!(RegFile::from_str(contents).is_err());
+    }
+}
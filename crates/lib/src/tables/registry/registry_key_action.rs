@@ -0,0 +1,47 @@
+use crate::types::column::formatted::Formatted;
+
+/// What happens to a registry key that is listed in the `Registry` table
+/// without a value.
+///
+/// These are written to the `Name` column, which has a special meaning when
+/// the `Value` column is null.
+///
+/// [*Reference*](https://learn.microsoft.com/en-us/windows/win32/msi/registry-table)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Deserialize)]
+pub enum RegistryKeyAction {
+    /// `+`: the key is created, if absent, when the component is installed.
+    Create,
+    /// `-`: the key is deleted, along with all of its values and subkeys,
+    /// when the component is uninstalled.
+    DeleteOnUninstall,
+    /// `*`: the key is created when the component is installed and deleted
+    /// when the component is uninstalled.
+    CreateAndDeleteOnUninstall,
+}
+
+impl From<RegistryKeyAction> for Formatted {
+    fn from(value: RegistryKeyAction) -> Formatted {
+        match value {
+            RegistryKeyAction::Create => "+",
+            RegistryKeyAction::DeleteOnUninstall => "-",
+            RegistryKeyAction::CreateAndDeleteOnUninstall => "*",
+        }
+        .to_string()
+        .into()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use test_case::test_case;
+
+    use super::RegistryKeyAction;
+    use crate::types::column::formatted::Formatted;
+
+    #[test_case(RegistryKeyAction::Create, "+"; "create")]
+    #[test_case(RegistryKeyAction::DeleteOnUninstall, "-"; "delete")]
+    #[test_case(RegistryKeyAction::CreateAndDeleteOnUninstall, "*"; "both")]
+    fn to_formatted(action: RegistryKeyAction, expected: &str) {
+        assert_eq!(Formatted::from(action).to_string(), expected);
+    }
+}
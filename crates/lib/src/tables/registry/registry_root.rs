@@ -0,0 +1,86 @@
+use std::str::FromStr;
+
+use anyhow::bail;
+
+/// The predefined registry key that a `Registry` table entry is written under.
+///
+/// [*Reference*](https://learn.microsoft.com/en-us/windows/win32/msi/registry-table)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Deserialize)]
+pub enum RegistryRoot {
+    /// `HKEY_CURRENT_USER` for a per-user installation, or
+    /// `HKEY_LOCAL_MACHINE` for a per-machine installation.
+    PerUserOrMachine = -1,
+    /// `HKEY_CLASSES_ROOT`
+    ClassesRoot = 0,
+    /// `HKEY_CURRENT_USER`
+    CurrentUser = 1,
+    /// `HKEY_LOCAL_MACHINE`
+    LocalMachine = 2,
+    /// `HKEY_USERS`
+    Users = 3,
+}
+
+impl From<RegistryRoot> for whimsi_msi::Value {
+    fn from(value: RegistryRoot) -> whimsi_msi::Value {
+        whimsi_msi::Value::Int(value as i32)
+    }
+}
+
+impl TryFrom<i32> for RegistryRoot {
+    type Error = anyhow::Error;
+
+    fn try_from(value: i32) -> anyhow::Result<Self> {
+        Ok(match value {
+            -1 => RegistryRoot::PerUserOrMachine,
+            0 => RegistryRoot::ClassesRoot,
+            1 => RegistryRoot::CurrentUser,
+            2 => RegistryRoot::LocalMachine,
+            3 => RegistryRoot::Users,
+            _ => bail!("[{value}] is not a valid registry root"),
+        })
+    }
+}
+
+/// Parses either the numeric value stored in the `Root` column or one of the
+/// usual abbreviations (`HKCR`, `HKCU`, `HKLM`, `HKU`, `HKMU`) or full names
+/// (`HKEY_LOCAL_MACHINE`, ...).
+impl FromStr for RegistryRoot {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> anyhow::Result<Self> {
+        if let Ok(number) = s.parse::<i32>() {
+            return RegistryRoot::try_from(number);
+        }
+        Ok(match s.to_uppercase().as_str() {
+            "HKMU" => RegistryRoot::PerUserOrMachine,
+            "HKCR" | "HKEY_CLASSES_ROOT" => RegistryRoot::ClassesRoot,
+            "HKCU" | "HKEY_CURRENT_USER" => RegistryRoot::CurrentUser,
+            "HKLM" | "HKEY_LOCAL_MACHINE" => RegistryRoot::LocalMachine,
+            "HKU" | "HKEY_USERS" => RegistryRoot::Users,
+            _ => bail!("[{s}] is not a valid registry root"),
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::str::FromStr;
+
+    use test_case::test_case;
+
+    use super::RegistryRoot;
+
+    #[test_case("-1", RegistryRoot::PerUserOrMachine; "numeric")]
+    #[test_case("HKLM", RegistryRoot::LocalMachine; "abbreviation")]
+    #[test_case("hkcu", RegistryRoot::CurrentUser; "lowercase")]
+    #[test_case("HKEY_CLASSES_ROOT", RegistryRoot::ClassesRoot; "full name")]
+    fn parse(input: &str, expected: RegistryRoot) {
+        assert_eq!(RegistryRoot::from_str(input).unwrap(), expected);
+    }
+
+    #[test]
+    fn parse_invalid() {
+        assert!(RegistryRoot::from_str("4").is_err());
+        assert!(RegistryRoot::from_str("HKEY_CURRENT_CONFIG").is_err());
+    }
+}
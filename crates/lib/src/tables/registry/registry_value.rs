@@ -0,0 +1,110 @@
+use itertools::Itertools;
+
+use crate::types::column::formatted::Formatted;
+
+/// A typed value for the `Value` column of the `Registry` table.
+///
+/// Windows Installer encodes the registry value type in a prefix of the
+/// value's text. This type writes that prefix so callers don't have to.
+///
+/// [*Reference*](https://learn.microsoft.com/en-us/windows/win32/msi/registry-table)
+#[derive(Debug, Clone, PartialEq)]
+pub enum RegistryValue {
+    /// A `REG_SZ` value.
+    String(String),
+    /// A `REG_EXPAND_SZ` value, written with a `#%` prefix.
+    ExpandableString(String),
+    /// A `REG_DWORD` value, written with a `#` prefix.
+    Dword(i32),
+    /// A `REG_BINARY` value, written as hexadecimal with a `#x` prefix.
+    Binary(Vec<u8>),
+    /// A `REG_MULTI_SZ` value, written as a `[~]` delimited list that
+    /// replaces any existing value.
+    MultiString(Vec<String>),
+}
+
+impl RegistryValue {
+    /// Escapes the text of the value so that it is written exactly as given
+    /// instead of being resolved by the installer. Used for data that was
+    /// never meant to be [`Formatted`], such as values imported from a
+    /// `.reg` file.
+    pub fn escaped(self) -> RegistryValue {
+        let escape = |string: String| Formatted::escaped(&string).to_string();
+        match self {
+            RegistryValue::String(string) => {
+                RegistryValue::String(escape(string))
+            }
+            RegistryValue::ExpandableString(string) => {
+                RegistryValue::ExpandableString(escape(string))
+            }
+            RegistryValue::MultiString(strings) => RegistryValue::MultiString(
+                strings.into_iter().map(escape).collect(),
+            ),
+            value @ (RegistryValue::Dword(_) | RegistryValue::Binary(_)) => {
+                value
+            }
+        }
+    }
+}
+
+impl From<RegistryValue> for Formatted {
+    fn from(value: RegistryValue) -> Formatted {
+        let text = match value {
+            // A leading `#` would be read as a type prefix, so it is escaped
+            // by doubling it.
+            RegistryValue::String(string) if string.starts_with('#') => {
+                format!("#{string}")
+            }
+            RegistryValue::String(string) => string,
+            RegistryValue::ExpandableString(string) => format!("#%{string}"),
+            RegistryValue::Dword(number) => format!("#{number}"),
+            RegistryValue::Binary(bytes) => {
+                format!(
+                    "#x{}",
+                    bytes.iter().map(|b| format!("{b:02X}")).join("")
+                )
+            }
+            RegistryValue::MultiString(strings) => {
+                format!("[~]{}[~]", strings.join("[~]"))
+            }
+        };
+        text.into()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use test_case::test_case;
+
+    use super::RegistryValue;
+    use crate::types::column::formatted::Formatted;
+
+    #[test_case(RegistryValue::String("Whimsi".to_string()), "Whimsi"; "string")]
+    #[test_case(RegistryValue::String("#1".to_string()), "##1"; "string with hash")]
+    #[test_case(RegistryValue::ExpandableString("%PATH%".to_string()), "#%%PATH%"; "expandable")]
+    #[test_case(RegistryValue::Dword(-1), "#-1"; "dword")]
+    #[test_case(RegistryValue::Binary(vec![0x01, 0xAB]), "#x01AB"; "binary")]
+    #[test_case(
+        RegistryValue::MultiString(vec!["a".to_string(), "b".to_string()]),
+        "[~]a[~]b[~]";
+        "multi string"
+    )]
+    fn to_formatted(value: RegistryValue, expected: &str) {
+        assert_eq!(Formatted::from(value).to_string(), expected);
+    }
+
+    #[test_case(RegistryValue::String(r"C:\[old]".to_string()), r"C:\[\[]old[\]]"; "brackets")]
+    #[test_case(RegistryValue::String("a[b".to_string()), r"a[\[]b"; "unclosed bracket")]
+    #[test_case(RegistryValue::ExpandableString("{%PATH%}".to_string()), r"#%[\{]%PATH%[\}]"; "braces")]
+    #[test_case(
+        RegistryValue::MultiString(vec!["[a]".to_string(), "b".to_string()]),
+        r"[~][\[]a[\]][~]b[~]";
+        "multi string"
+    )]
+    #[test_case(RegistryValue::Dword(1), "#1"; "dword")]
+    fn escaped_to_formatted(value: RegistryValue, expected: &str) {
+        let formatted = Formatted::from(value.escaped());
+        assert_eq!(formatted.to_string(), expected);
+        assert!(formatted.segments().is_ok());
+    }
+}
@@ -1,4 +1,3 @@
-use crate::constants::*;
 use crate::define_generator_table;
 use crate::define_identifier_generator;
 use crate::define_specific_identifier;
@@ -13,29 +12,7 @@ use crate::tables::registry::dao::RegistryDao;
 use crate::tables::registry::dao::RegistryIdentifier;
 
 define_identifier_generator!(Registry);
-define_generator_table!(
-    Registry,
-    vec![
-        whimsi_msi::Column::build("Registry")
-            .primary_key()
-            .id_string(DEFAULT_IDENTIFIER_MAX_LEN),
-        whimsi_msi::Column::build("Root").int16(),
-        whimsi_msi::Column::build("Key")
-            .localizable()
-            .category(whimsi_msi::Category::RegPath)
-            .string(REGPATH_MAX_LEN),
-        whimsi_msi::Column::build("Name")
-            .localizable()
-            .nullable()
-            .formatted_string(REGISTRY_NAME_MAX_LEN),
-        whimsi_msi::Column::build("Value")
-            .localizable()
-            .nullable()
-            .formatted_string(REGISTRY_VALUE_MAX_LEN),
-        whimsi_msi::Column::build("Component_")
-            .id_string(DEFAULT_IDENTIFIER_MAX_LEN),
-    ]
-);
+define_generator_table!(Registry);
 
 msi_list_boilerplate!(RegistryTable, RegistryDao);
 implement_id_generator_for_table!(RegistryTable, RegistryIdGenerator);
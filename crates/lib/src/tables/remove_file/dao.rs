@@ -0,0 +1,79 @@
+use getset::Getters;
+
+use crate::constants::*;
+use crate::define_identifier_generator;
+use crate::define_specific_identifier;
+use crate::define_specific_identifier_parsing;
+use crate::tables::builder_list_entry::MsiBuilderListEntry;
+use crate::tables::component::table::ComponentIdentifier;
+use crate::tables::remove_file::install_mode::RemoveFileInstallMode;
+use crate::types::column::identifier::Identifier;
+use crate::types::helpers::to_unique_msi_identifier::ToUniqueMsiIdentifier;
+
+define_specific_identifier!(RemoveFile);
+define_specific_identifier_parsing!(RemoveFile);
+define_identifier_generator!(RemoveFile);
+
+/// Files matching `filename` in the directory that `dir_property` resolves
+/// to, which are removed when the owning component is installed or
+/// uninstalled. When `filename` is `None` the directory itself is removed,
+/// as long as it is empty.
+///
+/// [*Reference*](https://learn.microsoft.com/en-us/windows/win32/msi/removefile-table)
+#[derive(
+    Debug,
+    Clone,
+    PartialEq,
+    Getters,
+    derive_more::Constructor,
+    whimsi_macros::MsiRow,
+)]
+#[getset(get = "pub")]
+pub struct RemoveFileDao {
+    #[msi(column = "FileKey", primary_key, category = "Identifier", max_len = DEFAULT_IDENTIFIER_MAX_LEN)]
+    remove_file: RemoveFileIdentifier,
+    #[msi(column = "Component_", category = "Identifier", max_len = DEFAULT_IDENTIFIER_MAX_LEN)]
+    component: ComponentIdentifier,
+    #[msi(column = "FileName", localizable, category = "WildCardFilename", max_len = FILENAME_MAX_LEN)]
+    filename: Option<String>,
+    #[msi(category = "Identifier", max_len = DEFAULT_IDENTIFIER_MAX_LEN)]
+    dir_property: Identifier,
+    #[msi(int16)]
+    install_mode: RemoveFileInstallMode,
+}
+
+impl ToUniqueMsiIdentifier for RemoveFileDao {
+    fn to_unique_msi_identifier(&self) -> Option<Identifier> {
+        self.remove_file.to_unique_msi_identifier()
+    }
+}
+
+impl MsiBuilderListEntry for RemoveFileDao {
+    fn conflicts(&self, other: &Self) -> bool {
+        self.remove_file == other.remove_file
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::str::FromStr;
+
+    use super::RemoveFileDao;
+    use super::RemoveFileIdentifier;
+    use crate::tables::component::table::ComponentIdentifier;
+    use crate::tables::msi_row::assert_round_trip;
+    use crate::tables::remove_file::install_mode::RemoveFileInstallMode;
+    use crate::types::column::identifier::Identifier;
+
+    #[test]
+    fn round_trip() {
+        let dao = RemoveFileDao::new(
+            RemoveFileIdentifier::from_str("remove_logs").unwrap(),
+            ComponentIdentifier::from_str("comp_test").unwrap(),
+            Some("*.log".to_string()),
+            Identifier::from_str("LOGDIR").unwrap(),
+            RemoveFileInstallMode::OnUninstall,
+        );
+        assert_round_trip(dao);
+    }
+}
@@ -0,0 +1,35 @@
+use std::str::FromStr;
+
+use anyhow::bail;
+
+/// When the files of a `RemoveFile` entry are removed.
+///
+/// [*Reference*](https://learn.microsoft.com/en-us/windows/win32/msi/removefile-table)
+#[derive(Clone, Copy, Debug, PartialEq, Eq, serde::Deserialize)]
+pub enum RemoveFileInstallMode {
+    /// When the owning component is installed.
+    OnInstall = 1,
+    /// When the owning component is uninstalled.
+    OnUninstall = 2,
+    /// Both when the owning component is installed and uninstalled.
+    OnBoth = 3,
+}
+
+impl From<RemoveFileInstallMode> for whimsi_msi::Value {
+    fn from(value: RemoveFileInstallMode) -> whimsi_msi::Value {
+        whimsi_msi::Value::Int(value as i32)
+    }
+}
+
+impl FromStr for RemoveFileInstallMode {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> anyhow::Result<Self> {
+        Ok(match s.parse::<i32>() {
+            Ok(1) => RemoveFileInstallMode::OnInstall,
+            Ok(2) => RemoveFileInstallMode::OnUninstall,
+            Ok(3) => RemoveFileInstallMode::OnBoth,
+            _ => bail!("[{s}] is not a valid RemoveFile install mode"),
+        })
+    }
+}
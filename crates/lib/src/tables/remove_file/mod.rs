@@ -0,0 +1,3 @@
+pub mod dao;
+pub mod install_mode;
+pub mod table;
@@ -0,0 +1,15 @@
+use crate::define_generator_table;
+use crate::implement_id_generator_for_table;
+use crate::implement_new_for_id_generator_table;
+use crate::msi_list_boilerplate;
+use crate::msi_table_boilerplate;
+use crate::tables::builder_list::MsiBuilderList;
+use crate::tables::builder_table::MsiBuilderTable;
+use crate::tables::remove_file::dao::RemoveFileDao;
+use crate::tables::remove_file::dao::RemoveFileIdGenerator;
+
+define_generator_table!(RemoveFile);
+
+msi_list_boilerplate!(RemoveFileTable, RemoveFileDao);
+implement_id_generator_for_table!(RemoveFileTable, RemoveFileIdGenerator);
+implement_new_for_id_generator_table!(RemoveFileTable, RemoveFileIdGenerator);
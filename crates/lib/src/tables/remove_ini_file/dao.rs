@@ -0,0 +1,70 @@
+use getset::Getters;
+
+use crate::constants::*;
+use crate::define_identifier_generator;
+use crate::define_specific_identifier;
+use crate::define_specific_identifier_parsing;
+use crate::tables::builder_list_entry::MsiBuilderListEntry;
+use crate::tables::component::table::ComponentIdentifier;
+use crate::tables::ini_file::ini_file_action::RemoveIniFileAction;
+use crate::types::column::filename::Filename;
+use crate::types::column::formatted::Formatted;
+use crate::types::column::formatted::FormattedColumns;
+use crate::types::column::identifier::Identifier;
+use crate::types::helpers::to_unique_msi_identifier::ToUniqueMsiIdentifier;
+
+define_specific_identifier!(RemoveIniFile);
+define_specific_identifier_parsing!(RemoveIniFile);
+define_identifier_generator!(RemoveIniFile);
+
+/// An entry that is removed from an INI file when the owning component is
+/// installed. `value` is only needed to remove a single tag.
+///
+/// [*Reference*](https://learn.microsoft.com/en-us/windows/win32/msi/removeinifile-table)
+#[derive(
+    Debug,
+    Clone,
+    PartialEq,
+    Getters,
+    derive_more::Constructor,
+    whimsi_macros::MsiRow,
+)]
+#[getset(get = "pub")]
+pub struct RemoveIniFileDao {
+    #[msi(primary_key, category = "Identifier", max_len = DEFAULT_IDENTIFIER_MAX_LEN)]
+    remove_ini_file: RemoveIniFileIdentifier,
+    #[msi(column = "FileName", localizable, category = "Filename", max_len = FILENAME_MAX_LEN)]
+    filename: Filename,
+    #[msi(category = "Identifier", max_len = DEFAULT_IDENTIFIER_MAX_LEN)]
+    dir_property: Option<Identifier>,
+    #[msi(localizable, category = "Formatted", max_len = INI_SECTION_MAX_LEN)]
+    section: Formatted,
+    #[msi(localizable, category = "Formatted", max_len = INI_KEY_MAX_LEN)]
+    key: Formatted,
+    #[msi(localizable, category = "Formatted", max_len = INI_VALUE_MAX_LEN)]
+    value: Option<Formatted>,
+    #[msi(int16)]
+    action: RemoveIniFileAction,
+    #[msi(column = "Component_", category = "Identifier", max_len = DEFAULT_IDENTIFIER_MAX_LEN)]
+    component: ComponentIdentifier,
+}
+
+impl ToUniqueMsiIdentifier for RemoveIniFileDao {
+    fn to_unique_msi_identifier(&self) -> Option<Identifier> {
+        self.remove_ini_file.to_unique_msi_identifier()
+    }
+}
+
+impl MsiBuilderListEntry for RemoveIniFileDao {
+    fn conflicts(&self, other: &Self) -> bool {
+        self.remove_ini_file == other.remove_ini_file
+    }
+}
+
+impl FormattedColumns for RemoveIniFileDao {
+    fn formatted_columns(&self) -> Vec<&Formatted> {
+        let mut columns = vec![&self.section, &self.key];
+        columns.extend(self.value.iter());
+        columns
+    }
+}
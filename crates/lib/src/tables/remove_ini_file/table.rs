@@ -0,0 +1,18 @@
+use crate::define_generator_table;
+use crate::implement_id_generator_for_table;
+use crate::implement_new_for_id_generator_table;
+use crate::msi_list_boilerplate;
+use crate::msi_table_boilerplate;
+use crate::tables::builder_list::MsiBuilderList;
+use crate::tables::builder_table::MsiBuilderTable;
+use crate::tables::remove_ini_file::dao::RemoveIniFileDao;
+use crate::tables::remove_ini_file::dao::RemoveIniFileIdGenerator;
+
+define_generator_table!(RemoveIniFile);
+
+msi_list_boilerplate!(RemoveIniFileTable, RemoveIniFileDao);
+implement_id_generator_for_table!(RemoveIniFileTable, RemoveIniFileIdGenerator);
+implement_new_for_id_generator_table!(
+    RemoveIniFileTable,
+    RemoveIniFileIdGenerator
+);
@@ -0,0 +1,95 @@
+use getset::Getters;
+
+use crate::constants::*;
+use crate::define_identifier_generator;
+use crate::define_specific_identifier;
+use crate::define_specific_identifier_parsing;
+use crate::tables::builder_list_entry::MsiBuilderListEntry;
+use crate::tables::component::table::ComponentIdentifier;
+use crate::tables::registry::registry_root::RegistryRoot;
+use crate::types::column::formatted::Formatted;
+use crate::types::column::formatted::FormattedColumns;
+use crate::types::column::identifier::Identifier;
+use crate::types::column::reg_path::RegPath;
+use crate::types::helpers::to_unique_msi_identifier::ToUniqueMsiIdentifier;
+
+define_specific_identifier!(RemoveRegistry);
+define_specific_identifier_parsing!(RemoveRegistry);
+define_identifier_generator!(RemoveRegistry);
+
+/// A registry value or key that is removed when the owning component is
+/// installed.
+///
+/// [*Reference*](https://learn.microsoft.com/en-us/windows/win32/msi/removeregistry-table)
+#[derive(Debug, Clone, PartialEq, Getters, whimsi_macros::MsiRow)]
+#[getset(get = "pub")]
+pub struct RemoveRegistryDao {
+    #[msi(primary_key, category = "Identifier", max_len = DEFAULT_IDENTIFIER_MAX_LEN)]
+    remove_registry: RemoveRegistryIdentifier,
+    #[msi(int16)]
+    root: RegistryRoot,
+    #[msi(localizable, category = "RegPath", max_len = REGPATH_MAX_LEN)]
+    key: RegPath,
+    /// Name of the value to remove. `None` removes the key's default value
+    /// and `-` removes the whole key along with its subkeys.
+    #[msi(localizable, category = "Formatted", max_len = REGISTRY_NAME_MAX_LEN)]
+    name: Option<Formatted>,
+    #[msi(column = "Component_", category = "Identifier", max_len = DEFAULT_IDENTIFIER_MAX_LEN)]
+    component: ComponentIdentifier,
+}
+
+impl RemoveRegistryDao {
+    pub fn new(
+        remove_registry: RemoveRegistryIdentifier,
+        root: RegistryRoot,
+        key: RegPath,
+        name: Option<Formatted>,
+        component: ComponentIdentifier,
+    ) -> RemoveRegistryDao {
+        RemoveRegistryDao { remove_registry, root, key, name, component }
+    }
+}
+
+impl ToUniqueMsiIdentifier for RemoveRegistryDao {
+    fn to_unique_msi_identifier(&self) -> Option<Identifier> {
+        self.remove_registry.to_unique_msi_identifier()
+    }
+}
+
+impl MsiBuilderListEntry for RemoveRegistryDao {
+    fn conflicts(&self, other: &Self) -> bool {
+        self.remove_registry == other.remove_registry
+    }
+}
+
+impl FormattedColumns for RemoveRegistryDao {
+    fn formatted_columns(&self) -> Vec<&Formatted> {
+        let mut columns = vec![self.key.formatted()];
+        columns.extend(self.name.iter());
+        columns
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::str::FromStr;
+
+    use super::RemoveRegistryDao;
+    use super::RemoveRegistryIdentifier;
+    use crate::tables::component::table::ComponentIdentifier;
+    use crate::tables::msi_row::assert_round_trip;
+    use crate::tables::registry::registry_root::RegistryRoot;
+    use crate::types::column::reg_path::RegPath;
+
+    #[test]
+    fn round_trip() {
+        let dao = RemoveRegistryDao::new(
+            RemoveRegistryIdentifier::from_str("remreg_test").unwrap(),
+            RegistryRoot::CurrentUser,
+            RegPath::from_str("Software\\Whimsi").unwrap(),
+            Some("-".to_string().into()),
+            ComponentIdentifier::from_str("comp_test").unwrap(),
+        );
+        assert_round_trip(dao);
+    }
+}
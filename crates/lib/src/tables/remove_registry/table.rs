@@ -0,0 +1,21 @@
+use crate::define_generator_table;
+use crate::implement_id_generator_for_table;
+use crate::implement_new_for_id_generator_table;
+use crate::msi_list_boilerplate;
+use crate::msi_table_boilerplate;
+use crate::tables::builder_list::MsiBuilderList;
+use crate::tables::builder_table::MsiBuilderTable;
+use crate::tables::remove_registry::dao::RemoveRegistryDao;
+use crate::tables::remove_registry::dao::RemoveRegistryIdGenerator;
+
+define_generator_table!(RemoveRegistry);
+
+msi_list_boilerplate!(RemoveRegistryTable, RemoveRegistryDao);
+implement_id_generator_for_table!(
+    RemoveRegistryTable,
+    RemoveRegistryIdGenerator
+);
+implement_new_for_id_generator_table!(
+    RemoveRegistryTable,
+    RemoveRegistryIdGenerator
+);
@@ -9,6 +9,7 @@ use crate::tables::dao::IsDao;
 use crate::tables::service_control::event::Event;
 use crate::tables::service_install::table::ServiceInstallIdentifier;
 use crate::types::column::formatted::Formatted;
+use crate::types::column::formatted::FormattedColumns;
 use crate::types::column::identifier::Identifier;
 use crate::types::column::identifier::ToIdentifier;
 use crate::types::helpers::to_msi_value::ToMsiOptionalValue;
@@ -70,3 +71,11 @@ impl ToUniqueMsiIdentifier for ServiceControlDao {
         None
     }
 }
+
+impl FormattedColumns for ServiceControlDao {
+    fn formatted_columns(&self) -> Vec<&Formatted> {
+        let mut columns = vec![&self.name];
+        columns.extend(self.arguments.iter());
+        columns
+    }
+}
@@ -10,6 +10,7 @@ use crate::tables::service_install::start_type::StartType;
 use crate::tables::service_install::table::ServiceInstallIdentifier;
 use crate::types::column::condition::Condition;
 use crate::types::column::formatted::Formatted;
+use crate::types::column::formatted::FormattedColumns;
 use crate::types::column::guid::Guid;
 use crate::types::column::identifier::Identifier;
 use crate::types::column::identifier::ToIdentifier;
@@ -91,3 +92,21 @@ impl ToUniqueMsiIdentifier for ServiceInstallDao {
         self.identifier.to_unique_msi_identifier()
     }
 }
+
+impl FormattedColumns for ServiceInstallDao {
+    fn formatted_columns(&self) -> Vec<&Formatted> {
+        [
+            &self.display_name,
+            &self.load_order_group,
+            &self.dependencies,
+            &self.start_name,
+            &self.password,
+            &self.arguments,
+            &self.description,
+        ]
+        .into_iter()
+        .flatten()
+        .chain([&self.name])
+        .collect()
+    }
+}
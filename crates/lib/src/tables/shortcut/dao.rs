@@ -9,6 +9,7 @@ use crate::tables::shortcut::table::ShortcutIdentifier;
 use crate::types::column::condition::Condition;
 use crate::types::column::filename::Filename;
 use crate::types::column::formatted::Formatted;
+use crate::types::column::formatted::FormattedColumns;
 use crate::types::column::guid::Guid;
 use crate::types::column::identifier::Identifier;
 use crate::types::column::identifier::ToIdentifier;
@@ -105,3 +106,16 @@ impl ToUniqueMsiIdentifier for ShortcutDao {
         self.identifier.to_unique_msi_identifier()
     }
 }
+
+impl FormattedColumns for ShortcutDao {
+    fn formatted_columns(&self) -> Vec<&Formatted> {
+        let mut columns = Vec::new();
+        if let Shortcut::Formatted(target) = &self.target {
+            columns.push(target);
+        }
+        columns.extend(self.arguments.iter());
+        columns.extend(self.display_resource_dll.iter());
+        columns.extend(self.description_resource_dll.iter());
+        columns
+    }
+}
@@ -1,44 +1,42 @@
+use getset::Getters;
+
 use crate::constants::*;
-use crate::constants::{self};
 use crate::tables::builder_list_entry::MsiBuilderListEntry;
-use crate::tables::dao::IsDao;
 use crate::tables::signature::table::SignatureIdentifier;
-use crate::types::column::condition::Condition;
-use crate::types::column::identifier::{Identifier, ToIdentifier};
-use crate::types::column::sequence::Sequence;
-use crate::types::helpers::to_msi_value::ToMsiOptionalValue;
+use crate::types::column::identifier::Identifier;
+use crate::types::column::version::Version;
 use crate::types::helpers::to_unique_msi_identifier::ToUniqueMsiIdentifier;
-use crate::types::standard_action::AdvtAction;
 
-#[derive(Debug, Clone, PartialEq)]
+/// The file a search must find. Any limits that are given must also be met
+/// by the file.
+///
+/// [*Reference*](https://learn.microsoft.com/en-us/windows/win32/msi/signature-table)
+#[derive(
+    Debug,
+    Clone,
+    Getters,
+    PartialEq,
+    derive_more::Constructor,
+    whimsi_macros::MsiRow,
+)]
+#[getset(get = "pub")]
 pub struct SignatureDao {
+    #[msi(primary_key, category = "Identifier", max_len = DEFAULT_IDENTIFIER_MAX_LEN)]
     signature: SignatureIdentifier,
+    #[msi(column = "FileName", category = "Text", max_len = FILENAME_MAX_LEN)]
     filename: String,
-    min_version: Option<String>,
-    max_version: Option<String>,
+    #[msi(category = "Text", max_len = SIGNATURE_VERSION_MAX_LEN)]
+    min_version: Option<Version>,
+    #[msi(category = "Text", max_len = SIGNATURE_VERSION_MAX_LEN)]
+    max_version: Option<Version>,
     min_size: Option<i32>,
     max_size: Option<i32>,
     min_date: Option<i32>,
     max_date: Option<i32>,
+    #[msi(category = "Text", max_len = 255)]
     languages: Option<String>,
 }
 
-impl IsDao for SignatureDao {
-    fn to_row(&self) -> Vec<whimsi_msi::Value> {
-        vec![
-            self.signature.clone().into(),
-            self.filename.clone().into(),
-            self.min_version.to_optional_value(),
-            self.max_version.to_optional_value(),
-            self.min_size.to_optional_value(),
-            self.max_size.to_optional_value(),
-            self.min_date.to_optional_value(),
-            self.max_date.to_optional_value(),
-            self.languages.to_optional_value(),
-        ]
-    }
-}
-
 impl ToUniqueMsiIdentifier for SignatureDao {
     fn to_unique_msi_identifier(&self) -> Option<Identifier> {
         self.signature.to_unique_msi_identifier()
@@ -0,0 +1,24 @@
+use bitflags::bitflags;
+
+bitflags! {
+    /// Options for how related products found through an upgrade row are
+    /// handled.
+    ///
+    /// [*Reference*](https://learn.microsoft.com/en-us/windows/win32/msi/upgrade-table)
+    #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+    pub struct UpgradeAttributes: i32 {
+        /// Keep the feature states of the related product.
+        const MIGRATE_FEATURES = 0x001;
+        /// Only set the action property, the related product is not removed.
+        const ONLY_DETECT = 0x002;
+        /// Continue the installation if the related product can't be
+        /// removed.
+        const IGNORE_REMOVE_FAILURE = 0x004;
+        /// Also match products of exactly the minimum version.
+        const VERSION_MIN_INCLUSIVE = 0x100;
+        /// Also match products of exactly the maximum version.
+        const VERSION_MAX_INCLUSIVE = 0x200;
+        /// Match products in any language except the listed ones.
+        const LANGUAGES_EXCLUSIVE = 0x400;
+    }
+}
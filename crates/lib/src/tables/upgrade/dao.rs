@@ -0,0 +1,81 @@
+use getset::Getters;
+
+use crate::constants::*;
+use crate::tables::builder_list_entry::MsiBuilderListEntry;
+use crate::types::column::formatted::Formatted;
+use crate::types::column::guid::Guid;
+use crate::types::column::identifier::Identifier;
+use crate::types::column::version::Version;
+use crate::types::helpers::to_unique_msi_identifier::ToUniqueMsiIdentifier;
+
+/// Finds installed products sharing `upgrade_code` within a range of
+/// versions and sets `action_property` to their product codes.
+///
+/// [*Reference*](https://learn.microsoft.com/en-us/windows/win32/msi/upgrade-table)
+#[derive(
+    Debug,
+    Clone,
+    Getters,
+    PartialEq,
+    derive_more::Constructor,
+    whimsi_macros::MsiRow,
+)]
+#[getset(get = "pub")]
+pub struct UpgradeDao {
+    #[msi(primary_key, category = "Guid", max_len = GUID_MAX_LEN)]
+    upgrade_code: Guid,
+    #[msi(primary_key, category = "Text", max_len = VERSION_MAX_LEN)]
+    version_min: Option<Version>,
+    #[msi(primary_key, category = "Text", max_len = VERSION_MAX_LEN)]
+    version_max: Option<Version>,
+    #[msi(primary_key, category = "Language", max_len = LANGUAGE_MAX_LEN)]
+    language: Option<String>,
+    #[msi(primary_key)]
+    attributes: i32,
+    #[msi(category = "Formatted", max_len = DEFAULT_IDENTIFIER_MAX_LEN)]
+    remove: Option<Formatted>,
+    #[msi(category = "UpperCase", max_len = DEFAULT_IDENTIFIER_MAX_LEN)]
+    action_property: Identifier,
+}
+
+impl MsiBuilderListEntry for UpgradeDao {
+    fn conflicts(&self, other: &Self) -> bool {
+        self.upgrade_code == other.upgrade_code
+            && self.version_min == other.version_min
+            && self.version_max == other.version_max
+            && self.language == other.language
+            && self.attributes == other.attributes
+    }
+}
+
+impl ToUniqueMsiIdentifier for UpgradeDao {
+    fn to_unique_msi_identifier(&self) -> Option<Identifier> {
+        None
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::str::FromStr;
+
+    use super::UpgradeDao;
+    use crate::tables::msi_row::assert_round_trip;
+    use crate::tables::upgrade::attributes::UpgradeAttributes;
+    use crate::types::column::guid::Guid;
+    use crate::types::column::identifier::Identifier;
+    use crate::types::column::version::Version;
+
+    #[test]
+    fn round_trip() {
+        let dao = UpgradeDao::new(
+            Guid::from_str("{B3E4E0A8-2F1C-4C43-9A57-0C5E7D0F3C11}").unwrap(),
+            None,
+            Some(Version::from_str("1.2.3").unwrap()),
+            None,
+            UpgradeAttributes::MIGRATE_FEATURES.bits(),
+            None,
+            Identifier::from_str("OLDERVERSIONDETECTED").unwrap(),
+        );
+        assert_round_trip(dao);
+    }
+}
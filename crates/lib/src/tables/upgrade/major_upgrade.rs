@@ -0,0 +1,41 @@
+use getset::Getters;
+use getset::WithSetters;
+
+use crate::constants::DEFAULT_DOWNGRADE_ERROR_MESSAGE;
+use crate::tables::generic_sequence::scheduling::ActionPlacement;
+use crate::types::standard_action::StandardAction;
+
+/// How a package replaces the installed versions of the same product.
+///
+/// [*Reference*](https://learn.microsoft.com/en-us/windows/win32/msi/major-upgrades)
+#[derive(Clone, Debug, PartialEq, Getters, WithSetters)]
+#[getset(get = "pub", set_with = "pub")]
+pub struct MajorUpgradeOptions {
+    /// Install over a newer version instead of refusing to.
+    allow_downgrades: bool,
+    /// Replace an installed copy of the same version.
+    allow_same_version_upgrades: bool,
+    /// Keep the features that were selected for the replaced version.
+    migrate_features: bool,
+    /// Shown when a newer version is already installed.
+    downgrade_error_message: String,
+    /// Where the replaced version is removed in the `InstallExecuteSequence`.
+    /// Defaults to right after `InstallValidate`, which removes it entirely
+    /// before anything of the new version is installed.
+    remove_existing_products: ActionPlacement,
+}
+
+impl Default for MajorUpgradeOptions {
+    fn default() -> Self {
+        Self {
+            allow_downgrades: false,
+            allow_same_version_upgrades: false,
+            migrate_features: true,
+            downgrade_error_message: DEFAULT_DOWNGRADE_ERROR_MESSAGE
+                .to_string(),
+            remove_existing_products: ActionPlacement::After(
+                StandardAction::InstallValidate.into(),
+            ),
+        }
+    }
+}
@@ -0,0 +1,4 @@
+pub mod attributes;
+pub mod dao;
+pub mod major_upgrade;
+pub mod table;
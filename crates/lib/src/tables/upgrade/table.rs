@@ -0,0 +1,27 @@
+use crate::msi_list_boilerplate;
+use crate::msi_table_boilerplate;
+use crate::tables::builder_list::MsiBuilderList;
+use crate::tables::builder_table::MsiBuilderTable;
+use crate::tables::msi_row::MsiRow;
+use crate::tables::upgrade::dao::UpgradeDao;
+
+#[derive(Debug, Clone, Default)]
+pub struct UpgradeTable {
+    entries: Vec<UpgradeDao>,
+}
+
+impl MsiBuilderTable for UpgradeTable {
+    type TableValue = UpgradeDao;
+
+    msi_table_boilerplate!();
+
+    fn name(&self) -> &'static str {
+        UpgradeDao::TABLE_NAME
+    }
+
+    fn columns(&self) -> Vec<whimsi_msi::Column> {
+        UpgradeDao::columns()
+    }
+}
+
+msi_list_boilerplate!(UpgradeTable, UpgradeDao);
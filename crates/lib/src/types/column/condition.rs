@@ -1,14 +1,554 @@
+use std::str::FromStr;
+
+use getset::Getters;
+use thiserror::Error;
+
+use super::identifier::Identifier;
+
+/// A conditional expression, such as `NOT Installed AND VersionNT >= 601`,
+/// evaluated by the installer at install time.
+///
+/// The text is parsed when the condition is created so syntax errors are
+/// caught when the package is built rather than when it is installed. The
+/// original text is what gets written to the package.
+///
+/// [*Reference*](https://learn.microsoft.com/en-us/windows/win32/msi/conditional-statement-syntax)
+#[derive(Clone, Debug, PartialEq, Getters, whimsi_macros::IntoStrMsiValue)]
+#[getset(get = "pub")]
+pub struct Condition {
+    #[getset(skip)]
+    text: String,
+    expression: ConditionExpression,
+}
+
+impl std::fmt::Display for Condition {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.text)
+    }
+}
+
+impl FromStr for Condition {
+    type Err = ConditionParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let tokens = tokenize(s)?;
+        let expression = Parser::new(tokens, s.chars().count()).parse()?;
+        Ok(Condition { text: s.trim().to_string(), expression })
+    }
+}
+
+/// The parsed form of a [`Condition`].
 #[derive(Clone, Debug, PartialEq)]
-pub struct Condition;
+pub enum ConditionExpression {
+    /// A lone value, which is true when it is set and not `0`.
+    Operand(Operand),
+    Comparison {
+        left: Operand,
+        operator: ComparisonOperator,
+        /// Set when the operator is prefixed with `~`.
+        case_insensitive: bool,
+        right: Operand,
+    },
+    Not(Box<ConditionExpression>),
+    Logical {
+        left: Box<ConditionExpression>,
+        operator: LogicalOperator,
+        right: Box<ConditionExpression>,
+    },
+}
+
+/// A value that can be used in a [`Condition`].
+#[derive(Clone, Debug, PartialEq)]
+pub enum Operand {
+    /// The value of a property, such as `Installed`.
+    Property(Identifier),
+    /// `%NAME`, the value of an environment variable.
+    Environment(String),
+    /// `?Component`, the installed state of a component.
+    ComponentInstalled(Identifier),
+    /// `$Component`, the action state of a component.
+    ComponentAction(Identifier),
+    /// `!Feature`, the installed state of a feature.
+    FeatureInstalled(Identifier),
+    /// `&Feature`, the action state of a feature.
+    FeatureAction(Identifier),
+    /// A string literal surrounded by double quotes.
+    String(String),
+    Integer(i16),
+}
 
-impl ToString for Condition {
-    fn to_string(&self) -> String {
-        todo!()
+/// Operators comparing two [`Operand`]s.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ComparisonOperator {
+    /// `=`
+    Equal,
+    /// `<>`
+    NotEqual,
+    /// `>`
+    Greater,
+    /// `>=`
+    GreaterOrEqual,
+    /// `<`
+    Less,
+    /// `<=`
+    LessOrEqual,
+    /// `><`, the left string contains the right string.
+    Contains,
+    /// `<<`, the left string starts with the right string.
+    StartsWith,
+    /// `>>`, the left string ends with the right string.
+    EndsWith,
+}
+
+/// Operators combining two expressions, listed from highest to lowest
+/// precedence.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LogicalOperator {
+    And,
+    Or,
+    Xor,
+    Eqv,
+    Imp,
+}
+
+impl LogicalOperator {
+    fn from_keyword(word: &str) -> Option<Self> {
+        match word.to_ascii_uppercase().as_str() {
+            "AND" => Some(LogicalOperator::And),
+            "OR" => Some(LogicalOperator::Or),
+            "XOR" => Some(LogicalOperator::Xor),
+            "EQV" => Some(LogicalOperator::Eqv),
+            "IMP" => Some(LogicalOperator::Imp),
+            _ => None,
+        }
     }
 }
 
-impl From<Condition> for whimsi_msi::Value {
-    fn from(value: Condition) -> Self {
-        value.to_string().into()
+// Longest operators first so `<>` isn't read as `<` followed by `>`.
+const COMPARISON_OPERATORS: &[(&str, ComparisonOperator)] = &[
+    ("><", ComparisonOperator::Contains),
+    ("<<", ComparisonOperator::StartsWith),
+    (">>", ComparisonOperator::EndsWith),
+    ("<>", ComparisonOperator::NotEqual),
+    (">=", ComparisonOperator::GreaterOrEqual),
+    ("<=", ComparisonOperator::LessOrEqual),
+    ("=", ComparisonOperator::Equal),
+    (">", ComparisonOperator::Greater),
+    ("<", ComparisonOperator::Less),
+];
+
+/// Positions are 1-based character offsets into the condition text.
+#[derive(Debug, Error, PartialEq)]
+pub enum ConditionParseError {
+    #[error("Condition is empty")]
+    Empty,
+    #[error("Unexpected character [{character}] at position {position}")]
+    UnexpectedCharacter { character: char, position: usize },
+    #[error("Unterminated string starting at position {position}")]
+    UnterminatedString { position: usize },
+    #[error("Integer [{text}] at position {position} is out of range")]
+    IntegerOutOfRange { text: String, position: usize },
+    #[error("Expected {expected} at position {position}")]
+    Expected { expected: &'static str, position: usize },
+    #[error("Unexpected [{found}] at position {position}")]
+    UnexpectedToken { found: String, position: usize },
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Token {
+    LeftParen,
+    RightParen,
+    Not,
+    Logical(LogicalOperator),
+    Comparison(ComparisonOperator, bool),
+    Operand(Operand),
+}
+
+#[derive(Clone, Debug)]
+struct Spanned {
+    token: Token,
+    text: String,
+    position: usize,
+}
+
+fn is_name_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || c == '_' || c == '.'
+}
+
+fn tokenize(input: &str) -> Result<Vec<Spanned>, ConditionParseError> {
+    let chars = input.chars().collect::<Vec<_>>();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+    while i < chars.len() {
+        let start = i;
+        let c = chars[i];
+        if c.is_whitespace() {
+            i += 1;
+            continue;
+        }
+        let token = match c {
+            '(' => {
+                i += 1;
+                Token::LeftParen
+            }
+            ')' => {
+                i += 1;
+                Token::RightParen
+            }
+            '"' => {
+                let end = chars[i + 1..].iter().position(|c| *c == '"').ok_or(
+                    ConditionParseError::UnterminatedString {
+                        position: start + 1,
+                    },
+                )?;
+                let string = chars[i + 1..i + 1 + end].iter().collect();
+                i += end + 2;
+                Token::Operand(Operand::String(string))
+            }
+            '~' | '=' | '<' | '>' => {
+                let case_insensitive = c == '~';
+                let offset = usize::from(case_insensitive);
+                let rest =
+                    chars[i + offset..].iter().take(2).collect::<String>();
+                let (text, operator) = COMPARISON_OPERATORS
+                    .iter()
+                    .find(|(text, _)| rest.starts_with(text))
+                    .ok_or(ConditionParseError::Expected {
+                        expected: "a comparison operator",
+                        position: start + offset + 1,
+                    })?;
+                i += offset + text.len();
+                Token::Comparison(*operator, case_insensitive)
+            }
+            '-' | '0'..='9' => {
+                let mut end = i + 1;
+                while end < chars.len() && chars[end].is_ascii_digit() {
+                    end += 1;
+                }
+                if end < chars.len() && is_name_char(chars[end]) {
+                    return Err(ConditionParseError::UnexpectedCharacter {
+                        character: chars[end],
+                        position: end + 1,
+                    });
+                }
+                let text = chars[i..end].iter().collect::<String>();
+                if text == "-" {
+                    return Err(ConditionParseError::Expected {
+                        expected: "a digit",
+                        position: end + 1,
+                    });
+                }
+                // Windows Installer integers range from -32767 to 32767.
+                let integer = text
+                    .parse::<i16>()
+                    .ok()
+                    .filter(|integer| *integer != i16::MIN)
+                    .ok_or(ConditionParseError::IntegerOutOfRange {
+                        text: text.clone(),
+                        position: start + 1,
+                    })?;
+                i = end;
+                Token::Operand(Operand::Integer(integer))
+            }
+            '%' | '$' | '?' | '&' | '!' => {
+                let mut end = i + 1;
+                while end < chars.len() && is_name_char(chars[end]) {
+                    end += 1;
+                }
+                let name = chars[i + 1..end].iter().collect::<String>();
+                if name.is_empty() {
+                    return Err(ConditionParseError::Expected {
+                        expected: "a name",
+                        position: end + 1,
+                    });
+                }
+                let operand = if c == '%' {
+                    Operand::Environment(name)
+                } else {
+                    let identifier =
+                        Identifier::from_str(&name).map_err(|_| {
+                            ConditionParseError::UnexpectedCharacter {
+                                character: chars[i + 1],
+                                position: start + 2,
+                            }
+                        })?;
+                    match c {
+                        '$' => Operand::ComponentAction(identifier),
+                        '?' => Operand::ComponentInstalled(identifier),
+                        '&' => Operand::FeatureAction(identifier),
+                        _ => Operand::FeatureInstalled(identifier),
+                    }
+                };
+                i = end;
+                Token::Operand(operand)
+            }
+            c if c.is_ascii_alphabetic() || c == '_' => {
+                let mut end = i + 1;
+                while end < chars.len() && is_name_char(chars[end]) {
+                    end += 1;
+                }
+                let word = chars[i..end].iter().collect::<String>();
+                i = end;
+                if word.eq_ignore_ascii_case("NOT") {
+                    Token::Not
+                } else if let Some(operator) =
+                    LogicalOperator::from_keyword(&word)
+                {
+                    Token::Logical(operator)
+                } else {
+                    // Only valid identifier characters were consumed.
+                    Token::Operand(Operand::Property(
+                        Identifier::from_str(&word).unwrap(),
+                    ))
+                }
+            }
+            _ => {
+                return Err(ConditionParseError::UnexpectedCharacter {
+                    character: c,
+                    position: start + 1,
+                });
+            }
+        };
+        tokens.push(Spanned {
+            token,
+            text: chars[start..i].iter().collect(),
+            position: start + 1,
+        });
+    }
+    Ok(tokens)
+}
+
+struct Parser {
+    tokens: Vec<Spanned>,
+    index: usize,
+    end_position: usize,
+}
+
+impl Parser {
+    fn new(tokens: Vec<Spanned>, length: usize) -> Self {
+        Parser { tokens, index: 0, end_position: length + 1 }
+    }
+
+    fn parse(mut self) -> Result<ConditionExpression, ConditionParseError> {
+        if self.tokens.is_empty() {
+            return Err(ConditionParseError::Empty);
+        }
+        let expression = self.parse_logical(LogicalOperator::Imp)?;
+        match self.peek() {
+            None => Ok(expression),
+            Some(spanned) => Err(ConditionParseError::UnexpectedToken {
+                found: spanned.text.clone(),
+                position: spanned.position,
+            }),
+        }
+    }
+
+    fn peek(&self) -> Option<&Spanned> {
+        self.tokens.get(self.index)
+    }
+
+    fn next_position(&self) -> usize {
+        self.peek().map(|spanned| spanned.position).unwrap_or(self.end_position)
+    }
+
+    fn next_token(&mut self) -> Option<Token> {
+        let token = self.peek().map(|spanned| spanned.token.clone());
+        self.index += 1;
+        token
+    }
+
+    /// Parses a chain of `operator`, whose operands are expressions of the
+    /// next higher precedence.
+    fn parse_logical(
+        &mut self,
+        operator: LogicalOperator,
+    ) -> Result<ConditionExpression, ConditionParseError> {
+        let higher = match operator {
+            LogicalOperator::Imp => Some(LogicalOperator::Eqv),
+            LogicalOperator::Eqv => Some(LogicalOperator::Xor),
+            LogicalOperator::Xor => Some(LogicalOperator::Or),
+            LogicalOperator::Or => Some(LogicalOperator::And),
+            LogicalOperator::And => None,
+        };
+        let mut parse_operand = |parser: &mut Self| match higher {
+            Some(higher) => parser.parse_logical(higher),
+            None => parser.parse_not(),
+        };
+        let mut left = parse_operand(self)?;
+        while self
+            .peek()
+            .is_some_and(|spanned| spanned.token == Token::Logical(operator))
+        {
+            self.index += 1;
+            let right = parse_operand(self)?;
+            left = ConditionExpression::Logical {
+                left: Box::new(left),
+                operator,
+                right: Box::new(right),
+            };
+        }
+        Ok(left)
+    }
+
+    fn parse_not(
+        &mut self,
+    ) -> Result<ConditionExpression, ConditionParseError> {
+        if self.peek().is_some_and(|spanned| spanned.token == Token::Not) {
+            self.index += 1;
+            return Ok(ConditionExpression::Not(Box::new(self.parse_not()?)));
+        }
+        self.parse_term()
+    }
+
+    fn parse_term(
+        &mut self,
+    ) -> Result<ConditionExpression, ConditionParseError> {
+        let position = self.next_position();
+        match self.next_token() {
+            Some(Token::LeftParen) => {
+                let expression = self.parse_logical(LogicalOperator::Imp)?;
+                let position = self.next_position();
+                match self.next_token() {
+                    Some(Token::RightParen) => Ok(expression),
+                    _ => Err(ConditionParseError::Expected {
+                        expected: "[)]",
+                        position,
+                    }),
+                }
+            }
+            Some(Token::Operand(left)) => {
+                let Some(Token::Comparison(operator, case_insensitive)) =
+                    self.peek().map(|spanned| spanned.token.clone())
+                else {
+                    return Ok(ConditionExpression::Operand(left));
+                };
+                self.index += 1;
+                let position = self.next_position();
+                match self.next_token() {
+                    Some(Token::Operand(right)) => {
+                        Ok(ConditionExpression::Comparison {
+                            left,
+                            operator,
+                            case_insensitive,
+                            right,
+                        })
+                    }
+                    _ => Err(ConditionParseError::Expected {
+                        expected: "a value",
+                        position,
+                    }),
+                }
+            }
+            _ => Err(ConditionParseError::Expected {
+                expected: "a value or [(]",
+                position,
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::str::FromStr;
+
+    use test_case::test_case;
+
+    use super::ComparisonOperator;
+    use super::Condition;
+    use super::ConditionExpression;
+    use super::ConditionParseError;
+    use super::LogicalOperator;
+    use super::Operand;
+    use crate::types::column::identifier::Identifier;
+
+    fn property(name: &str) -> Operand {
+        Operand::Property(Identifier::from_str(name).unwrap())
+    }
+
+    #[test_case("Installed"; "property")]
+    #[test_case("NOT Installed"; "not")]
+    #[test_case("VersionNT >= 601 AND NOT Installed"; "comparison")]
+    #[test_case("(A OR B) AND NOT (C XOR D)"; "parentheses")]
+    #[test_case("A EQV B IMP C"; "eqv and imp")]
+    #[test_case("$MainComponent = 3"; "component action")]
+    #[test_case("?MainComponent = 3"; "component installed")]
+    #[test_case("&MainFeature = 3 OR !MainFeature = 2"; "feature states")]
+    #[test_case("%PROCESSOR_ARCHITECTURE = \"x86\""; "environment")]
+    #[test_case("WindowsFolder >< \"Win\""; "contains")]
+    #[test_case("WindowsFolder ~<< \"c:\\\""; "case insensitive starts with")]
+    #[test_case("ProductName >> \"Tools\""; "ends with")]
+    #[test_case("REMOVE ~= \"ALL\""; "case insensitive equal")]
+    #[test_case("Version9X<>-1"; "negative integer without spaces")]
+    #[test_case("not installed and (a or b)"; "lowercase keywords")]
+    fn valid_condition(input: &str) {
+        let condition = Condition::from_str(input).unwrap();
+        assert_eq!(condition.to_string(), input);
+    }
+
+    #[test]
+    fn precedence() {
+        let condition = Condition::from_str("NOT A OR B AND C = 1").unwrap();
+        let expected = ConditionExpression::Logical {
+            left: Box::new(ConditionExpression::Not(Box::new(
+                ConditionExpression::Operand(property("A")),
+            ))),
+            operator: LogicalOperator::Or,
+            right: Box::new(ConditionExpression::Logical {
+                left: Box::new(ConditionExpression::Operand(property("B"))),
+                operator: LogicalOperator::And,
+                right: Box::new(ConditionExpression::Comparison {
+                    left: property("C"),
+                    operator: ComparisonOperator::Equal,
+                    case_insensitive: false,
+                    right: Operand::Integer(1),
+                }),
+            }),
+        };
+        assert_eq!(condition.expression(), &expected);
+    }
+
+    #[test_case("", ConditionParseError::Empty; "empty")]
+    #[test_case(
+        "A AND",
+        ConditionParseError::Expected { expected: "a value or [(]", position: 6 };
+        "missing operand"
+    )]
+    #[test_case(
+        "(A OR B",
+        ConditionParseError::Expected { expected: "[)]", position: 8 };
+        "unclosed parenthesis"
+    )]
+    #[test_case(
+        "A = \"B",
+        ConditionParseError::UnterminatedString { position: 5 };
+        "unterminated string"
+    )]
+    #[test_case(
+        "A = 40000",
+        ConditionParseError::IntegerOutOfRange { text: "40000".to_string(), position: 5 };
+        "integer out of range"
+    )]
+    #[test_case(
+        "A = B = C",
+        ConditionParseError::UnexpectedToken { found: "=".to_string(), position: 7 };
+        "chained comparison"
+    )]
+    #[test_case(
+        "A ~ B",
+        ConditionParseError::Expected { expected: "a comparison operator", position: 4 };
+        "bare tilde"
+    )]
+    #[test_case(
+        "A # B",
+        ConditionParseError::UnexpectedCharacter { character: '#', position: 3 };
+        "invalid character"
+    )]
+    #[test_case(
+        "$ = 1",
+        ConditionParseError::Expected { expected: "a name", position: 2 };
+        "missing component name"
+    )]
+    fn invalid_condition(input: &str, expected: ConditionParseError) {
+        assert_eq!(Condition::from_str(input), Err(expected));
     }
 }
@@ -1,3 +1,16 @@
+use std::str::FromStr;
+
+use thiserror::Error;
+
+use super::identifier::Identifier;
+
+/// Text that the installer resolves at install time, replacing references
+/// such as `[INSTALLDIR]` or `[#FileKey]` with their values.
+///
+/// Any text can be stored, [`Formatted::segments`] parses it into the
+/// references the installer will resolve.
+///
+/// [*Reference*](https://learn.microsoft.com/en-us/windows/win32/msi/formatted)
 #[derive(
     Debug,
     Clone,
@@ -5,6 +18,265 @@
     Default,
     derive_more::From,
     derive_more::Display,
+    derive_more::FromStr,
     whimsi_macros::IntoStrMsiValue,
 )]
 pub struct Formatted(String);
+
+impl Formatted {
+    /// Formatted text that resolves to exactly `text`. `[`, `]`, `{` and `}`
+    /// are escaped so the installer doesn't read them as references.
+    pub fn escaped(text: &str) -> Formatted {
+        let mut escaped = String::with_capacity(text.len());
+        for c in text.chars() {
+            match c {
+                '[' | ']' | '{' | '}' => {
+                    escaped.push_str("[\\");
+                    escaped.push(c);
+                    escaped.push(']');
+                }
+                c => escaped.push(c),
+            }
+        }
+        Formatted(escaped)
+    }
+
+    /// Parses the text into literal text and references.
+    ///
+    /// Fails if a `[` or `{` is never closed or a reference isn't valid.
+    pub fn segments(
+        &self,
+    ) -> Result<Vec<FormattedSegment>, FormattedParseError> {
+        let chars = self.0.chars().collect::<Vec<_>>();
+        let mut index = 0;
+        parse_segments(&chars, &mut index, None)
+    }
+}
+
+/// A piece of [`Formatted`] text.
+#[derive(Clone, Debug, PartialEq)]
+pub enum FormattedSegment {
+    /// Text that is written as is.
+    Text(String),
+    /// `[Property]`, the value of a property or the path of a directory.
+    Property(Identifier),
+    /// `[#FileKey]`, the full path of a file.
+    FileFullPath(Identifier),
+    /// `[!FileKey]`, the full short path of a file.
+    FileShortPath(Identifier),
+    /// `[$ComponentKey]`, the directory a component is installed to.
+    ComponentDirectory(Identifier),
+    /// `[%ENV]`, the value of an environment variable.
+    Environment(String),
+    /// `[\x]`, the character `x` without any special meaning.
+    Escaped(char),
+    /// `[~]`, a null character.
+    Null,
+    /// `{...}`, text that is removed entirely if any property it
+    /// references is not set.
+    Group(Vec<FormattedSegment>),
+}
+
+/// Positions are 1-based character offsets into the formatted text.
+#[derive(Debug, Error, PartialEq)]
+pub enum FormattedParseError {
+    #[error("Unclosed [[] at position {position}")]
+    UnclosedBracket { position: usize },
+    #[error("Unclosed [{{] at position {position}")]
+    UnclosedBrace { position: usize },
+    #[error("Empty reference at position {position}")]
+    EmptyReference { position: usize },
+    #[error("Invalid reference [{reference}] at position {position}")]
+    InvalidReference { reference: String, position: usize },
+}
+
+/// Parses segments until the end of the text, or until the `}` closing the
+/// group opened at `group_start`.
+fn parse_segments(
+    chars: &[char],
+    index: &mut usize,
+    group_start: Option<usize>,
+) -> Result<Vec<FormattedSegment>, FormattedParseError> {
+    let mut segments = Vec::new();
+    let mut text = String::new();
+    loop {
+        let Some(&c) = chars.get(*index) else {
+            if let Some(position) = group_start {
+                return Err(FormattedParseError::UnclosedBrace { position });
+            }
+            break;
+        };
+        let position = *index + 1;
+        match c {
+            '[' => {
+                if !text.is_empty() {
+                    segments.push(FormattedSegment::Text(std::mem::take(
+                        &mut text,
+                    )));
+                }
+                segments.push(parse_reference(chars, index)?);
+            }
+            '{' => {
+                if !text.is_empty() {
+                    segments.push(FormattedSegment::Text(std::mem::take(
+                        &mut text,
+                    )));
+                }
+                *index += 1;
+                let group = parse_segments(chars, index, Some(position))?;
+                segments.push(FormattedSegment::Group(group));
+            }
+            '}' if group_start.is_some() => {
+                *index += 1;
+                break;
+            }
+            c => {
+                text.push(c);
+                *index += 1;
+            }
+        }
+    }
+    if !text.is_empty() {
+        segments.push(FormattedSegment::Text(text));
+    }
+    Ok(segments)
+}
+
+/// Parses the reference starting with the `[` at `index`.
+fn parse_reference(
+    chars: &[char],
+    index: &mut usize,
+) -> Result<FormattedSegment, FormattedParseError> {
+    let position = *index + 1;
+    // `[\]]` escapes the closing bracket itself so it has to be handled
+    // before looking for the end of the reference.
+    if chars.get(*index + 1) == Some(&'\\') {
+        return match (chars.get(*index + 2), chars.get(*index + 3)) {
+            (Some(&escaped), Some(']')) => {
+                *index += 4;
+                Ok(FormattedSegment::Escaped(escaped))
+            }
+            _ => Err(FormattedParseError::UnclosedBracket { position }),
+        };
+    }
+    let length = chars[*index + 1..]
+        .iter()
+        .position(|c| *c == ']')
+        .ok_or(FormattedParseError::UnclosedBracket { position })?;
+    let reference =
+        chars[*index + 1..*index + 1 + length].iter().collect::<String>();
+    *index += length + 2;
+
+    let invalid = || FormattedParseError::InvalidReference {
+        reference: reference.clone(),
+        position,
+    };
+    let identifier =
+        |name: &str| Identifier::from_str(name).map_err(|_| invalid());
+    let mut rest = reference.chars();
+    let segment = match rest.next() {
+        None => return Err(FormattedParseError::EmptyReference { position }),
+        Some('~') if reference.len() == 1 => FormattedSegment::Null,
+        Some('#') => FormattedSegment::FileFullPath(identifier(rest.as_str())?),
+        Some('!') => {
+            FormattedSegment::FileShortPath(identifier(rest.as_str())?)
+        }
+        Some('$') => {
+            FormattedSegment::ComponentDirectory(identifier(rest.as_str())?)
+        }
+        Some('%') if !rest.as_str().is_empty() => {
+            FormattedSegment::Environment(rest.as_str().to_string())
+        }
+        Some(_) => FormattedSegment::Property(identifier(&reference)?),
+    };
+    Ok(segment)
+}
+
+/// Implemented by DAOs with `Formatted` columns so the builder can check
+/// their references before the package is written.
+pub(crate) trait FormattedColumns {
+    fn formatted_columns(&self) -> Vec<&Formatted>;
+}
+
+#[cfg(test)]
+mod test {
+    use std::str::FromStr;
+
+    use test_case::test_case;
+
+    use super::Formatted;
+    use super::FormattedParseError;
+    use super::FormattedSegment;
+    use crate::types::column::identifier::Identifier;
+
+    fn id(name: &str) -> Identifier {
+        Identifier::from_str(name).unwrap()
+    }
+
+    #[test_case("Plain text", vec![FormattedSegment::Text("Plain text".to_string())]; "text")]
+    #[test_case(
+        "[INSTALLDIR]bin",
+        vec![
+            FormattedSegment::Property(id("INSTALLDIR")),
+            FormattedSegment::Text("bin".to_string()),
+        ];
+        "property"
+    )]
+    #[test_case(
+        "\"[#App.exe]\" [!App.exe] [$Main]",
+        vec![
+            FormattedSegment::Text("\"".to_string()),
+            FormattedSegment::FileFullPath(id("App.exe")),
+            FormattedSegment::Text("\" ".to_string()),
+            FormattedSegment::FileShortPath(id("App.exe")),
+            FormattedSegment::Text(" ".to_string()),
+            FormattedSegment::ComponentDirectory(id("Main")),
+        ];
+        "file and component"
+    )]
+    #[test_case(
+        "[%PATH][~][\\[][\\]]",
+        vec![
+            FormattedSegment::Environment("PATH".to_string()),
+            FormattedSegment::Null,
+            FormattedSegment::Escaped('['),
+            FormattedSegment::Escaped(']'),
+        ];
+        "environment null and escapes"
+    )]
+    #[test_case(
+        "a{-p [PASSWORD]}",
+        vec![
+            FormattedSegment::Text("a".to_string()),
+            FormattedSegment::Group(vec![
+                FormattedSegment::Text("-p ".to_string()),
+                FormattedSegment::Property(id("PASSWORD")),
+            ]),
+        ];
+        "group"
+    )]
+    #[test_case("a}b", vec![FormattedSegment::Text("a}b".to_string())]; "stray closing brace")]
+    fn valid_formatted(input: &str, expected: Vec<FormattedSegment>) {
+        let formatted = Formatted::from(input.to_string());
+        assert_eq!(formatted.segments(), Ok(expected));
+    }
+
+    #[test_case("[INSTALLDIR", FormattedParseError::UnclosedBracket { position: 1 }; "unclosed bracket")]
+    #[test_case("a{[B]", FormattedParseError::UnclosedBrace { position: 2 }; "unclosed brace")]
+    #[test_case("a[]", FormattedParseError::EmptyReference { position: 2 }; "empty reference")]
+    #[test_case("[\\a", FormattedParseError::UnclosedBracket { position: 1 }; "unclosed escape")]
+    #[test_case(
+        "[#1file]",
+        FormattedParseError::InvalidReference { reference: "#1file".to_string(), position: 1 };
+        "invalid file key"
+    )]
+    #[test_case(
+        "x [My Prop]",
+        FormattedParseError::InvalidReference { reference: "My Prop".to_string(), position: 3 };
+        "invalid property"
+    )]
+    fn invalid_formatted(input: &str, expected: FormattedParseError) {
+        let formatted = Formatted::from(input.to_string());
+        assert_eq!(formatted.segments(), Err(expected));
+    }
+}
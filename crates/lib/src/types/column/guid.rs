@@ -1,10 +1,28 @@
+use std::str::FromStr;
+
+use anyhow::Context;
 use uuid::Uuid;
 
-#[derive(Clone, derive_more::Display, Debug, PartialEq)]
+/// A GUID in the upper case, braced form the installer expects, such as
+/// `{6F9619FF-8B86-D011-B42D-00C04FC964FF}`.
+///
+/// [*Reference*](https://learn.microsoft.com/en-us/windows/win32/msi/guid)
+#[derive(Clone, derive_more::Display, Debug, PartialEq, Eq, Hash)]
 pub struct Guid(String);
 impl From<Uuid> for Guid {
     fn from(value: Uuid) -> Self {
-        todo!()
+        Self(value.braced().to_string().to_uppercase())
+    }
+}
+
+impl FromStr for Guid {
+    type Err = anyhow::Error;
+
+    /// Accepts any form [`Uuid`] can parse, with or without braces.
+    fn from_str(s: &str) -> anyhow::Result<Self> {
+        let uuid = Uuid::parse_str(s.trim())
+            .with_context(|| format!("[{s}] is not a valid GUID"))?;
+        Ok(uuid.into())
     }
 }
 
@@ -13,3 +31,27 @@ impl From<Guid> for whimsi_msi::Value {
         value.0.into()
     }
 }
+
+#[cfg(test)]
+mod test {
+    use std::str::FromStr;
+
+    use test_case::test_case;
+
+    use super::Guid;
+
+    #[test_case("{6F9619FF-8B86-D011-B42D-00C04FC964FF}"; "braced")]
+    #[test_case("6f9619ff-8b86-d011-b42d-00c04fc964ff"; "lower case")]
+    #[test_case("6F9619FF8B86D011B42D00C04FC964FF"; "simple")]
+    fn guids_are_normalized(input: &str) {
+        assert_eq!(
+            Guid::from_str(input).unwrap().to_string(),
+            "{6F9619FF-8B86-D011-B42D-00C04FC964FF}"
+        );
+    }
+
+    #[test]
+    fn invalid_guids_fail() {
+        assert!(Guid::from_str("{not-a-guid}").is_err());
+    }
+}
@@ -14,6 +14,12 @@ use crate::types::column::formatted::Formatted;
 )]
 pub struct RegPath(Formatted);
 
+impl RegPath {
+    pub(crate) fn formatted(&self) -> &Formatted {
+        &self.0
+    }
+}
+
 impl FromStr for RegPath {
     type Err = anyhow::Error;
 
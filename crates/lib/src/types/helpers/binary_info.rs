@@ -0,0 +1,32 @@
+use std::path::Path;
+use std::path::PathBuf;
+
+use crate::tables::binary::table::BinaryIdentifier;
+
+/// Where the data for a `Binary` table entry comes from.
+#[derive(Clone, Debug, PartialEq, derive_more::From)]
+pub enum BinaryData {
+    /// A file on the building system, read when the MSI is built.
+    Path(PathBuf),
+    /// Data held in memory.
+    Bytes(Vec<u8>),
+}
+
+impl From<&Path> for BinaryData {
+    fn from(value: &Path) -> Self {
+        BinaryData::Path(value.to_path_buf())
+    }
+}
+
+impl From<&[u8]> for BinaryData {
+    fn from(value: &[u8]) -> Self {
+        BinaryData::Bytes(value.to_vec())
+    }
+}
+
+#[derive(Clone, Debug, getset::Getters, derive_more::Constructor)]
+#[getset(get = "pub(crate)")]
+pub(crate) struct BinaryInfo {
+    data: BinaryData,
+    identifier: BinaryIdentifier,
+}
@@ -1,16 +1,45 @@
-use std::cell::RefCell;
-use std::rc::Rc;
 use std::str::FromStr;
+use std::sync::Arc;
+use std::sync::RwLock;
 
 use anyhow::Context;
 
 use crate::types::column::identifier::Identifier;
 use crate::types::column::identifier::ToIdentifier;
 
+/// The identifiers used across every table of an MSI, shared between the
+/// table generators so that no two of them hand out the same identifier.
+///
+/// Backed by `Arc<RwLock<..>>` so that a builder can be moved to, or shared
+/// with, another thread.
+#[derive(Debug, Clone, Default)]
+pub struct SharedIdentifiers(Arc<RwLock<Vec<Identifier>>>);
+
+impl SharedIdentifiers {
+    pub fn contains(&self, identifier: &Identifier) -> bool {
+        self.0.read().unwrap().contains(identifier)
+    }
+
+    pub fn push(&self, identifier: Identifier) {
+        self.0.write().unwrap().push(identifier);
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.read().unwrap().len()
+    }
+}
+
+impl PartialEq for SharedIdentifiers {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+            || *self.0.read().unwrap() == *other.0.read().unwrap()
+    }
+}
+
 pub(crate) trait IdGenerator {
     type IdentifierType: ToIdentifier + FromStr<Err = anyhow::Error>;
     fn id_prefix(&self) -> &str;
-    fn used(&self) -> &Rc<RefCell<Vec<Identifier>>>;
+    fn used(&self) -> &SharedIdentifiers;
     fn count(&self) -> usize;
     fn count_mut(&mut self) -> &mut usize;
 
@@ -31,7 +60,7 @@ pub(crate) trait IdGenerator {
                 <Self::IdentifierType as ToIdentifier>::to_identifier(
                     &new_identifier,
                 );
-            if !self.used().borrow().contains(&generic_identifier) {
+            if !self.used().contains(&generic_identifier) {
                 return new_identifier;
             }
 
@@ -44,11 +73,11 @@ pub(crate) trait IdGenerator {
         identifier: impl ToIdentifier,
     ) -> anyhow::Result<()> {
         let identifier = identifier.to_identifier();
-        if self.used().borrow().contains(&identifier) {
+        if self.used().contains(&identifier) {
             anyhow::bail!("Identifier [{}] is already used", identifier)
         }
 
-        self.used().borrow_mut().push(identifier);
+        self.used().push(identifier);
         Ok(())
     }
 }
@@ -104,7 +133,7 @@ macro_rules! define_identifier_generator {
             #[derive(Debug, Clone, Default, PartialEq)]
             pub(crate) struct [<$id_type:camel IdGenerator>] {
                 count: usize,
-                used: std::rc::Rc<std::cell::RefCell<Vec<$crate::types::column::identifier::Identifier>>>,
+                used: $crate::types::helpers::id_generator::SharedIdentifiers,
             }
 
             impl $crate::types::helpers::id_generator::IdGenerator for [<$id_type:camel IdGenerator>] {
@@ -114,7 +143,7 @@ macro_rules! define_identifier_generator {
                     $crate::constants::[<$id_type:upper _IDENTIFIER_PREFIX>]
                 }
 
-                fn used(&self) -> &std::rc::Rc<std::cell::RefCell<Vec<$crate::types::column::identifier::Identifier>>> {
+                fn used(&self) -> &$crate::types::helpers::id_generator::SharedIdentifiers {
                     &self.used
                 }
 
@@ -127,9 +156,9 @@ macro_rules! define_identifier_generator {
                 }
             }
 
-            impl From<std::rc::Rc<std::cell::RefCell<Vec<$crate::types::column::identifier::Identifier>>>> for [<$id_type:camel IdGenerator>] {
-                fn from(value: std::rc::Rc<std::cell::RefCell<Vec<$crate::types::column::identifier::Identifier>>>) -> Self {
-                    let count = value.borrow().len();
+            impl From<$crate::types::helpers::id_generator::SharedIdentifiers> for [<$id_type:camel IdGenerator>] {
+                fn from(value: $crate::types::helpers::id_generator::SharedIdentifiers) -> Self {
+                    let count = value.len();
                     Self {
                         used: value,
                         count: 0,
@@ -160,11 +189,7 @@ macro_rules! implement_new_for_id_generator_table {
     ($table:ident, $generator:ident) => {
         impl $table {
             pub fn new(
-                identifiers: std::rc::Rc<
-                    std::cell::RefCell<
-                        Vec<$crate::types::column::identifier::Identifier>,
-                    >,
-                >,
+                identifiers: $crate::types::helpers::id_generator::SharedIdentifiers,
             ) -> Self {
                 let entries = Vec::new();
                 let generator = $generator::from(identifiers);
@@ -1,10 +1,12 @@
 pub mod architecture;
+pub mod binary_info;
 pub mod cabinet_info;
 pub mod cabinets;
 pub(crate) mod icon;
 pub(crate) mod id_generator;
 pub mod invalid_char;
 pub mod page_count;
+pub mod reproducibility;
 pub mod security_flag;
 pub mod to_msi_value;
 pub mod to_unique_msi_identifier;
@@ -0,0 +1,170 @@
+use std::io::Read;
+use std::io::Seek;
+use std::io::Write;
+use std::time::Duration;
+use std::time::SystemTime;
+use std::time::UNIX_EPOCH;
+
+use anyhow::Context;
+use getset::Getters;
+use itertools::Itertools;
+use md5::Digest;
+use md5::Md5;
+use uuid::Uuid;
+
+use crate::constants::DEFAULT_REPRODUCIBLE_TIMESTAMP;
+use crate::constants::PACKAGE_CODE_GUID_NAME;
+use crate::constants::SOURCE_DATE_EPOCH_VARIABLE;
+
+/// Settings that make two builds from identical inputs produce byte-identical
+/// MSIs.
+///
+/// Every timestamp written to the package (summary information and cabinet
+/// file dates) is fixed to `timestamp`, and every GUID the builder would
+/// otherwise generate randomly is derived from `seed` and what it identifies
+/// instead. The PackageCode is derived from the contents of the package, so
+/// packages that differ in any way never share one.
+#[derive(Clone, Debug, PartialEq, Getters)]
+#[getset(get = "pub")]
+pub struct Reproducibility {
+    seed: String,
+    timestamp: SystemTime,
+}
+
+impl Reproducibility {
+    pub fn new(seed: impl ToString, timestamp: SystemTime) -> Self {
+        Self { seed: seed.to_string(), timestamp }
+    }
+
+    /// Reproducible settings for an explicit seed. The timestamp is taken from
+    /// `SOURCE_DATE_EPOCH` when it is set, otherwise a fixed default is used.
+    pub fn from_seed(seed: impl ToString) -> anyhow::Result<Self> {
+        let timestamp = match std::env::var(SOURCE_DATE_EPOCH_VARIABLE) {
+            Ok(value) => parse_source_date_epoch(&value)?,
+            Err(_) => {
+                UNIX_EPOCH + Duration::from_secs(DEFAULT_REPRODUCIBLE_TIMESTAMP)
+            }
+        };
+        Ok(Self::new(seed, timestamp))
+    }
+
+    /// Reproducible settings driven by the `SOURCE_DATE_EPOCH` environment
+    /// variable, as described at <https://reproducible-builds.org/specs/source-date-epoch/>.
+    ///
+    /// Returns `None` if the variable is not set. The variable's value is also
+    /// used as the seed.
+    pub fn from_env() -> anyhow::Result<Option<Self>> {
+        let Ok(seed) = std::env::var(SOURCE_DATE_EPOCH_VARIABLE) else {
+            return Ok(None);
+        };
+        let timestamp = parse_source_date_epoch(&seed)?;
+        Ok(Some(Self::new(seed, timestamp)))
+    }
+
+    /// Derives a GUID for the given purpose (for example `"ProductCode"`)
+    /// from the values that identify what it is for, such as the UpgradeCode,
+    /// ProductVersion and ProductName of a product.
+    ///
+    /// The same seed, name and inputs always produce the same GUID, and
+    /// changing any of them produces an unrelated GUID.
+    pub fn guid(&self, name: &str, inputs: &[&str]) -> Uuid {
+        let namespace =
+            Uuid::new_v5(&Uuid::NAMESPACE_OID, self.seed.as_bytes());
+        let name =
+            std::iter::once(name).chain(inputs.iter().copied()).join("\0");
+        Uuid::new_v5(&namespace, name.as_bytes())
+    }
+
+    /// Derives the PackageCode of a fully written package from a hash of
+    /// every row of every table and the contents of every stream.
+    pub fn package_code<F: Read + Write + Seek>(
+        &self,
+        package: &mut whimsi_msi::Package<F>,
+    ) -> anyhow::Result<Uuid> {
+        let mut hasher = Md5::new();
+        let table_names = package
+            .tables()
+            .map(|table| table.name().to_string())
+            .sorted()
+            .collect_vec();
+        for table_name in table_names {
+            hasher.update(table_name.as_bytes());
+            for row in
+                package.select_rows(whimsi_msi::Select::table(&table_name))?
+            {
+                for index in 0..row.len() {
+                    hasher.update(format!("\0{:?}", row[index]).as_bytes());
+                }
+                hasher.update(b"\n");
+            }
+        }
+        let stream_names = package.streams().sorted().collect_vec();
+        for stream_name in stream_names {
+            hasher.update(stream_name.as_bytes());
+            let mut stream = package.read_stream(&stream_name)?;
+            std::io::copy(&mut stream, &mut hasher)?;
+        }
+        let digest =
+            hasher.finalize().iter().map(|byte| format!("{byte:02x}")).join("");
+        Ok(self.guid(PACKAGE_CODE_GUID_NAME, &[&digest]))
+    }
+
+    /// The fixed timestamp as a date and time for cabinet file entries.
+    pub fn cabinet_datetime(&self) -> time::PrimitiveDateTime {
+        let datetime = time::OffsetDateTime::from(self.timestamp);
+        time::PrimitiveDateTime::new(datetime.date(), datetime.time())
+    }
+}
+
+/// Parses the value of `SOURCE_DATE_EPOCH`.
+fn parse_source_date_epoch(value: &str) -> anyhow::Result<SystemTime> {
+    let seconds = value.trim().parse::<u64>().with_context(|| {
+        format!(
+            "{SOURCE_DATE_EPOCH_VARIABLE} [{value}] is not a number of seconds"
+        )
+    })?;
+    Ok(UNIX_EPOCH + Duration::from_secs(seconds))
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+    use std::time::UNIX_EPOCH;
+
+    use super::Reproducibility;
+
+    #[test]
+    fn guids_are_derived_from_seed() {
+        let timestamp = UNIX_EPOCH + Duration::from_secs(1_700_000_000);
+        let first = Reproducibility::new("seed", timestamp);
+        let second = Reproducibility::new("seed", timestamp);
+        let other = Reproducibility::new("other seed", timestamp);
+
+        assert_eq!(
+            first.guid("ProductCode", &["1.0.0"]),
+            second.guid("ProductCode", &["1.0.0"])
+        );
+        assert_ne!(
+            first.guid("ProductCode", &["1.0.0"]),
+            first.guid("PackageCode", &["1.0.0"])
+        );
+        assert_ne!(
+            first.guid("ProductCode", &["1.0.0"]),
+            first.guid("ProductCode", &["1.0.1"])
+        );
+        assert_ne!(
+            first.guid("ProductCode", &["1.0.0"]),
+            other.guid("ProductCode", &["1.0.0"])
+        );
+    }
+
+    #[test]
+    fn cabinet_datetime_matches_timestamp() {
+        // 2023-11-14 22:13:20 UTC
+        let timestamp = UNIX_EPOCH + Duration::from_secs(1_700_000_000);
+        let datetime = Reproducibility::new("", timestamp).cabinet_datetime();
+        assert_eq!(datetime.year(), 2023);
+        assert_eq!(datetime.hour(), 22);
+        assert_eq!(datetime.minute(), 13);
+    }
+}
@@ -11,7 +11,9 @@ use subenum::subenum;
 /// NOTE: Enum and docs generated by Gemini. Feel free to submit a pull request
 /// if any mistakes are seen.
 #[subenum(AdvtAction)]
-#[derive(Clone, Copy, Debug, Eq, PartialEq, strum::Display)]
+#[derive(
+    Clone, Copy, Debug, Eq, PartialEq, strum::Display, strum::EnumString,
+)]
 pub enum StandardAction {
     /// A top-level action used for an administrative installation.
     ///
@@ -31,6 +33,7 @@ pub enum StandardAction {
     BindImage = 4300,
     /// Uses file signatures to validate that qualifying products are installed
     /// on a system before an upgrade installation is performed.
+    #[strum(serialize = "CCPSearch")]
     CcpsSearch = 500,
     /// Ends the internal installation costing process begun by the
     /// CostInitialize action.
@@ -180,6 +183,7 @@ pub enum StandardAction {
     ResolveSource,
     /// Uses file signatures to validate that qualifying products are installed
     /// on a system before an upgrade installation is performed.
+    #[strum(serialize = "RMCCPSearch")]
     RmccpSearch = 600,
     /// Prompts the user for a system restart at the end of the installation.
     ///
@@ -231,3 +235,23 @@ pub enum StandardAction {
     /// Sets up registry information.
     WriteRegistryValues = 5000,
 }
+
+impl StandardAction {
+    /// The sequence number the action is usually given, or `None` for
+    /// actions that have to be placed relative to others.
+    pub fn suggested_sequence(&self) -> Option<i16> {
+        match self {
+            StandardAction::Admin
+            | StandardAction::Advertise
+            | StandardAction::DisableRollback
+            | StandardAction::ForceReboot
+            | StandardAction::Install
+            | StandardAction::InstallSfpCatalogFile
+            | StandardAction::RemoveExistingProducts
+            | StandardAction::ResolveSource
+            | StandardAction::ScheduleReboot
+            | StandardAction::Sequence => None,
+            action => Some(*action as i16),
+        }
+    }
+}
@@ -2,7 +2,12 @@ extern crate proc_macro;
 
 use proc_macro::TokenStream;
 use quote::quote;
-use syn::{DeriveInput, parse_macro_input};
+use syn::parse::{Parse, ParseStream};
+use syn::punctuated::Punctuated;
+use syn::{
+    Attribute, Data, DataStruct, DeriveInput, Expr, ExprLit, Field, Fields,
+    GenericArgument, Ident, Lit, PathArguments, Token, Type, parse_macro_input,
+};
 
 #[proc_macro_derive(IntoStrMsiValue)]
 pub fn msi_value_convert_derive(input: TokenStream) -> TokenStream {
@@ -26,3 +31,281 @@ pub fn msi_value_convert_derive(input: TokenStream) -> TokenStream {
     // Hand back the generated code
     TokenStream::from(expanded)
 }
+
+/// Derives `whimsi_lib::tables::msi_row::MsiRow` for a struct whose fields
+/// map one-to-one, in order, onto the columns of an MSI table.
+///
+/// The table name defaults to the struct name with any `Dao` suffix removed
+/// and can be overridden with `#[msi(table = "Name")]` on the struct. Each
+/// field becomes a column named after the field in CamelCase unless
+/// `#[msi(column = "Name_")]` is given. Other field options are:
+///
+/// - `primary_key`, `localizable`, `nullable` (implied by `Option<T>` fields)
+/// - `category = "Identifier"`, naming a `whimsi_msi::Category` variant
+/// - `max_len = EXPR` for string columns (defaults to 0, meaning unbounded)
+/// - `min = EXPR, max = EXPR` for integer value ranges
+/// - `int16`, `int32` or `binary` to force the column type; otherwise `i16`
+///   and `i32` fields become integer columns and everything else a string
+#[proc_macro_derive(MsiRow, attributes(msi))]
+pub fn msi_row_derive(input: TokenStream) -> TokenStream {
+    let ast = parse_macro_input!(input as DeriveInput);
+    match expand_msi_row(&ast) {
+        Ok(expanded) => TokenStream::from(expanded),
+        Err(error) => TokenStream::from(error.to_compile_error()),
+    }
+}
+
+/// A single `key` or `key = value` entry inside an `#[msi(...)]` attribute.
+struct MsiArg {
+    key: Ident,
+    value: Option<Expr>,
+}
+
+impl Parse for MsiArg {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let key: Ident = input.parse()?;
+        let value = if input.peek(Token![=]) {
+            input.parse::<Token![=]>()?;
+            Some(input.parse()?)
+        } else {
+            None
+        };
+        Ok(MsiArg { key, value })
+    }
+}
+
+#[derive(Default)]
+struct ColumnOptions {
+    column: Option<String>,
+    primary_key: bool,
+    localizable: bool,
+    nullable: bool,
+    category: Option<Ident>,
+    max_len: Option<Expr>,
+    min: Option<Expr>,
+    max: Option<Expr>,
+    coltype: Option<Ident>,
+}
+
+fn msi_args(attrs: &[Attribute]) -> syn::Result<Vec<MsiArg>> {
+    let mut args = Vec::new();
+    for attr in attrs.iter().filter(|attr| attr.path.is_ident("msi")) {
+        let parsed = attr.parse_args_with(
+            Punctuated::<MsiArg, Token![,]>::parse_terminated,
+        )?;
+        args.extend(parsed);
+    }
+    Ok(args)
+}
+
+fn string_value(arg: &MsiArg) -> syn::Result<String> {
+    match &arg.value {
+        Some(Expr::Lit(ExprLit { lit: Lit::Str(lit), .. })) => Ok(lit.value()),
+        _ => Err(syn::Error::new(
+            arg.key.span(),
+            format!("`{}` expects a string literal", arg.key),
+        )),
+    }
+}
+
+fn expr_value(arg: &MsiArg) -> syn::Result<Expr> {
+    arg.value.clone().ok_or_else(|| {
+        syn::Error::new(
+            arg.key.span(),
+            format!("`{}` expects a value", arg.key),
+        )
+    })
+}
+
+fn column_options(field: &Field) -> syn::Result<ColumnOptions> {
+    let mut options = ColumnOptions::default();
+    for arg in msi_args(&field.attrs)? {
+        match arg.key.to_string().as_str() {
+            "column" => options.column = Some(string_value(&arg)?),
+            "primary_key" => options.primary_key = true,
+            "localizable" => options.localizable = true,
+            "nullable" => options.nullable = true,
+            "category" => {
+                options.category =
+                    Some(Ident::new(&string_value(&arg)?, arg.key.span()))
+            }
+            "max_len" => options.max_len = Some(expr_value(&arg)?),
+            "min" => options.min = Some(expr_value(&arg)?),
+            "max" => options.max = Some(expr_value(&arg)?),
+            "int16" | "int32" | "binary" => options.coltype = Some(arg.key),
+            _ => {
+                return Err(syn::Error::new(
+                    arg.key.span(),
+                    format!("Unknown msi column option `{}`", arg.key),
+                ));
+            }
+        }
+    }
+    Ok(options)
+}
+
+/// Returns the `T` in `Option<T>`, or `None` if the type is not an `Option`.
+fn option_inner(ty: &Type) -> Option<&Type> {
+    let Type::Path(path) = ty else { return None };
+    let segment = path.path.segments.last()?;
+    if segment.ident != "Option" {
+        return None;
+    }
+    let PathArguments::AngleBracketed(args) = &segment.arguments else {
+        return None;
+    };
+    match args.args.first()? {
+        GenericArgument::Type(inner) => Some(inner),
+        _ => None,
+    }
+}
+
+fn is_primitive(ty: &Type, name: &str) -> bool {
+    match ty {
+        Type::Path(path) => path.path.is_ident(name),
+        _ => false,
+    }
+}
+
+fn camel_case(name: &str) -> String {
+    name.split('_')
+        .filter(|part| !part.is_empty())
+        .map(|part| {
+            let mut chars = part.chars();
+            match chars.next() {
+                Some(first) => first.to_uppercase().chain(chars).collect(),
+                None => String::new(),
+            }
+        })
+        .collect()
+}
+
+fn expand_msi_row(ast: &DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
+    let name = &ast.ident;
+    let fields = match &ast.data {
+        Data::Struct(DataStruct { fields: Fields::Named(fields), .. }) => {
+            &fields.named
+        }
+        _ => {
+            return Err(syn::Error::new(
+                name.span(),
+                "MsiRow can only be derived for structs with named fields",
+            ));
+        }
+    };
+
+    let mut table_name = name.to_string();
+    if let Some(stripped) = table_name.strip_suffix("Dao") {
+        table_name = stripped.to_string();
+    }
+    for arg in msi_args(&ast.attrs)? {
+        match arg.key.to_string().as_str() {
+            "table" => table_name = string_value(&arg)?,
+            _ => {
+                return Err(syn::Error::new(
+                    arg.key.span(),
+                    format!("Unknown msi table option `{}`", arg.key),
+                ));
+            }
+        }
+    }
+
+    let mut columns = Vec::new();
+    let mut values = Vec::new();
+    let mut reads = Vec::new();
+    for field in fields {
+        let ident = field.ident.as_ref().unwrap();
+        let options = column_options(field)?;
+        let column_name = options
+            .column
+            .clone()
+            .unwrap_or_else(|| camel_case(&ident.to_string()));
+        let inner = option_inner(&field.ty);
+
+        let mut builder = quote! { whimsi_msi::Column::build(#column_name) };
+        if options.primary_key {
+            builder = quote! { #builder.primary_key() };
+        }
+        if options.localizable {
+            builder = quote! { #builder.localizable() };
+        }
+        if options.nullable || inner.is_some() {
+            builder = quote! { #builder.nullable() };
+        }
+        match (&options.min, &options.max) {
+            (Some(min), Some(max)) => {
+                builder = quote! { #builder.range(#min, #max) };
+            }
+            (None, None) => {}
+            _ => {
+                return Err(syn::Error::new(
+                    ident.span(),
+                    "`min` and `max` must be given together",
+                ));
+            }
+        }
+        if let Some(category) = &options.category {
+            builder =
+                quote! { #builder.category(whimsi_msi::Category::#category) };
+        }
+        let value_type = inner.unwrap_or(&field.ty);
+        let coltype = match &options.coltype {
+            Some(coltype) => coltype.to_string(),
+            None if is_primitive(value_type, "i16") => "int16".to_string(),
+            None if is_primitive(value_type, "i32") => "int32".to_string(),
+            None => "string".to_string(),
+        };
+        builder = match coltype.as_str() {
+            "int16" => quote! { #builder.int16() },
+            "int32" => quote! { #builder.int32() },
+            "binary" => quote! { #builder.binary() },
+            _ => {
+                let max_len = options
+                    .max_len
+                    .clone()
+                    .map(|max_len| quote! { #max_len })
+                    .unwrap_or_else(|| quote! { 0 });
+                quote! { #builder.string(#max_len) }
+            }
+        };
+        columns.push(builder);
+
+        values.push(if inner.is_some() {
+            quote! {
+                ::whimsi_lib::types::helpers::to_msi_value::ToMsiOptionalValue::to_optional_value(&self.#ident)
+            }
+        } else {
+            quote! {
+                ::whimsi_lib::types::helpers::to_msi_value::ToMsiValue::to_msi_value(&self.#ident)
+            }
+        });
+        let read = if inner.is_some() {
+            quote! { read_optional_column }
+        } else {
+            quote! { read_column }
+        };
+        reads.push(quote! {
+            #ident: ::whimsi_lib::tables::msi_row::#read(row, #table_name, #column_name)?
+        });
+    }
+
+    let (impl_generics, ty_generics, where_clause) =
+        ast.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics ::whimsi_lib::tables::msi_row::MsiRow for #name #ty_generics #where_clause {
+            const TABLE_NAME: &'static str = #table_name;
+
+            fn columns() -> Vec<whimsi_msi::Column> {
+                vec![#(#columns),*]
+            }
+
+            fn to_row(&self) -> Vec<whimsi_msi::Value> {
+                vec![#(#values),*]
+            }
+
+            fn from_row(row: &whimsi_msi::Row) -> anyhow::Result<Self> {
+                Ok(Self { #(#reads),* })
+            }
+        }
+    })
+}
@@ -6,7 +6,7 @@ use std::{
     collections::{BTreeMap, HashMap, HashSet},
     io::{Read, Seek},
     path::PathBuf,
-    rc::Rc,
+    sync::Arc,
 };
 use whimsi_msi::{
     Column, PackageType, Rows, SummaryInfo, Table,
@@ -167,10 +167,10 @@ fn get_string_pool<F: Read + Seek>(comp: &mut CompoundFile<F>) -> StringPool {
 fn get_table_info<F: Read + Seek>(
     string_pool: &StringPool,
     comp: &mut CompoundFile<F>,
-) -> (HashSet<String>, BTreeMap<String, Rc<Table>>) {
-    let mut all_tables = BTreeMap::<String, Rc<Table>>::new();
+) -> (HashSet<String>, BTreeMap<String, Arc<Table>>) {
+    let mut all_tables = BTreeMap::<String, Arc<Table>>::new();
     const TABLES_TABLE_NAME: &str = "_Tables";
-    fn make_tables_table(long_string_refs: bool) -> Rc<Table> {
+    fn make_tables_table(long_string_refs: bool) -> Arc<Table> {
         Table::new(
             TABLES_TABLE_NAME.to_string(),
             vec![Column::build("Name").primary_key().string(64)],
@@ -186,7 +186,7 @@ fn get_table_info<F: Read + Seek>(
         let rows = Rows::new(
             string_pool,
             table.clone(),
-            table.read_rows(stream).unwrap(),
+            table.read_rows(stream, string_pool).unwrap(),
         );
         for row in rows {
             let table_name = row[0].as_str().unwrap().to_string();
@@ -208,7 +208,7 @@ fn get_column_info<F: Read + Seek>(
     table_names: &HashSet<String>,
     string_pool: &StringPool,
     comp: &mut CompoundFile<F>,
-    all_tables: &mut BTreeMap<String, Rc<Table>>,
+    all_tables: &mut BTreeMap<String, Arc<Table>>,
 ) -> HashMap<String, BTreeMap<i32, (String, i32)>> {
     // Read in _Columns table:
     let mut columns_map: HashMap<String, BTreeMap<i32, (String, i32)>> =
@@ -218,7 +218,7 @@ fn get_column_info<F: Read + Seek>(
             .collect();
     {
         const COLUMNS_TABLE_NAME: &str = "_Columns";
-        fn make_columns_table(long_string_refs: bool) -> Rc<Table> {
+        fn make_columns_table(long_string_refs: bool) -> Arc<Table> {
             Table::new(
                 COLUMNS_TABLE_NAME.to_string(),
                 vec![
@@ -237,7 +237,7 @@ fn get_column_info<F: Read + Seek>(
             let rows = Rows::new(
                 string_pool,
                 table.clone(),
-                table.read_rows(stream).unwrap(),
+                table.read_rows(stream, string_pool).unwrap(),
             );
             for row in rows {
                 let table_name = row[0].as_str().unwrap();
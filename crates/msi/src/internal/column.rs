@@ -178,6 +178,7 @@ pub struct Column {
     foreign_key: Option<(String, i32)>,
     category: Option<Category>,
     enum_values: Vec<String>,
+    is_temporary: bool,
 }
 
 impl Column {
@@ -209,6 +210,7 @@ impl Column {
                 foreign_key: self.foreign_key.clone(),
                 category: self.category,
                 enum_values: self.enum_values.clone(),
+                is_temporary: self.is_temporary,
             }
         }
     }
@@ -276,6 +278,13 @@ impl Column {
         self.is_primary_key
     }
 
+    /// Returns true if this column is temporary, meaning that its values are
+    /// only held in memory and are never written to the package file.
+    #[must_use]
+    pub fn is_temporary(&self) -> bool {
+        self.is_temporary
+    }
+
     /// Returns the (min, max) integer value range for this column, if any.
     #[must_use]
     pub fn value_range(&self) -> Option<(i32, i32)> {
@@ -316,7 +325,7 @@ impl Column {
                         number > (i16::MIN as i32)
                             && number <= (i16::MAX as i32)
                     }
-                    ColumnType::Int32 => number > i32::MIN && number < i32::MAX,
+                    ColumnType::Int32 => number > i32::MIN,
                     ColumnType::Str(_) => false,
                 }
             }
@@ -328,9 +337,11 @@ impl Column {
                     {
                         return false;
                     }
-                    if !self.enum_values.is_empty() && !self.enum_values.contains(string) {
-                            return false;
-                        }
+                    if !self.enum_values.is_empty()
+                        && !self.enum_values.contains(string)
+                    {
+                        return false;
+                    }
                     max_len == 0 || string.chars().count() <= max_len
                 }
             },
@@ -350,6 +361,7 @@ pub struct ColumnBuilder {
     foreign_key: Option<(String, i32)>,
     category: Option<Category>,
     enum_values: Vec<String>,
+    is_temporary: bool,
 }
 
 impl ColumnBuilder {
@@ -363,6 +375,7 @@ impl ColumnBuilder {
             foreign_key: None,
             category: None,
             enum_values: Vec::new(),
+            is_temporary: false,
         }
     }
 
@@ -387,6 +400,16 @@ impl ColumnBuilder {
         self
     }
 
+    /// Makes the column be temporary.  Temporary columns (and tables made up
+    /// entirely of temporary columns) are kept in memory and are never written
+    /// to the package file; this is equivalent to the `TEMPORARY` keyword in
+    /// Windows Installer SQL.
+    #[must_use]
+    pub fn temporary(mut self) -> ColumnBuilder {
+        self.is_temporary = true;
+        self
+    }
+
     /// Makes the column only permit values in the given range.
     #[must_use]
     pub fn range(mut self, min: i32, max: i32) -> ColumnBuilder {
@@ -477,6 +500,7 @@ impl ColumnBuilder {
             foreign_key: self.foreign_key,
             category: self.category,
             enum_values: self.enum_values,
+            is_temporary: self.is_temporary,
         }
     }
 
@@ -492,6 +516,7 @@ impl ColumnBuilder {
             foreign_key: self.foreign_key,
             category: self.category,
             enum_values: self.enum_values,
+            is_temporary: self.is_temporary,
         })
     }
 }
@@ -640,6 +665,16 @@ mod tests {
         assert_eq!(&output as &[u8], b"\x01\x00\x00");
     }
 
+    #[test]
+    fn int32_column_accepts_i32_max() {
+        let column = Column::build("Bar").int32();
+        assert!(column.is_valid_value(&Value::Int(i32::MAX)));
+        assert!(!column.is_valid_value(&Value::Int(i32::MIN)));
+
+        let column = Column::build("Bar").nullable().int32();
+        assert!(column.is_valid_value(&Value::Int(i32::MAX)));
+    }
+
     #[test]
     fn valid_column_value() {
         let column = Column::build("Foo").nullable().int16();
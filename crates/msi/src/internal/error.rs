@@ -0,0 +1,243 @@
+use crate::internal::column::ColumnType;
+use crate::internal::value::Value;
+use std::error;
+use std::fmt;
+use std::io;
+
+// ========================================================================= //
+
+/// A specialized `Result` type for MSI package operations.
+pub type Result<T> = std::result::Result<T, MsiError>;
+
+// ========================================================================= //
+
+/// An error that occurred while reading or modifying an MSI package.
+///
+/// Errors that concern a particular table, column, key, or value carry those
+/// details so that callers can react to them without parsing messages.  Any
+/// `MsiError` can be converted into an `io::Error` (and back again, if the
+/// `io::Error` was created from an `MsiError`).
+#[derive(Debug)]
+pub enum MsiError {
+    /// An I/O error occurred in the underlying file.
+    Io(io::Error),
+    /// The named table does not exist in the package.
+    TableNotFound {
+        /// The name of the missing table.
+        table: String,
+    },
+    /// A table with this name already exists in the package.
+    TableAlreadyExists {
+        /// The name of the existing table.
+        table: String,
+    },
+    /// The table has no column with the given name.
+    ColumnNotFound {
+        /// The name of the table.
+        table: String,
+        /// The name of the missing column.
+        column: String,
+    },
+    /// The table already has a column with the given name.
+    ColumnAlreadyExists {
+        /// The name of the table.
+        table: String,
+        /// The name of the existing column.
+        column: String,
+    },
+    /// The table already contains a row with the given primary key.
+    DuplicateKey {
+        /// The name of the table.
+        table: String,
+        /// The values of the primary key columns.
+        key: Vec<Value>,
+    },
+    /// A value is not permitted by the type, range, category, or set of
+    /// allowed values of the column it was to be stored in.
+    InvalidValue {
+        /// The name of the table.
+        table: String,
+        /// The name of the column.
+        column: String,
+        /// The type of the column.
+        column_type: ColumnType,
+        /// The rejected value.
+        value: Value,
+    },
+    /// The named stream does not exist in the package.
+    StreamNotFound {
+        /// The name of the missing stream.
+        stream: String,
+    },
+    /// An argument was invalid for some other reason.
+    InvalidInput(String),
+    /// The package contains malformed data.
+    InvalidData(String),
+}
+
+impl MsiError {
+    /// Returns the `io::ErrorKind` that corresponds to this error.
+    #[must_use]
+    pub fn kind(&self) -> io::ErrorKind {
+        match *self {
+            MsiError::Io(ref error) => error.kind(),
+            MsiError::TableNotFound { .. }
+            | MsiError::StreamNotFound { .. } => io::ErrorKind::NotFound,
+            MsiError::TableAlreadyExists { .. }
+            | MsiError::ColumnAlreadyExists { .. }
+            | MsiError::DuplicateKey { .. } => io::ErrorKind::AlreadyExists,
+            MsiError::ColumnNotFound { .. }
+            | MsiError::InvalidValue { .. }
+            | MsiError::InvalidInput(_) => io::ErrorKind::InvalidInput,
+            MsiError::InvalidData(_) => io::ErrorKind::InvalidData,
+        }
+    }
+
+    /// Returns the name of the table involved in this error, if any.
+    #[must_use]
+    pub fn table(&self) -> Option<&str> {
+        match *self {
+            MsiError::TableNotFound { ref table }
+            | MsiError::TableAlreadyExists { ref table }
+            | MsiError::ColumnNotFound { ref table, .. }
+            | MsiError::ColumnAlreadyExists { ref table, .. }
+            | MsiError::DuplicateKey { ref table, .. }
+            | MsiError::InvalidValue { ref table, .. } => Some(table),
+            _ => None,
+        }
+    }
+
+    /// Returns the name of the column involved in this error, if any.
+    #[must_use]
+    pub fn column(&self) -> Option<&str> {
+        match *self {
+            MsiError::ColumnNotFound { ref column, .. }
+            | MsiError::ColumnAlreadyExists { ref column, .. }
+            | MsiError::InvalidValue { ref column, .. } => Some(column),
+            _ => None,
+        }
+    }
+
+    /// Returns the `MsiError` wrapped inside an `io::Error`, if there is one.
+    #[must_use]
+    pub fn from_io_error(error: &io::Error) -> Option<&MsiError> {
+        error.get_ref().and_then(|inner| inner.downcast_ref::<MsiError>())
+    }
+}
+
+impl fmt::Display for MsiError {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            MsiError::Io(ref error) => error.fmt(formatter),
+            MsiError::TableNotFound { ref table } => {
+                write!(formatter, "Table {table:?} does not exist")
+            }
+            MsiError::TableAlreadyExists { ref table } => {
+                write!(formatter, "Table {table:?} already exists")
+            }
+            MsiError::ColumnNotFound { ref table, ref column } => {
+                write!(
+                    formatter,
+                    "Table {table:?} has no column named {column:?}"
+                )
+            }
+            MsiError::ColumnAlreadyExists { ref table, ref column } => write!(
+                formatter,
+                "Table {table:?} already has a column named {column:?}"
+            ),
+            MsiError::DuplicateKey { ref table, ref key } => write!(
+                formatter,
+                "Table {table:?} already contains a row with key {key:?}"
+            ),
+            MsiError::InvalidValue {
+                ref table,
+                ref column,
+                column_type,
+                ref value,
+            } => write!(
+                formatter,
+                "{value} is not a valid value for column {column:?} of type \
+                 {column_type} in table {table:?}"
+            ),
+            MsiError::StreamNotFound { ref stream } => {
+                write!(formatter, "Stream {stream:?} does not exist")
+            }
+            MsiError::InvalidInput(ref message)
+            | MsiError::InvalidData(ref message) => message.fmt(formatter),
+        }
+    }
+}
+
+impl error::Error for MsiError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match *self {
+            MsiError::Io(ref error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for MsiError {
+    fn from(error: io::Error) -> MsiError {
+        if MsiError::from_io_error(&error).is_some() {
+            let inner = error.into_inner().unwrap();
+            return *inner.downcast::<MsiError>().unwrap();
+        }
+        MsiError::Io(error)
+    }
+}
+
+impl From<MsiError> for io::Error {
+    fn from(error: MsiError) -> io::Error {
+        match error {
+            MsiError::Io(error) => error,
+            error => io::Error::new(error.kind(), error),
+        }
+    }
+}
+
+// ========================================================================= //
+
+#[cfg(test)]
+mod tests {
+    use super::MsiError;
+    use crate::internal::value::Value;
+    use std::io;
+
+    #[test]
+    fn round_trip_through_io_error() {
+        let error = MsiError::DuplicateKey {
+            table: "Foo".to_string(),
+            key: vec![Value::Int(1)],
+        };
+        let io_error = io::Error::from(error);
+        assert_eq!(io_error.kind(), io::ErrorKind::AlreadyExists);
+        assert_eq!(
+            io_error.to_string(),
+            "Table \"Foo\" already contains a row with key [Int(1)]"
+        );
+        assert_eq!(
+            MsiError::from_io_error(&io_error).unwrap().table(),
+            Some("Foo")
+        );
+        match MsiError::from(io_error) {
+            MsiError::DuplicateKey { table, key } => {
+                assert_eq!(table, "Foo");
+                assert_eq!(key, vec![Value::Int(1)]);
+            }
+            other => panic!("Unexpected error: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn plain_io_error_is_wrapped() {
+        let io_error = io::Error::new(io::ErrorKind::UnexpectedEof, "eof");
+        let error = MsiError::from(io_error);
+        assert!(matches!(error, MsiError::Io(_)));
+        assert_eq!(error.kind(), io::ErrorKind::UnexpectedEof);
+        let io_error = io::Error::from(error);
+        assert!(MsiError::from_io_error(&io_error).is_none());
+    }
+}
+
+// ========================================================================= //
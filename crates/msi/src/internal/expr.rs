@@ -1,6 +1,6 @@
 use crate::internal::table::Row;
 use crate::internal::value::Value;
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::fmt;
 use std::ops;
 
@@ -158,6 +158,16 @@ impl Expr {
         self.ast.populate_column_names(&mut names);
         names
     }
+
+    /// Returns the columns that this expression pins to a single literal
+    /// value; that is, the `column = literal` comparisons that are joined by
+    /// `AND` at the top level of the expression.  Any row for which the
+    /// expression is true must have these values in these columns.
+    pub(crate) fn pinned_columns(&self) -> HashMap<&str, &Value> {
+        let mut pinned = HashMap::new();
+        self.ast.populate_pinned_columns(&mut pinned);
+        pinned
+    }
 }
 
 /// Produces an expression that evaluates to the negative of the subexpression.
@@ -333,6 +343,28 @@ impl Ast {
         }
     }
 
+    fn populate_pinned_columns<'a>(
+        &'a self,
+        pinned: &mut HashMap<&'a str, &'a Value>,
+    ) {
+        match *self {
+            Ast::BinOp(BinOp::Eq, ref arg1, ref arg2) => {
+                match (arg1.as_ref(), arg2.as_ref()) {
+                    (Ast::Column(name), Ast::Literal(value))
+                    | (Ast::Literal(value), Ast::Column(name)) => {
+                        pinned.entry(name.as_str()).or_insert(value);
+                    }
+                    _ => {}
+                }
+            }
+            Ast::And(ref arg1, ref arg2) => {
+                arg1.populate_pinned_columns(pinned);
+                arg2.populate_pinned_columns(pinned);
+            }
+            _ => {}
+        }
+    }
+
     fn format_with_precedence(
         &self,
         formatter: &mut fmt::Formatter,
@@ -616,6 +648,23 @@ mod tests {
         assert_eq!(expr.column_names(), expected);
     }
 
+    #[test]
+    fn pinned_columns() {
+        let expr = Expr::col("Foo")
+            .eq(Expr::string("foo"))
+            .and(Expr::integer(3).eq(Expr::col("Bar")))
+            .and(Expr::col("Baz").gt(Expr::integer(0)));
+        let pinned = expr.pinned_columns();
+        assert_eq!(pinned.len(), 2);
+        assert_eq!(pinned["Foo"], &Value::from("foo"));
+        assert_eq!(pinned["Bar"], &Value::Int(3));
+
+        let expr = Expr::col("Foo")
+            .eq(Expr::string("foo"))
+            .or(Expr::col("Bar").eq(Expr::integer(3)));
+        assert!(expr.pinned_columns().is_empty());
+    }
+
     #[test]
     fn display() {
         let expr = (Expr::col("Foo") / Expr::integer(10))
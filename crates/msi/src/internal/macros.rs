@@ -1,45 +1,24 @@
 // ========================================================================= //
 
-macro_rules! already_exists {
-    ($e:expr) => {
-        return Err(::std::io::Error::new(::std::io::ErrorKind::AlreadyExists,
-                                         $e))
-    };
-    ($fmt:expr, $($arg:tt)+) => {
-        return Err(::std::io::Error::new(::std::io::ErrorKind::AlreadyExists,
-                                         format!($fmt, $($arg)+)))
-    };
-}
-
 macro_rules! invalid_data {
     ($e:expr) => {
-        return Err(::std::io::Error::new(::std::io::ErrorKind::InvalidData,
-                                         $e))
+        return Err($crate::internal::error::MsiError::InvalidData(($e).into())
+                   .into())
     };
     ($fmt:expr, $($arg:tt)+) => {
-        return Err(::std::io::Error::new(::std::io::ErrorKind::InvalidData,
-                                         format!($fmt, $($arg)+)))
+        return Err($crate::internal::error::MsiError::InvalidData(
+            format!($fmt, $($arg)+)).into())
     };
 }
 
 macro_rules! invalid_input {
     ($e:expr) => {
-        return Err(::std::io::Error::new(::std::io::ErrorKind::InvalidInput,
-                                         $e))
-    };
-    ($fmt:expr, $($arg:tt)+) => {
-        return Err(::std::io::Error::new(::std::io::ErrorKind::InvalidInput,
-                                         format!($fmt, $($arg)+)))
-    };
-}
-
-macro_rules! not_found {
-    ($e:expr) => {
-        return Err(::std::io::Error::new(::std::io::ErrorKind::NotFound, $e))
+        return Err($crate::internal::error::MsiError::InvalidInput(($e).into())
+                   .into())
     };
     ($fmt:expr, $($arg:tt)+) => {
-        return Err(::std::io::Error::new(::std::io::ErrorKind::NotFound,
-                                         format!($fmt, $($arg)+)))
+        return Err($crate::internal::error::MsiError::InvalidInput(
+            format!($fmt, $($arg)+)).into())
     };
 }
 
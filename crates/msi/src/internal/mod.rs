@@ -4,6 +4,8 @@ mod macros;
 pub mod category;
 pub mod codepage;
 pub mod column;
+/// Structured errors returned by package operations.
+pub mod error;
 pub mod expr;
 pub mod language;
 pub mod package;
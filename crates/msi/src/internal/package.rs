@@ -1,6 +1,7 @@
 use crate::internal::category::Category;
 use crate::internal::codepage::CodePage;
 use crate::internal::column::Column;
+use crate::internal::error::{MsiError, Result};
 use crate::internal::expr::Expr;
 use crate::internal::query::{Delete, Insert, Select, Update};
 use crate::internal::stream::{StreamReader, StreamWriter, Streams};
@@ -10,13 +11,16 @@ use crate::internal::streamname::{
 };
 use crate::internal::stringpool::{StringPool, StringPoolBuilder};
 use crate::internal::summary::SummaryInfo;
-use crate::internal::table::{Rows, Table};
+use crate::internal::table::{
+    HeldTable, KeyIndex, Row, Rows, Table, load_key_index, load_rows,
+    store_rows,
+};
 use crate::internal::value::{Value, ValueRef};
 use cfb;
-use std::borrow::Borrow;
+use std::borrow::{Borrow, Cow};
 use std::collections::{BTreeMap, HashMap, HashSet, btree_map};
-use std::io::{self, Read, Seek, Write};
-use std::rc::Rc;
+use std::io::{Read, Seek, Write};
+use std::sync::Arc;
 use uuid::Uuid;
 
 // ========================================================================= //
@@ -36,7 +40,7 @@ const MAX_NUM_TABLE_COLUMNS: usize = 32;
 
 // ========================================================================= //
 
-fn make_columns_table(long_string_refs: bool) -> Rc<Table> {
+fn make_columns_table(long_string_refs: bool) -> Arc<Table> {
     Table::new(
         COLUMNS_TABLE_NAME.to_string(),
         vec![
@@ -49,7 +53,7 @@ fn make_columns_table(long_string_refs: bool) -> Rc<Table> {
     )
 }
 
-fn make_tables_table(long_string_refs: bool) -> Rc<Table> {
+fn make_tables_table(long_string_refs: bool) -> Arc<Table> {
     Table::new(
         TABLES_TABLE_NAME.to_string(),
         vec![Column::build("Name").primary_key().string(64)],
@@ -76,7 +80,7 @@ fn make_validation_columns() -> Vec<Column> {
     ]
 }
 
-fn make_validation_table(long_string_refs: bool) -> Rc<Table> {
+fn make_validation_table(long_string_refs: bool) -> Arc<Table> {
     Table::new(
         VALIDATION_TABLE_NAME.to_string(),
         make_validation_columns(),
@@ -84,6 +88,58 @@ fn make_validation_table(long_string_refs: bool) -> Rc<Table> {
     )
 }
 
+fn make_columns_row(
+    table_name: &str,
+    number: usize,
+    column: &Column,
+) -> Vec<Value> {
+    vec![
+        Value::Str(table_name.to_string()),
+        Value::Int(number as i32),
+        Value::Str(column.name().to_string()),
+        Value::Int(column.bitfield()),
+    ]
+}
+
+fn make_validation_row(table_name: &str, column: &Column) -> Vec<Value> {
+    let (min_value, max_value) = if let Some((min, max)) = column.value_range()
+    {
+        (Value::Int(min), Value::Int(max))
+    } else {
+        (Value::Null, Value::Null)
+    };
+    let (key_table, key_column) =
+        if let Some((table, column)) = column.foreign_key() {
+            (Value::Str(table.to_string()), Value::Int(column))
+        } else {
+            (Value::Null, Value::Null)
+        };
+    vec![
+        Value::Str(table_name.to_string()),
+        Value::Str(column.name().to_string()),
+        Value::Str(if column.is_nullable() {
+            "Y".to_string()
+        } else {
+            "N".to_string()
+        }),
+        min_value,
+        max_value,
+        key_table,
+        key_column,
+        if let Some(category) = column.category() {
+            Value::Str(category.to_string())
+        } else {
+            Value::Null
+        },
+        if let Some(values) = column.enum_values() {
+            Value::Str(values.join(";"))
+        } else {
+            Value::Null
+        },
+        Value::Null,
+    ]
+}
+
 fn is_reserved_table_name(table_name: &str) -> bool {
     table_name == COLUMNS_TABLE_NAME
         || table_name == TABLES_TABLE_NAME
@@ -193,8 +249,10 @@ pub struct Package<F> {
     summary_info: SummaryInfo,
     is_summary_info_modified: bool,
     string_pool: StringPool,
-    tables: BTreeMap<String, Rc<Table>>,
-    finisher: Option<Box<dyn Finish<F>>>,
+    tables: BTreeMap<String, Arc<Table>>,
+    held: BTreeMap<String, HeldTable>,
+    indices: BTreeMap<String, KeyIndex>,
+    finisher: Option<Box<dyn Finish<F> + Send + Sync>>,
 }
 
 impl<F> Package<F> {
@@ -204,6 +262,12 @@ impl<F> Package<F> {
         self.package_type
     }
 
+    /// Returns the version of the compound file format used by this package.
+    #[must_use]
+    pub fn cfb_version(&self) -> cfb::Version {
+        self.comp().version()
+    }
+
     /// Returns summary information for this package.
     #[must_use]
     pub fn summary_info(&self) -> &SummaryInfo {
@@ -224,7 +288,7 @@ impl<F> Package<F> {
 
     /// Returns the database table with the given name (if any).
     pub fn get_table(&self, table_name: &str) -> Option<&Table> {
-        self.tables.get(table_name).map(Rc::borrow)
+        self.tables.get(table_name).map(Arc::borrow)
     }
 
     /// Returns an iterator over the database tables in this package.
@@ -254,8 +318,18 @@ impl<F> Package<F> {
         self.comp().is_stream(DIGITAL_SIGNATURE_STREAM_NAME)
     }
 
+    /// Returns true if the table with the given name is currently held in
+    /// memory (see `hold_table`).  Temporary tables, and tables with temporary
+    /// columns, are always held.
+    #[must_use]
+    pub fn is_table_held(&self, table_name: &str) -> bool {
+        self.held.contains_key(table_name)
+    }
+
     /// Consumes the `Package` object, returning the underlying reader/writer.
-    pub fn into_inner(mut self) -> io::Result<F> {
+    /// Any temporary tables and columns are discarded.
+    pub fn into_inner(mut self) -> Result<F> {
+        self.release_temporary_data();
         if let Some(finisher) = self.finisher.take() {
             finisher.finish(&mut self)?;
         }
@@ -269,13 +343,41 @@ impl<F> Package<F> {
     fn comp_mut(&mut self) -> &mut cfb::CompoundFile<F> {
         self.comp.as_mut().unwrap()
     }
+
+    /// Discards all temporary tables and the values in all temporary columns,
+    /// releasing their strings from the string pool.  Held persistent tables
+    /// stay held, so that the finisher will write them out.
+    fn release_temporary_data(&mut self) {
+        self.indices.clear();
+        let held_names: Vec<String> = self.held.keys().cloned().collect();
+        for table_name in held_names {
+            let table = self.tables[&table_name].clone();
+            if table.is_temporary() {
+                let held_table = self.held.remove(&table_name).unwrap();
+                for value_ref in held_table.rows.into_iter().flatten() {
+                    value_ref.remove(&mut self.string_pool);
+                }
+                self.tables.remove(&table_name);
+            } else {
+                let num_columns = table.num_persistent_columns();
+                let held_table = self.held.get_mut(&table_name).unwrap();
+                for row in &mut held_table.rows {
+                    for value_ref in row.drain(num_columns..) {
+                        value_ref.remove(&mut self.string_pool);
+                    }
+                }
+                self.tables
+                    .insert(table_name, table.without_temporary_columns());
+            }
+        }
+    }
 }
 
 impl<F: Read + Seek> Package<F> {
     /// Opens an existing MSI file, using the underlying reader.  If the
     /// underlying reader also supports the `Write` trait, then the `Package`
     /// object will be writable as well.
-    pub fn open(inner: F) -> io::Result<Package<F>> {
+    pub fn open(inner: F) -> Result<Package<F>> {
         let mut comp = cfb::CompoundFile::open_strict(inner)?;
         let package_type = {
             let root_entry = comp.root_entry();
@@ -300,7 +402,7 @@ impl<F: Read + Seek> Package<F> {
             let stream = comp.open_stream(name)?;
             builder.build_from_data(stream)?
         };
-        let mut all_tables = BTreeMap::<String, Rc<Table>>::new();
+        let mut all_tables = BTreeMap::<String, Arc<Table>>::new();
         // Read in _Tables table:
         let table_names: HashSet<String> = {
             let table = make_tables_table(string_pool.long_string_refs());
@@ -311,10 +413,16 @@ impl<F: Read + Seek> Package<F> {
                 let rows = Rows::new(
                     &string_pool,
                     table.clone(),
-                    table.read_rows(stream)?,
+                    table.read_rows(stream, &string_pool)?,
                 );
                 for row in rows {
-                    let table_name = row[0].as_str().unwrap().to_string();
+                    let Some(table_name) = row[0].as_str() else {
+                        invalid_data!(
+                            "Null table name in {:?} table",
+                            TABLES_TABLE_NAME
+                        );
+                    };
+                    let table_name = table_name.to_string();
                     if names.contains(&table_name) {
                         invalid_data!(
                             "Repeated key in {:?} table: {:?}",
@@ -342,12 +450,18 @@ impl<F: Read + Seek> Package<F> {
                 let rows = Rows::new(
                     &string_pool,
                     table.clone(),
-                    table.read_rows(stream)?,
+                    table.read_rows(stream, &string_pool)?,
                 );
                 for row in rows {
-                    let table_name = row[0].as_str().unwrap();
+                    let (Some(table_name), Some(col_index), Some(col_name)) =
+                        (row[0].as_str(), row[1].as_int(), row[2].as_str())
+                    else {
+                        invalid_data!(
+                            "Null key or name in {:?} table",
+                            COLUMNS_TABLE_NAME
+                        );
+                    };
                     if let Some(cols) = columns_map.get_mut(table_name) {
-                        let col_index = row[1].as_int().unwrap();
                         if cols.contains_key(&col_index) {
                             invalid_data!(
                                 "Repeated key in {:?} table: {:?}",
@@ -355,9 +469,16 @@ impl<F: Read + Seek> Package<F> {
                                 (table_name, col_index)
                             );
                         }
-                        let col_name = row[2].as_str().unwrap().to_string();
-                        let type_bits = row[3].as_int().unwrap();
-                        cols.insert(col_index, (col_name, type_bits));
+                        let Some(type_bits) = row[3].as_int() else {
+                            invalid_data!(
+                                "Null column type in {:?} table",
+                                COLUMNS_TABLE_NAME
+                            );
+                        };
+                        cols.insert(
+                            col_index,
+                            (col_name.to_string(), type_bits),
+                        );
                     } else {
                         invalid_data!(
                             "_Columns mentions table {:?}, which isn't in \
@@ -379,18 +500,18 @@ impl<F: Read + Seek> Package<F> {
             let stream_name = table.stream_name();
             if comp.exists(&stream_name) {
                 let stream = comp.open_stream(&stream_name)?;
-                for value_refs in table.read_rows(stream)? {
-                    let table_name = value_refs[0]
-                        .to_value(&string_pool)
-                        .as_str()
-                        .unwrap()
-                        .to_string();
-                    let column_name = value_refs[1]
-                        .to_value(&string_pool)
-                        .as_str()
-                        .unwrap()
-                        .to_string();
-                    let key = (table_name, column_name);
+                for value_refs in table.read_rows(stream, &string_pool)? {
+                    let table_name = value_refs[0].to_value(&string_pool);
+                    let column_name = value_refs[1].to_value(&string_pool);
+                    let (Some(table_name), Some(column_name)) =
+                        (table_name.as_str(), column_name.as_str())
+                    else {
+                        invalid_data!(
+                            "Null key in {:?} table",
+                            VALIDATION_TABLE_NAME
+                        );
+                    };
+                    let key = (table_name.to_string(), column_name.to_string());
                     if validation_map.contains_key(&key) {
                         invalid_data!(
                             "Repeated key in {:?} table: {:?}",
@@ -422,39 +543,34 @@ impl<F: Read + Seek> Package<F> {
                 let key = (table_name.clone(), column_name);
                 if let Some(value_refs) = validation_map.get(&key) {
                     let is_nullable = value_refs[2].to_value(&string_pool);
-                    if is_nullable.as_str().unwrap() == "Y" {
+                    if is_nullable.as_str() == Some("Y") {
                         builder = builder.nullable();
                     }
                     let min_value = value_refs[3].to_value(&string_pool);
                     let max_value = value_refs[4].to_value(&string_pool);
-                    if !min_value.is_null() && !max_value.is_null() {
-                        let min = min_value.as_int().unwrap();
-                        let max = max_value.as_int().unwrap();
+                    if let (Some(min), Some(max)) =
+                        (min_value.as_int(), max_value.as_int())
+                    {
                         builder = builder.range(min, max);
                     }
                     let key_table = value_refs[5].to_value(&string_pool);
                     let key_column = value_refs[6].to_value(&string_pool);
-                    if !key_table.is_null() && !key_column.is_null() {
-                        builder = builder.foreign_key(
-                            key_table.as_str().unwrap(),
-                            key_column.as_int().unwrap(),
-                        );
+                    if let (Some(key_table), Some(key_column)) =
+                        (key_table.as_str(), key_column.as_int())
+                    {
+                        builder = builder.foreign_key(key_table, key_column);
                     }
                     let category_value = value_refs[7].to_value(&string_pool);
-                    if !category_value.is_null() {
-                        let category = category_value
-                            .as_str()
-                            .unwrap()
-                            .parse::<Category>()
-                            .ok();
-                        if let Some(category) = category {
-                            builder = builder.category(category);
-                        }
+                    let category = category_value
+                        .as_str()
+                        .and_then(|category| category.parse::<Category>().ok());
+                    if let Some(category) = category {
+                        builder = builder.category(category);
                     }
                     let enum_values = value_refs[8].to_value(&string_pool);
-                    if !enum_values.is_null() {
+                    if let Some(enum_values) = enum_values.as_str() {
                         let enum_values: Vec<&str> =
-                            enum_values.as_str().unwrap().split(';').collect();
+                            enum_values.split(';').collect();
                         builder = builder.enum_values(&enum_values);
                     }
                 }
@@ -471,6 +587,8 @@ impl<F: Read + Seek> Package<F> {
             is_summary_info_modified: false,
             string_pool,
             tables: all_tables,
+            held: BTreeMap::new(),
+            indices: BTreeMap::new(),
             finisher: None,
         })
     }
@@ -478,43 +596,111 @@ impl<F: Read + Seek> Package<F> {
     /// Attempts to execute a select query.  Returns an error if the query
     /// fails (e.g. due to the column names being incorrect or the table(s) not
     /// existing).
-    pub fn select_rows(&mut self, query: Select) -> io::Result<Rows<'_>> {
-        query.exec(self.comp.as_mut().unwrap(), &self.string_pool, &self.tables)
+    pub fn select_rows(&mut self, query: Select) -> Result<Rows<'_>> {
+        query.exec(
+            self.comp.as_mut().unwrap(),
+            &self.string_pool,
+            &self.tables,
+            &self.held,
+            &mut self.indices,
+        )
+    }
+
+    /// Returns the row of the given table whose primary key columns hold the
+    /// given values (in column order), or `None` if there is no such row.
+    /// Returns an error if the table doesn't exist, or if the number of values
+    /// doesn't match the number of primary key columns.
+    ///
+    /// Lookups go through an index of the table's primary keys, which is
+    /// built on first use and kept until the table is next modified, so that
+    /// resolving many keys in the same table doesn't rescan it each time.
+    pub fn get_row(
+        &mut self,
+        table_name: &str,
+        key: &[Value],
+    ) -> Result<Option<Row>> {
+        let table = match self.tables.get(table_name) {
+            Some(table) => table.clone(),
+            None => {
+                return Err(MsiError::TableNotFound {
+                    table: table_name.to_string(),
+                });
+            }
+        };
+        let num_keys = table.primary_key_indices().len();
+        if key.len() != num_keys {
+            invalid_input!(
+                "Table {:?} has {} primary key column(s), but {} key value(s) \
+                 were given",
+                table_name,
+                num_keys,
+                key.len()
+            );
+        }
+        let index = load_key_index(
+            self.comp.as_mut().unwrap(),
+            &self.string_pool,
+            &self.held,
+            &mut self.indices,
+            &table,
+        )?;
+        Ok(index.get(key).into_iter().next().map(|value_refs| {
+            let values = value_refs
+                .into_iter()
+                .map(|value_ref| value_ref.to_value(&self.string_pool))
+                .collect();
+            Row::new(table, values)
+        }))
     }
 
     /// Opens an existing binary stream in the package for reading.
     pub fn read_stream(
         &mut self,
         stream_name: &str,
-    ) -> io::Result<StreamReader<F>> {
+    ) -> Result<StreamReader<F>> {
         if !streamname::is_valid(stream_name, false) {
             invalid_input!("{:?} is not a valid stream name", stream_name);
         }
         let encoded_name = streamname::encode(stream_name, false);
         if !self.comp().is_stream(&encoded_name) {
-            not_found!("Stream {:?} does not exist", stream_name);
+            return Err(MsiError::StreamNotFound {
+                stream: stream_name.to_string(),
+            });
         }
         Ok(StreamReader::new(self.comp_mut().open_stream(&encoded_name)?))
     }
 
-    // TODO: pub fn has_valid_digital_signature(&mut self) -> io::Result<bool>
+    // TODO: pub fn has_valid_digital_signature(&mut self) -> Result<bool>
 }
 
 impl<F: Read + Write + Seek> Package<F> {
     /// Creates a new, empty package of the given type, using the underlying
     /// reader/writer.  The reader/writer should be initially empty.
-    pub fn create(
+    ///
+    /// The package uses version 3 of the compound file format (with 512-byte
+    /// sectors), which is what Windows Installer itself produces.  Use
+    /// `create_with_version` for packages larger than about 2 GB.
+    pub fn create(package_type: PackageType, inner: F) -> Result<Package<F>> {
+        Package::create_with_version(package_type, cfb::Version::V3, inner)
+    }
+
+    /// Creates a new, empty package of the given type, using the given
+    /// version of the compound file format.  Version 4 uses 4096-byte
+    /// sectors, which allows packages (and the streams within them, such as
+    /// embedded cabinets) to grow well beyond the roughly 2 GB limit of
+    /// version 3.
+    pub fn create_with_version(
         package_type: PackageType,
+        version: cfb::Version,
         inner: F,
-    ) -> io::Result<Package<F>> {
-        let mut comp =
-            cfb::CompoundFile::create_with_version(cfb::Version::V3, inner)?;
+    ) -> Result<Package<F>> {
+        let mut comp = cfb::CompoundFile::create_with_version(version, inner)?;
         comp.set_storage_clsid("/", package_type.clsid())?;
         let mut summary_info = SummaryInfo::new();
         summary_info.set_title(package_type.default_title().to_string());
         let string_pool = StringPool::new(summary_info.codepage());
         let tables = {
-            let mut tables = BTreeMap::<String, Rc<Table>>::new();
+            let mut tables = BTreeMap::<String, Arc<Table>>::new();
             let table = make_tables_table(string_pool.long_string_refs());
             tables.insert(table.name().to_string(), table);
             let table = make_columns_table(string_pool.long_string_refs());
@@ -528,6 +714,8 @@ impl<F: Read + Write + Seek> Package<F> {
             is_summary_info_modified: true,
             string_pool,
             tables,
+            held: BTreeMap::new(),
+            indices: BTreeMap::new(),
             finisher: None,
         };
         package
@@ -556,11 +744,16 @@ impl<F: Read + Write + Seek> Package<F> {
     /// Creates a new database table.  Returns an error without modifying the
     /// database if the table name or columns are invalid, or if a table with
     /// that name already exists.
+    ///
+    /// If every column is temporary (see `ColumnBuilder::temporary`), the
+    /// table is a temporary table: it is held in memory and never written to
+    /// the package file.  It is discarded when freed (see `free_table`) or
+    /// when the package is closed.
     pub fn create_table<S: Into<String>>(
         &mut self,
         table_name: S,
         columns: Vec<Column>,
-    ) -> io::Result<()> {
+    ) -> Result<()> {
         self.create_table_with_name(table_name.into(), columns)
     }
 
@@ -568,7 +761,7 @@ impl<F: Read + Write + Seek> Package<F> {
         &mut self,
         table_name: String,
         columns: Vec<Column>,
-    ) -> io::Result<()> {
+    ) -> Result<()> {
         if !Table::is_valid_name(&table_name) {
             invalid_input!("{:?} is not a valid table name", table_name);
         }
@@ -603,82 +796,283 @@ impl<F: Read + Write + Seek> Package<F> {
                 column_names.insert(name);
             }
         }
+        let is_temporary = columns.iter().all(Column::is_temporary);
+        if !is_temporary {
+            if columns
+                .iter()
+                .skip_while(|column| !column.is_temporary())
+                .any(|column| !column.is_temporary())
+            {
+                invalid_input!(
+                    "Temporary columns must come after all persistent columns"
+                );
+            }
+            if let Some(column) = columns
+                .iter()
+                .find(|column| column.is_temporary() && column.is_primary_key())
+            {
+                invalid_input!(
+                    "Cannot use temporary column {:?} as a primary key of a \
+                     persistent table",
+                    column.name()
+                );
+            }
+        }
         if self.tables.contains_key(&table_name) {
-            already_exists!("Table {:?} already exists", table_name);
-        }
-        self.insert_rows(
-            Insert::into(COLUMNS_TABLE_NAME).rows(
-                columns
-                    .iter()
-                    .enumerate()
-                    .map(|(index, column)| {
-                        vec![
-                            Value::Str(table_name.clone()),
-                            Value::Int(1 + index as i32),
-                            Value::Str(column.name().to_string()),
-                            Value::Int(column.bitfield()),
-                        ]
-                    })
-                    .collect(),
-            ),
-        )?;
-        self.insert_rows(
-            Insert::into(TABLES_TABLE_NAME)
-                .row(vec![Value::Str(table_name.clone())]),
-        )?;
-        let validation_rows: Vec<Vec<Value>> = columns
-            .iter()
-            .map(|column| {
-                let (min_value, max_value) =
-                    if let Some((min, max)) = column.value_range() {
-                        (Value::Int(min), Value::Int(max))
-                    } else {
-                        (Value::Null, Value::Null)
-                    };
-                let (key_table, key_column) =
-                    if let Some((table, column)) = column.foreign_key() {
-                        (Value::Str(table.to_string()), Value::Int(column))
-                    } else {
-                        (Value::Null, Value::Null)
-                    };
-                vec![
-                    Value::Str(table_name.clone()),
-                    Value::Str(column.name().to_string()),
-                    Value::Str(if column.is_nullable() {
-                        "Y".to_string()
-                    } else {
-                        "N".to_string()
-                    }),
-                    min_value,
-                    max_value,
-                    key_table,
-                    key_column,
-                    if let Some(category) = column.category() {
-                        Value::Str(category.to_string())
-                    } else {
-                        Value::Null
-                    },
-                    if let Some(values) = column.enum_values() {
-                        Value::Str(values.join(";"))
-                    } else {
-                        Value::Null
-                    },
-                    Value::Null,
-                ]
-            })
-            .collect();
+            return Err(MsiError::TableAlreadyExists {
+                table: table_name.to_string(),
+            });
+        }
         let long_string_refs = self.string_pool.long_string_refs();
         let table = Table::new(table_name.clone(), columns, long_string_refs);
+        let persistent_columns =
+            &table.columns()[..table.num_persistent_columns()];
+        if !is_temporary {
+            self.insert_rows(
+                Insert::into(COLUMNS_TABLE_NAME).rows(
+                    persistent_columns
+                        .iter()
+                        .enumerate()
+                        .map(|(index, column)| {
+                            make_columns_row(&table_name, 1 + index, column)
+                        })
+                        .collect(),
+                ),
+            )?;
+            self.insert_rows(
+                Insert::into(TABLES_TABLE_NAME)
+                    .row(vec![Value::Str(table_name.clone())]),
+            )?;
+        }
+        let validation_rows: Vec<Vec<Value>> = persistent_columns
+            .iter()
+            .map(|column| make_validation_row(&table_name, column))
+            .collect();
+        if persistent_columns.len() < table.columns().len() {
+            self.held.insert(
+                table_name.clone(),
+                HeldTable { rows: Vec::new(), hold_count: 1 },
+            );
+        }
+        self.indices.remove(&table_name);
         self.tables.insert(table_name, table);
-        self.insert_rows(
-            Insert::into(VALIDATION_TABLE_NAME).rows(validation_rows),
+        if !validation_rows.is_empty() {
+            self.insert_rows(
+                Insert::into(VALIDATION_TABLE_NAME).rows(validation_rows),
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Adds a new column to an existing database table, setting the new
+    /// column to null in all existing rows.  This is equivalent to `ALTER
+    /// TABLE ... ADD` in Windows Installer SQL.  Returns an error without
+    /// modifying the database if the column is invalid, if the table already
+    /// has a column with that name, or if no such table exists.
+    ///
+    /// New columns cannot be primary key columns, and must be nullable unless
+    /// the table is empty.  If the new column is temporary (`ALTER TABLE ...
+    /// ADD ... TEMPORARY`), the table is held in memory until it is freed
+    /// (see `free_table`), at which point the temporary column is discarded.
+    pub fn add_column(
+        &mut self,
+        table_name: &str,
+        column: Column,
+    ) -> Result<()> {
+        if is_reserved_table_name(table_name) {
+            invalid_input!("Cannot alter special {:?} table", table_name);
+        }
+        let table = match self.tables.get(table_name) {
+            Some(table) => table.clone(),
+            None => {
+                return Err(MsiError::TableNotFound {
+                    table: table_name.to_string(),
+                });
+            }
+        };
+        if !Column::is_valid_name(column.name()) {
+            invalid_input!("{:?} is not a valid column name", column.name());
+        }
+        if table.has_column(column.name()) {
+            return Err(MsiError::ColumnAlreadyExists {
+                table: table_name.to_string(),
+                column: column.name().to_string(),
+            });
+        }
+        if table.columns().len() >= MAX_NUM_TABLE_COLUMNS {
+            invalid_input!(
+                "Cannot add a column to table {:?}, which already has {} \
+                 columns",
+                table_name,
+                MAX_NUM_TABLE_COLUMNS
+            );
+        }
+        if column.is_primary_key() {
+            invalid_input!(
+                "Cannot add primary key column {:?} to an existing table",
+                column.name()
+            );
+        }
+        if table.is_temporary() && !column.is_temporary() {
+            invalid_input!(
+                "Cannot add persistent column {:?} to temporary table {:?}",
+                column.name(),
+                table_name
+            );
+        }
+        let mut rows = load_rows(
+            self.comp.as_mut().unwrap(),
+            &self.string_pool,
+            &self.held,
+            &table,
         )?;
+        if !column.is_nullable() && !rows.is_empty() {
+            invalid_input!(
+                "Cannot add non-nullable column {:?} to non-empty table {:?}",
+                column.name(),
+                table_name
+            );
+        }
+        let index = if column.is_temporary() {
+            table.columns().len()
+        } else {
+            table.num_persistent_columns()
+        };
+        let original_rows = rows.clone();
+        for row in &mut rows {
+            row.insert(index, ValueRef::Null);
+        }
+        if column.is_temporary() {
+            self.held
+                .entry(table_name.to_string())
+                .or_insert(HeldTable { rows: Vec::new(), hold_count: 1 });
+        }
+        let mut columns = table.columns().to_vec();
+        columns.insert(index, column.clone());
+        let new_table = Table::new(
+            table_name.to_string(),
+            columns,
+            self.string_pool.long_string_refs(),
+        );
+        self.set_finisher();
+        self.indices.remove(table_name);
+        // Rewrite the rows before touching the schema tables, so that a
+        // failure leaves the table as it was.
+        store_rows(
+            self.comp.as_mut().unwrap(),
+            &mut self.held,
+            &new_table,
+            rows,
+        )?;
+        if !column.is_temporary() {
+            let result = self
+                .insert_rows(
+                    Insert::into(COLUMNS_TABLE_NAME).row(make_columns_row(
+                        table_name,
+                        1 + index,
+                        &column,
+                    )),
+                )
+                .and_then(|()| {
+                    self.insert_rows(
+                        Insert::into(VALIDATION_TABLE_NAME)
+                            .row(make_validation_row(table_name, &column)),
+                    )
+                });
+            if let Err(error) = result {
+                self.delete_rows(
+                    Delete::from(COLUMNS_TABLE_NAME).with(
+                        Expr::col("Table").eq(Expr::string(table_name)).and(
+                            Expr::col("Name").eq(Expr::string(column.name())),
+                        ),
+                    ),
+                )?;
+                store_rows(
+                    self.comp.as_mut().unwrap(),
+                    &mut self.held,
+                    &table,
+                    original_rows,
+                )?;
+                return Err(error);
+            }
+        }
+        self.tables.insert(table_name.to_string(), new_table);
+        Ok(())
+    }
+
+    /// Holds the table with the given name in memory, so that queries on it
+    /// no longer read and rewrite its stream.  Each call must be balanced by
+    /// a call to `free_table`.  This is equivalent to `ALTER TABLE ... HOLD`
+    /// in Windows Installer SQL.
+    pub fn hold_table(&mut self, table_name: &str) -> Result<()> {
+        let table = match self.tables.get(table_name) {
+            Some(table) => table.clone(),
+            None => {
+                return Err(MsiError::TableNotFound {
+                    table: table_name.to_string(),
+                });
+            }
+        };
+        if let Some(held_table) = self.held.get_mut(table_name) {
+            held_table.hold_count += 1;
+            return Ok(());
+        }
+        let rows = load_rows(
+            self.comp.as_mut().unwrap(),
+            &self.string_pool,
+            &self.held,
+            &table,
+        )?;
+        self.held
+            .insert(table_name.to_string(), HeldTable { rows, hold_count: 1 });
+        Ok(())
+    }
+
+    /// Releases one hold on the table with the given name.  When the last
+    /// hold is released, a temporary table is dropped, the table's temporary
+    /// columns are discarded, and its remaining rows are written back to the
+    /// package.  This is equivalent to `ALTER TABLE ... FREE` in Windows
+    /// Installer SQL.
+    pub fn free_table(&mut self, table_name: &str) -> Result<()> {
+        let held_table = match self.held.get_mut(table_name) {
+            Some(held_table) => held_table,
+            None => invalid_input!("Table {:?} is not held", table_name),
+        };
+        held_table.hold_count -= 1;
+        if held_table.hold_count > 0 {
+            return Ok(());
+        }
+        let rows = self.held.remove(table_name).unwrap().rows;
+        self.indices.remove(table_name);
+        let table = self.tables[table_name].clone();
+        self.set_finisher();
+        if table.is_temporary() {
+            for value_ref in rows.into_iter().flatten() {
+                value_ref.remove(&mut self.string_pool);
+            }
+            self.tables.remove(table_name);
+            return Ok(());
+        }
+        let num_columns = table.num_persistent_columns();
+        let rows: Vec<Vec<ValueRef>> = rows
+            .into_iter()
+            .map(|mut row| {
+                for value_ref in row.drain(num_columns..) {
+                    value_ref.remove(&mut self.string_pool);
+                }
+                row
+            })
+            .collect();
+        let table = table.without_temporary_columns();
+        let stream = self.comp_mut().create_stream(table.stream_name())?;
+        table.write_rows(stream, rows)?;
+        self.tables.insert(table_name.to_string(), table);
         Ok(())
     }
 
     /// Removes an existing database table.  Returns an error without modifying
     /// the database if the table name is invalid, or if no such table exists.
-    pub fn drop_table(&mut self, table_name: &str) -> io::Result<()> {
+    pub fn drop_table(&mut self, table_name: &str) -> Result<()> {
         if is_reserved_table_name(table_name) {
             invalid_input!("Cannot drop special {:?} table", table_name);
         }
@@ -686,7 +1080,20 @@ impl<F: Read + Write + Seek> Package<F> {
             invalid_input!("{:?} is not a valid table name", table_name);
         }
         if !self.tables.contains_key(table_name) {
-            not_found!("Table {:?} does not exist", table_name);
+            return Err(MsiError::TableNotFound {
+                table: table_name.to_string(),
+            });
+        }
+        self.indices.remove(table_name);
+        if let Some(held_table) = self.held.remove(table_name) {
+            self.set_finisher();
+            for value_ref in held_table.rows.into_iter().flatten() {
+                value_ref.remove(&mut self.string_pool);
+            }
+        }
+        if self.tables[table_name].is_temporary() {
+            self.tables.remove(table_name);
+            return Ok(());
         }
         let stream_name = self.tables.get(table_name).unwrap().stream_name();
         if self.comp().exists(&stream_name) {
@@ -710,36 +1117,42 @@ impl<F: Read + Write + Seek> Package<F> {
 
     /// Attempts to execute a delete query.  Returns an error without modifying
     /// the database if the query fails (e.g. due to the table not existing).
-    pub fn delete_rows(&mut self, query: Delete) -> io::Result<()> {
+    pub fn delete_rows(&mut self, query: Delete) -> Result<()> {
         self.set_finisher();
+        self.indices.remove(query.table_name());
         query.exec(
             self.comp.as_mut().unwrap(),
             &mut self.string_pool,
             &self.tables,
+            &mut self.held,
         )
     }
 
     /// Attempts to execute an insert query.  Returns an error without
     /// modifying the database if the query fails (e.g. due to values being
     /// invalid, or keys not being unique, or the table not existing).
-    pub fn insert_rows(&mut self, query: Insert) -> io::Result<()> {
+    pub fn insert_rows(&mut self, query: Insert) -> Result<()> {
         self.set_finisher();
+        self.indices.remove(query.table_name());
         query.exec(
             self.comp.as_mut().unwrap(),
             &mut self.string_pool,
             &self.tables,
+            &mut self.held,
         )
     }
 
     /// Attempts to execute an update query.  Returns an error without
     /// modifying the database if the query fails (e.g. due to values being
     /// invalid, or column names being incorrect, or the table not existing).
-    pub fn update_rows(&mut self, query: Update) -> io::Result<()> {
+    pub fn update_rows(&mut self, query: Update) -> Result<()> {
         self.set_finisher();
+        self.indices.remove(query.table_name());
         query.exec(
             self.comp.as_mut().unwrap(),
             &mut self.string_pool,
             &self.tables,
+            &mut self.held,
         )
     }
 
@@ -747,7 +1160,7 @@ impl<F: Read + Write + Seek> Package<F> {
     pub fn write_stream(
         &mut self,
         stream_name: &str,
-    ) -> io::Result<StreamWriter<F>> {
+    ) -> Result<StreamWriter<F>> {
         if !streamname::is_valid(stream_name, false) {
             invalid_input!("{:?} is not a valid stream name", stream_name);
         }
@@ -756,23 +1169,25 @@ impl<F: Read + Write + Seek> Package<F> {
     }
 
     /// Removes an existing binary stream from the package.
-    pub fn remove_stream(&mut self, stream_name: &str) -> io::Result<()> {
+    pub fn remove_stream(&mut self, stream_name: &str) -> Result<()> {
         if !streamname::is_valid(stream_name, false) {
             invalid_input!("{:?} is not a valid stream name", stream_name);
         }
         let encoded_name = streamname::encode(stream_name, false);
         if !self.comp().is_stream(&encoded_name) {
-            not_found!("Stream {:?} does not exist", stream_name);
+            return Err(MsiError::StreamNotFound {
+                stream: stream_name.to_string(),
+            });
         }
-        self.comp_mut().remove_stream(&encoded_name)
+        Ok(self.comp_mut().remove_stream(&encoded_name)?)
     }
 
-    // TODO: pub fn add_digital_signature(&mut self, ...) -> io::Result<()>
+    // TODO: pub fn add_digital_signature(&mut self, ...) -> Result<()>
 
     /// Removes any existing digital signature from the package.  This can be
     /// useful if you need to modify a signed package (which will invalidate
     /// the signature).
-    pub fn remove_digital_signature(&mut self) -> io::Result<()> {
+    pub fn remove_digital_signature(&mut self) -> Result<()> {
         if self.comp().is_stream(DIGITAL_SIGNATURE_STREAM_NAME) {
             self.comp_mut().remove_stream(DIGITAL_SIGNATURE_STREAM_NAME)?;
         }
@@ -783,17 +1198,23 @@ impl<F: Read + Write + Seek> Package<F> {
         Ok(())
     }
 
-    /// Flushes any buffered changes to the underlying writer.
-    pub fn flush(&mut self) -> io::Result<()> {
+    /// Flushes any buffered changes to the underlying writer.  Temporary
+    /// tables and columns are not written.
+    pub fn flush(&mut self) -> Result<()> {
         if let Some(finisher) = self.finisher.take() {
             finisher.finish(self)?;
         }
-        self.comp_mut().flush()
+        if !self.held.is_empty() {
+            // Held tables must be written out again when the package closes.
+            self.set_finisher();
+        }
+        Ok(self.comp_mut().flush()?)
     }
 
     fn set_finisher(&mut self) {
         if self.finisher.is_none() {
-            let finisher: Box<dyn Finish<F>> = Box::new(FinishImpl {});
+            let finisher: Box<dyn Finish<F> + Send + Sync> =
+                Box::new(FinishImpl {});
             self.finisher = Some(finisher);
         }
     }
@@ -802,6 +1223,7 @@ impl<F: Read + Write + Seek> Package<F> {
 impl<F> Drop for Package<F> {
     fn drop(&mut self) {
         if let Some(finisher) = self.finisher.take() {
+            self.release_temporary_data();
             let _ = finisher.finish(self);
         }
     }
@@ -814,14 +1236,14 @@ impl<F> Drop for Package<F> {
 /// No guarantees are made about the order in which items are returned.
 #[derive(Clone)]
 pub struct Tables<'a> {
-    iter: btree_map::Values<'a, String, Rc<Table>>,
+    iter: btree_map::Values<'a, String, Arc<Table>>,
 }
 
 impl<'a> Iterator for Tables<'a> {
     type Item = &'a Table;
 
     fn next(&mut self) -> Option<&'a Table> {
-        self.iter.next().map(Rc::borrow)
+        self.iter.next().map(Arc::borrow)
     }
 
     fn size_hint(&self) -> (usize, Option<usize>) {
@@ -834,13 +1256,13 @@ impl<'a> ExactSizeIterator for Tables<'a> {}
 // ========================================================================= //
 
 trait Finish<F> {
-    fn finish(&self, package: &mut Package<F>) -> io::Result<()>;
+    fn finish(&self, package: &mut Package<F>) -> Result<()>;
 }
 
 struct FinishImpl {}
 
 impl<F: Read + Write + Seek> Finish<F> for FinishImpl {
-    fn finish(&self, package: &mut Package<F>) -> io::Result<()> {
+    fn finish(&self, package: &mut Package<F>) -> Result<()> {
         if package.is_summary_info_modified {
             let stream = package
                 .comp
@@ -850,18 +1272,42 @@ impl<F: Read + Write + Seek> Finish<F> for FinishImpl {
             package.summary_info.write(stream)?;
             package.is_summary_info_modified = false;
         }
+        for (table_name, held_table) in &package.held {
+            let table = &package.tables[table_name];
+            if table.is_temporary() {
+                continue;
+            }
+            let num_columns = table.num_persistent_columns();
+            let rows: Vec<Vec<ValueRef>> = held_table
+                .rows
+                .iter()
+                .map(|row| row[..num_columns].to_vec())
+                .collect();
+            let table = table.without_temporary_columns();
+            let stream = package
+                .comp
+                .as_mut()
+                .unwrap()
+                .create_stream(table.stream_name())?;
+            table.write_rows(stream, rows)?;
+        }
         if package.string_pool.is_modified() {
+            let string_pool = persistent_string_pool(
+                &package.string_pool,
+                &package.tables,
+                &package.held,
+            );
             {
                 let name = streamname::encode(STRING_POOL_TABLE_NAME, true);
                 let stream =
                     package.comp.as_mut().unwrap().create_stream(name)?;
-                package.string_pool.write_pool(stream)?;
+                string_pool.write_pool(stream)?;
             }
             {
                 let name = streamname::encode(STRING_DATA_TABLE_NAME, true);
                 let stream =
                     package.comp.as_mut().unwrap().create_stream(name)?;
-                package.string_pool.write_data(stream)?;
+                string_pool.write_data(stream)?;
             }
             package.string_pool.mark_unmodified();
         }
@@ -869,17 +1315,45 @@ impl<F: Read + Write + Seek> Finish<F> for FinishImpl {
     }
 }
 
+/// Returns the string pool as it should be written to the package.  Strings
+/// referenced by temporary tables and columns, which are never written, are
+/// released from the returned copy.
+fn persistent_string_pool<'a>(
+    string_pool: &'a StringPool,
+    tables: &BTreeMap<String, Arc<Table>>,
+    held: &BTreeMap<String, HeldTable>,
+) -> Cow<'a, StringPool> {
+    let mut string_pool = Cow::Borrowed(string_pool);
+    for (table_name, held_table) in held {
+        let table = &tables[table_name];
+        let num_columns = if table.is_temporary() {
+            0
+        } else {
+            table.num_persistent_columns()
+        };
+        for row in &held_table.rows {
+            for value_ref in &row[num_columns..] {
+                if let ValueRef::Str(string_ref) = *value_ref {
+                    string_pool.to_mut().decref(string_ref);
+                }
+            }
+        }
+    }
+    string_pool
+}
+
 // ========================================================================= //
 
 #[cfg(test)]
 mod tests {
-    use super::{Package, PackageType};
+    use super::{Package, PackageType, STRING_DATA_TABLE_NAME};
     use crate::internal::codepage::CodePage;
     use crate::internal::column::Column;
     use crate::internal::expr::Expr;
     use crate::internal::query::{Insert, Select, Update};
+    use crate::internal::streamname;
     use crate::internal::value::Value;
-    use std::io::Cursor;
+    use std::io::{Cursor, Read};
 
     #[test]
     fn set_database_codepage() {
@@ -974,6 +1448,52 @@ mod tests {
             .collect();
         assert_eq!(values, vec![(1, -5), (2, 42), (3, -5)]);
     }
+
+    #[test]
+    fn flush_releases_strings_of_temporary_columns() {
+        let cursor = Cursor::new(Vec::new());
+        let mut package =
+            Package::create(PackageType::Installer, cursor).unwrap();
+        let columns = vec![
+            Column::build("Number").primary_key().int16(),
+            Column::build("Word").nullable().string(50),
+        ];
+        package.create_table("Numbers", columns).unwrap();
+        package
+            .insert_rows(
+                Insert::into("Numbers")
+                    .row(vec![Value::Int(1), Value::from("One")]),
+            )
+            .unwrap();
+        package
+            .add_column(
+                "Numbers",
+                Column::build("Scratch").temporary().nullable().string(0),
+            )
+            .unwrap();
+        package
+            .update_rows(
+                Update::table("Numbers")
+                    .set("Scratch", Value::from("Ephemeral")),
+            )
+            .unwrap();
+        package.flush().unwrap();
+
+        let name = streamname::encode(STRING_DATA_TABLE_NAME, true);
+        let mut data = Vec::new();
+        package
+            .comp_mut()
+            .open_stream(name)
+            .unwrap()
+            .read_to_end(&mut data)
+            .unwrap();
+        let data = String::from_utf8_lossy(&data);
+        assert!(data.contains("One"));
+        assert!(!data.contains("Ephemeral"));
+        let rows = package.select_rows(Select::table("Numbers")).unwrap();
+        let row = rows.last().unwrap();
+        assert_eq!(row["Scratch"], Value::from("Ephemeral"));
+    }
 }
 
 // ========================================================================= //
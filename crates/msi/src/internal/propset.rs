@@ -4,6 +4,7 @@
 //     aa380367(v=vs.85).aspx
 
 use crate::internal::codepage::CodePage;
+use crate::internal::error::Result;
 use crate::internal::timestamp::Timestamp;
 use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
 use std::cmp;
@@ -58,7 +59,7 @@ impl PropertyValue {
     fn read<R: Read>(
         mut reader: R,
         codepage: CodePage,
-    ) -> io::Result<PropertyValue> {
+    ) -> Result<PropertyValue> {
         let type_number = reader.read_u32::<LittleEndian>()?;
         match type_number {
             0 => Ok(PropertyValue::Empty),
@@ -69,9 +70,10 @@ impl PropertyValue {
             30 => {
                 let length = reader.read_u32::<LittleEndian>()?;
                 let length = if length == 0 { 0 } else { length - 1 };
-                let mut bytes: Vec<u8> = Vec::with_capacity(length as usize);
-                for _ in 0..length {
-                    bytes.push(reader.read_u8()?);
+                let mut bytes: Vec<u8> = Vec::new();
+                reader.by_ref().take(length as u64).read_to_end(&mut bytes)?;
+                if bytes.len() != length as usize {
+                    invalid_data!("Property set string is truncated");
                 }
                 if reader.read_u8()? != 0 {
                     invalid_data!("Property set string not null-terminated");
@@ -204,7 +206,7 @@ impl PropertySet {
         }
     }
 
-    pub fn read<R: Read + Seek>(mut reader: R) -> io::Result<PropertySet> {
+    pub fn read<R: Read + Seek>(mut reader: R) -> Result<PropertySet> {
         // Property set header:
         if reader.read_u16::<LittleEndian>()? != BYTE_ORDER_MARK {
             invalid_data!("Invalid byte order mark");
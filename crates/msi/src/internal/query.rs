@@ -4,16 +4,20 @@ use std::fmt;
 use std::io::Read;
 use std::io::Seek;
 use std::io::Write;
-use std::io::{self};
-use std::rc::Rc;
+use std::sync::Arc;
 
 use cfb;
 
+use crate::internal::error::MsiError;
+use crate::internal::error::Result;
 use crate::internal::expr::Expr;
 use crate::internal::stringpool::StringPool;
+use crate::internal::table::HeldTable;
+use crate::internal::table::KeyIndex;
 use crate::internal::table::Row;
 use crate::internal::table::Rows;
 use crate::internal::table::Table;
+use crate::internal::table::{load_key_index, load_rows, store_rows};
 use crate::internal::value::Value;
 use crate::internal::value::ValueRef;
 
@@ -45,39 +49,42 @@ impl Delete {
         self
     }
 
+    /// Returns the name of the table that this query modifies.
+    pub(crate) fn table_name(&self) -> &str {
+        &self.table_name
+    }
+
     pub(crate) fn exec<F>(
         self,
         comp: &mut cfb::CompoundFile<F>,
         string_pool: &mut StringPool,
-        tables: &BTreeMap<String, Rc<Table>>,
-    ) -> io::Result<()>
+        tables: &BTreeMap<String, Arc<Table>>,
+        held: &mut BTreeMap<String, HeldTable>,
+    ) -> Result<()>
     where
         F: Read + Write + Seek,
     {
         let table = match tables.get(&self.table_name) {
             Some(table) => table,
-            None => not_found!("Table {:?} does not exist", self.table_name),
+            None => {
+                return Err(MsiError::TableNotFound {
+                    table: self.table_name.to_string(),
+                });
+            }
         };
         // Validate the condition.
         if let Some(ref expr) = self.condition {
             for column_name in expr.column_names() {
                 if !table.has_column(column_name) {
-                    invalid_input!(
-                        "Table {:?} has no column named {:?}",
-                        self.table_name,
-                        column_name
-                    );
+                    return Err(MsiError::ColumnNotFound {
+                        table: self.table_name.to_string(),
+                        column: column_name.to_string(),
+                    });
                 }
             }
         }
         // Read in the rows from the table.
-        let stream_name = table.stream_name();
-        let mut rows = if comp.exists(&stream_name) {
-            let stream = comp.open_stream(&stream_name)?;
-            table.read_rows(stream)?
-        } else {
-            Vec::new()
-        };
+        let mut rows = load_rows(comp, string_pool, held, table)?;
         // Delete rows from the table.
         rows.retain(|value_refs| {
             let should_delete = match self.condition {
@@ -102,14 +109,13 @@ impl Delete {
             }
         });
         // Write the table back out to the file.
-        let stream = comp.create_stream(&stream_name)?;
-        table.write_rows(stream, rows)?;
+        store_rows(comp, held, table, rows)?;
         Ok(())
     }
 }
 
 impl fmt::Display for Delete {
-    fn fmt(&self, formatter: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
         formatter.write_str("DELETE FROM ")?;
         formatter.write_str(&self.table_name)?;
         if let Some(ref expr) = self.condition {
@@ -148,18 +154,28 @@ impl Insert {
         self
     }
 
+    /// Returns the name of the table that this query modifies.
+    pub(crate) fn table_name(&self) -> &str {
+        &self.table_name
+    }
+
     pub(crate) fn exec<F>(
         self,
         comp: &mut cfb::CompoundFile<F>,
         string_pool: &mut StringPool,
-        tables: &BTreeMap<String, Rc<Table>>,
-    ) -> io::Result<()>
+        tables: &BTreeMap<String, Arc<Table>>,
+        held: &mut BTreeMap<String, HeldTable>,
+    ) -> Result<()>
     where
         F: Read + Write + Seek,
     {
         let table = match tables.get(&self.table_name) {
             Some(table) => table,
-            None => not_found!("Table {:?} does not exist", self.table_name),
+            None => {
+                return Err(MsiError::TableNotFound {
+                    table: self.table_name.to_string(),
+                });
+            }
         };
         // Validate the new rows.
         for values in &self.new_rows {
@@ -174,37 +190,32 @@ impl Insert {
             }
             for (column, value) in table.columns().iter().zip(values.iter()) {
                 if !column.is_valid_value(value) {
-                    invalid_input!(
-                        "{:?} is not a valid value for column {:?} of type {:?}",
-                        value,
-                        column.name(),
-                        column.coltype(),
-                    );
+                    return Err(MsiError::InvalidValue {
+                        table: self.table_name.clone(),
+                        column: column.name().to_string(),
+                        column_type: column.coltype(),
+                        value: value.clone(),
+                    });
                 }
                 // TODO: Validate foreign keys.
             }
         }
         // Read in the rows from the table.
-        let stream_name = table.stream_name();
         let key_indices = table.primary_key_indices();
         let mut rows_map = BTreeMap::<Vec<Value>, Vec<ValueRef>>::new();
-        if comp.exists(&stream_name) {
-            let stream = comp.open_stream(&stream_name)?;
-            for row in table.read_rows(stream)? {
-                let keys: Vec<Value> = key_indices
-                    .iter()
-                    .map(|&index| row[index].to_value(string_pool))
-                    .collect();
-                if rows_map.contains_key(&keys) {
-                    invalid_data!(
-                        "Malformed table {:?} contains multiple rows with \
-                         key {:?}",
-                        self.table_name,
-                        keys
-                    );
-                }
-                rows_map.insert(keys, row);
+        for row in load_rows(comp, string_pool, held, table)? {
+            let keys: Vec<Value> = key_indices
+                .iter()
+                .map(|&index| row[index].to_value(string_pool))
+                .collect();
+            if rows_map.contains_key(&keys) {
+                invalid_data!(
+                    "Malformed table {:?} contains multiple rows with key {:?}",
+                    self.table_name,
+                    keys
+                );
             }
+            rows_map.insert(keys, row);
         }
         // Check if any of the new rows already exist in the table (or conflict
         // with each other).
@@ -215,11 +226,10 @@ impl Insert {
                 .map(|&index| values[index].clone())
                 .collect();
             if rows_map.contains_key(&keys) {
-                already_exists!(
-                    "Table {:?} already contains a row with key {:?}",
-                    self.table_name,
-                    keys
-                );
+                return Err(MsiError::DuplicateKey {
+                    table: self.table_name.to_string(),
+                    key: keys,
+                });
             }
             if new_keys_set.contains(&keys) {
                 invalid_input!(
@@ -243,14 +253,13 @@ impl Insert {
         }
         // Write the table back out to the file.
         let rows: Vec<Vec<ValueRef>> = rows_map.into_values().collect();
-        let stream = comp.create_stream(&stream_name)?;
-        table.write_rows(stream, rows)?;
+        store_rows(comp, held, table, rows)?;
         Ok(())
     }
 }
 
 impl fmt::Display for Insert {
-    fn fmt(&self, formatter: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
         formatter.write_str("INSERT INTO ")?;
         formatter.write_str(&self.table_name)?;
         if !self.new_rows.is_empty() {
@@ -292,8 +301,10 @@ impl Join {
         self,
         comp: &mut cfb::CompoundFile<F>,
         string_pool: &'a StringPool,
-        tables: &BTreeMap<String, Rc<Table>>,
-    ) -> io::Result<Rows<'a>>
+        tables: &BTreeMap<String, Arc<Table>>,
+        held: &BTreeMap<String, HeldTable>,
+        indices: &mut BTreeMap<String, KeyIndex>,
+    ) -> Result<Rows<'a>>
     where
         F: Read + Seek,
     {
@@ -302,24 +313,20 @@ impl Join {
                 let table = match tables.get(&table_name) {
                     Some(table) => table,
                     None => {
-                        not_found!("Table {:?} does not exist", table_name)
+                        return Err(MsiError::TableNotFound {
+                            table: table_name.to_string(),
+                        });
                     }
                 };
-                let stream_name = table.stream_name();
-                let rows = if comp.exists(&stream_name) {
-                    let stream = comp.open_stream(&stream_name)?;
-                    table.read_rows(stream)?
-                } else {
-                    Vec::new()
-                };
+                let rows = load_rows(comp, string_pool, held, table)?;
                 Ok(Rows::new(string_pool, table.clone(), rows))
             }
             Join::Inner(select1, select2, condition) => {
                 let (table1, rows1) = select1
-                    .exec(comp, string_pool, tables)?
+                    .exec(comp, string_pool, tables, held, indices)?
                     .into_table_and_values();
                 let (table2, rows2) = select2
-                    .exec(comp, string_pool, tables)?
+                    .exec(comp, string_pool, tables, held, indices)?
                     .into_table_and_values();
                 let columns =
                     table1
@@ -357,10 +364,10 @@ impl Join {
             }
             Join::Left(select1, select2, condition) => {
                 let (table1, rows1) = select1
-                    .exec(comp, string_pool, tables)?
+                    .exec(comp, string_pool, tables, held, indices)?
                     .into_table_and_values();
                 let (table2, rows2) = select2
-                    .exec(comp, string_pool, tables)?
+                    .exec(comp, string_pool, tables, held, indices)?
                     .into_table_and_values();
                 let columns = table1
                     .columns()
@@ -412,7 +419,7 @@ impl Join {
 }
 
 impl fmt::Display for Join {
-    fn fmt(&self, formatter: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
         match self {
             Join::Table(table_name) => table_name.fmt(formatter),
             Join::Inner(lhs, rhs, on) => {
@@ -507,13 +514,24 @@ impl Select {
         self,
         comp: &mut cfb::CompoundFile<F>,
         string_pool: &'a StringPool,
-        tables: &BTreeMap<String, Rc<Table>>,
-    ) -> io::Result<Rows<'a>>
+        tables: &BTreeMap<String, Arc<Table>>,
+        held: &BTreeMap<String, HeldTable>,
+        indices: &mut BTreeMap<String, KeyIndex>,
+    ) -> Result<Rows<'a>>
     where
         F: Read + Seek,
     {
-        // Join the table(s) to be queried.
-        let rows = self.from.exec(comp, string_pool, tables)?;
+        // Join the table(s) to be queried.  If the condition pins every
+        // primary key column of a single table, look the matching row up in
+        // the table's key index instead of scanning the whole table.
+        let rows = match self.key_lookup(tables) {
+            Some((table, key)) => {
+                let index =
+                    load_key_index(comp, string_pool, held, indices, &table)?;
+                Rows::new(string_pool, table, index.get(&key))
+            }
+            None => self.from.exec(comp, string_pool, tables, held, indices)?,
+        };
         let (mut table, mut rows) = rows.into_table_and_values();
         // Validate the selected column names.
         let mut column_indices =
@@ -522,11 +540,10 @@ impl Select {
             match table.index_for_column_name(column_name.as_str()) {
                 Some(index) => column_indices.push(index),
                 None => {
-                    invalid_input!(
-                        "Table {:?} has no column named {:?}",
-                        table.name(),
-                        column_name
-                    );
+                    return Err(MsiError::ColumnNotFound {
+                        table: table.name().to_string(),
+                        column: column_name.to_string(),
+                    });
                 }
             }
         }
@@ -534,11 +551,10 @@ impl Select {
         if let Some(ref expr) = self.condition {
             for column_name in expr.column_names() {
                 if !table.has_column(column_name) {
-                    invalid_input!(
-                        "Table {:?} has no column named {:?}",
-                        table.name(),
-                        column_name
-                    );
+                    return Err(MsiError::ColumnNotFound {
+                        table: table.name().to_string(),
+                        column: column_name.to_string(),
+                    });
                 }
             }
         }
@@ -571,10 +587,33 @@ impl Select {
         Ok(Rows::new(string_pool, table, rows))
     }
 
-    fn format_for_join(
+    /// If this query selects from a single table and its condition pins every
+    /// primary key column of that table to a literal value, returns the table
+    /// and the pinned key values.
+    fn key_lookup(
         &self,
-        formatter: &mut fmt::Formatter,
-    ) -> Result<(), fmt::Error> {
+        tables: &BTreeMap<String, Arc<Table>>,
+    ) -> Option<(Arc<Table>, Vec<Value>)> {
+        let table = match self.from {
+            Join::Table(ref table_name) => tables.get(table_name)?,
+            _ => return None,
+        };
+        let pinned = self.condition.as_ref()?.pinned_columns();
+        let key_indices = table.primary_key_indices();
+        if key_indices.is_empty() {
+            return None;
+        }
+        let key = key_indices
+            .iter()
+            .map(|&index| {
+                let column_name = table.columns()[index].name();
+                pinned.get(column_name).map(|&value| value.clone())
+            })
+            .collect::<Option<Vec<Value>>>()?;
+        Some((table.clone(), key))
+    }
+
+    fn format_for_join(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
         if self.column_names.is_empty() && self.condition.is_none() {
             if let Join::Table(ref name) = self.from {
                 return formatter.write_str(name.as_str());
@@ -588,7 +627,7 @@ impl Select {
 }
 
 impl fmt::Display for Select {
-    fn fmt(&self, formatter: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
         formatter.write_str("SELECT ")?;
         if self.column_names.is_empty() {
             formatter.write_str("*")?;
@@ -657,35 +696,45 @@ impl Update {
         self
     }
 
+    /// Returns the name of the table that this query modifies.
+    pub(crate) fn table_name(&self) -> &str {
+        &self.table_name
+    }
+
     pub(crate) fn exec<F>(
         self,
         comp: &mut cfb::CompoundFile<F>,
         string_pool: &mut StringPool,
-        tables: &BTreeMap<String, Rc<Table>>,
-    ) -> io::Result<()>
+        tables: &BTreeMap<String, Arc<Table>>,
+        held: &mut BTreeMap<String, HeldTable>,
+    ) -> Result<()>
     where
         F: Read + Write + Seek,
     {
         let table = match tables.get(&self.table_name) {
             Some(table) => table,
-            None => not_found!("Table {:?} does not exist", self.table_name),
+            None => {
+                return Err(MsiError::TableNotFound {
+                    table: self.table_name.to_string(),
+                });
+            }
         };
         // Validate the updates.
         for (column_name, value) in &self.updates {
             if !table.has_column(column_name.as_str()) {
-                invalid_input!(
-                    "Table {:?} has no column named {:?}",
-                    self.table_name,
-                    column_name
-                );
+                return Err(MsiError::ColumnNotFound {
+                    table: self.table_name.to_string(),
+                    column: column_name.to_string(),
+                });
             }
             let column = table.get_column(column_name).unwrap();
             if !column.is_valid_value(value) {
-                invalid_input!(
-                    "{} is not a valid value for column {:?}",
-                    value,
-                    column_name
-                );
+                return Err(MsiError::InvalidValue {
+                    table: self.table_name.clone(),
+                    column: column_name.clone(),
+                    column_type: column.coltype(),
+                    value: value.clone(),
+                });
             }
             // TODO: Validate foreign keys.
         }
@@ -693,22 +742,15 @@ impl Update {
         if let Some(ref expr) = self.condition {
             for column_name in expr.column_names() {
                 if !table.has_column(column_name) {
-                    invalid_input!(
-                        "Table {:?} has no column named {:?}",
-                        self.table_name,
-                        column_name
-                    );
+                    return Err(MsiError::ColumnNotFound {
+                        table: self.table_name.to_string(),
+                        column: column_name.to_string(),
+                    });
                 }
             }
         }
         // Read in the rows from the table.
-        let stream_name = table.stream_name();
-        let mut rows = if comp.exists(&stream_name) {
-            let stream = comp.open_stream(&stream_name)?;
-            table.read_rows(stream)?
-        } else {
-            Vec::new()
-        };
+        let mut rows = load_rows(comp, string_pool, held, table)?;
         // Update the rows.
         for value_refs in &mut rows {
             let should_update = match self.condition {
@@ -733,14 +775,13 @@ impl Update {
             }
         }
         // Write the table back out to the file.
-        let stream = comp.create_stream(&stream_name)?;
-        table.write_rows(stream, rows)?;
+        store_rows(comp, held, table, rows)?;
         Ok(())
     }
 }
 
 impl fmt::Display for Update {
-    fn fmt(&self, formatter: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
         formatter.write_str("UPDATE ")?;
         formatter.write_str(&self.table_name)?;
         formatter.write_str(" SET ")?;
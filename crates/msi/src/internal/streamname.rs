@@ -167,6 +167,21 @@ mod tests {
             false
         ));
     }
+
+    #[test]
+    fn decode_every_encoded_char() {
+        // Stream names come straight from the (possibly corrupted) CFB
+        // directory, so decoding must cope with any character at all.
+        for value in 0x3700..0x4900 {
+            let chr = char::from_u32(value).unwrap();
+            let name = format!("\u{4840}{chr}");
+            let (decoded, is_table) = decode(&name);
+            assert!(is_table);
+            assert!(!decoded.is_empty());
+        }
+        assert_eq!(decode("\u{4840}"), (String::new(), true));
+        assert_eq!(decode(""), (String::new(), false));
+    }
 }
 
 // ========================================================================= //
@@ -1,4 +1,5 @@
 use crate::internal::codepage::CodePage;
+use crate::internal::error::{MsiError, Result};
 use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
 use std::io::{self, Read, Write};
 
@@ -76,6 +77,19 @@ impl StringRef {
 
 // ========================================================================= //
 
+/// Reports an entry that the `_StringPool` stream ends partway through as
+/// invalid data, rather than as an I/O error.
+fn truncated_entry(error: io::Error, index: usize) -> MsiError {
+    if error.kind() == io::ErrorKind::UnexpectedEof {
+        MsiError::InvalidData(format!(
+            "String pool entry {} is truncated",
+            index
+        ))
+    } else {
+        error.into()
+    }
+}
+
 pub struct StringPoolBuilder {
     codepage: CodePage,
     long_string_refs: bool,
@@ -83,9 +97,7 @@ pub struct StringPoolBuilder {
 }
 
 impl StringPoolBuilder {
-    pub fn read_from_pool<R: Read>(
-        mut reader: R,
-    ) -> io::Result<StringPoolBuilder> {
+    pub fn read_from_pool<R: Read>(mut reader: R) -> Result<StringPoolBuilder> {
         let codepage_id = reader.read_u32::<LittleEndian>()?;
         let long_string_refs = (codepage_id & LONG_STRING_REFS_BIT) != 0;
         let codepage_id = (codepage_id & !LONG_STRING_REFS_BIT) as i32;
@@ -98,10 +110,21 @@ impl StringPoolBuilder {
         };
         let mut lengths_and_refcounts = Vec::<(u32, u16)>::new();
         while let Ok(length) = reader.read_u16::<LittleEndian>() {
+            let index = lengths_and_refcounts.len() + 1;
             let mut length = length as u32;
-            let refcount = reader.read_u16::<LittleEndian>()?;
+            let refcount = reader
+                .read_u16::<LittleEndian>()
+                .map_err(|error| truncated_entry(error, index))?;
             if length == 0 && refcount > 0 {
-                length = reader.read_u32::<LittleEndian>()?;
+                length = reader
+                    .read_u32::<LittleEndian>()
+                    .map_err(|error| truncated_entry(error, index))?;
+            }
+            if lengths_and_refcounts.len() >= MAX_STRING_REF as usize {
+                invalid_data!(
+                    "String pool has too many entries (> {})",
+                    MAX_STRING_REF
+                );
             }
             lengths_and_refcounts.push((length, refcount));
         }
@@ -112,14 +135,22 @@ impl StringPoolBuilder {
         })
     }
 
-    pub fn build_from_data<R: Read>(
-        self,
-        mut reader: R,
-    ) -> io::Result<StringPool> {
+    pub fn build_from_data<R: Read>(self, mut reader: R) -> Result<StringPool> {
         let mut strings = Vec::<(String, u16)>::new();
         for (length, refcount) in self.lengths_and_refcounts {
-            let mut buffer = vec![0u8; length as usize];
-            reader.read_exact(&mut buffer)?;
+            // Don't trust the length from the pool to size the buffer up
+            // front; only allocate as much as the data stream actually has.
+            let mut buffer = Vec::<u8>::new();
+            reader.by_ref().take(length as u64).read_to_end(&mut buffer)?;
+            if buffer.len() != length as usize {
+                invalid_data!(
+                    "String data is truncated (string {} should have {} \
+                     bytes, but only {} remain)",
+                    strings.len() + 1,
+                    length,
+                    buffer.len()
+                );
+            }
             strings.push((self.codepage.decode(&buffer), refcount));
         }
         Ok(StringPool {
@@ -134,6 +165,7 @@ impl StringPoolBuilder {
 // ========================================================================= //
 
 /// The string pool for an MSI package.
+#[derive(Clone)]
 pub struct StringPool {
     codepage: CodePage,
     strings: Vec<(String, u16)>,
@@ -165,7 +197,6 @@ impl StringPool {
 
     /// Returns the number of strings in the string pool (including empty
     /// entries).
-    #[allow(dead_code)]
     pub fn num_strings(&self) -> u32 {
         self.strings.len() as u32
     }
@@ -262,7 +293,7 @@ impl StringPool {
     }
 
     /// Writes to the `_StringPool` table.
-    pub fn write_pool<W: Write>(&self, mut writer: W) -> io::Result<()> {
+    pub fn write_pool<W: Write>(&self, mut writer: W) -> Result<()> {
         let mut codepage_id = self.codepage.id() as u32;
         if self.long_string_refs {
             codepage_id |= LONG_STRING_REFS_BIT;
@@ -281,7 +312,7 @@ impl StringPool {
     }
 
     /// Writes to the `_StringData` table.
-    pub fn write_data<W: Write>(&self, mut writer: W) -> io::Result<()> {
+    pub fn write_data<W: Write>(&self, mut writer: W) -> Result<()> {
         for (string, _) in &self.strings {
             writer.write_all(&self.codepage.encode(string.as_str()))?;
         }
@@ -295,6 +326,7 @@ impl StringPool {
 mod tests {
     use super::{StringPool, StringPoolBuilder, StringRef};
     use crate::internal::codepage::CodePage;
+    use crate::internal::error::MsiError;
 
     #[test]
     fn read_string_ref() {
@@ -510,6 +542,22 @@ mod tests {
         assert_eq!(string_pool.get(StringRef(1)), "Quux");
     }
 
+    #[test]
+    fn truncated_pool_entry() {
+        let pool: &[u8] = b"\xe9\xfd\x00\x00\x03\x00\x01\x00\x00\x00\x02";
+        let error = StringPoolBuilder::read_from_pool(pool).err().unwrap();
+        assert!(matches!(error, MsiError::InvalidData(_)), "{:?}", error);
+        assert_eq!(error.to_string(), "String pool entry 2 is truncated");
+    }
+
+    #[test]
+    fn truncated_string_data() {
+        let pool: &[u8] = b"\xe9\xfd\x00\x00\x06\x00\x01\x00";
+        let builder = StringPoolBuilder::read_from_pool(pool).expect("pool");
+        let error = builder.build_from_data(&b"Foo"[..]).err().unwrap();
+        assert!(matches!(error, MsiError::InvalidData(_)), "{:?}", error);
+    }
+
     #[test]
     #[should_panic(expected = "Unknown codepage for string pool (123456)")]
     fn invalid_codepage() {
@@ -1,4 +1,5 @@
 use crate::internal::codepage::CodePage;
+use crate::internal::error::Result;
 use crate::internal::language::Language;
 use crate::internal::propset::{OperatingSystem, PropertySet, PropertyValue};
 use crate::internal::timestamp::Timestamp;
@@ -54,7 +55,7 @@ impl SummaryInfo {
         summary
     }
 
-    pub fn read<R: Read + Seek>(reader: R) -> io::Result<SummaryInfo> {
+    pub fn read<R: Read + Seek>(reader: R) -> Result<SummaryInfo> {
         let properties = PropertySet::read(reader)?;
         if properties.format_identifier() != &FMTID {
             invalid_data!("Property set has wrong format identifier");
@@ -1,11 +1,14 @@
 use crate::internal::category::Category;
 use crate::internal::column::Column;
+use crate::internal::error::Result;
 use crate::internal::streamname;
 use crate::internal::stringpool::StringPool;
 use crate::internal::value::{Value, ValueRef};
+use cfb;
+use std::collections::{BTreeMap, HashMap, btree_map};
 use std::io::{self, Read, Seek, SeekFrom, Write};
 use std::ops::Index;
-use std::rc::Rc;
+use std::sync::Arc;
 
 // ========================================================================= //
 
@@ -25,8 +28,8 @@ impl Table {
         name: String,
         columns: Vec<Column>,
         long_string_refs: bool,
-    ) -> Rc<Table> {
-        Rc::new(Table { name, columns, long_string_refs })
+    ) -> Arc<Table> {
+        Arc::new(Table { name, columns, long_string_refs })
     }
 
     /// Returns the name of the table.
@@ -55,6 +58,26 @@ impl Table {
         &self.columns
     }
 
+    /// Returns true if this is a temporary table (that is, if all of its
+    /// columns are temporary).  Temporary tables exist only in memory and are
+    /// never written to the package file.
+    #[must_use]
+    pub fn is_temporary(&self) -> bool {
+        self.columns.iter().all(Column::is_temporary)
+    }
+
+    /// Returns the number of leading columns that are persisted to the package
+    /// file.  Temporary columns always come after all persistent columns.
+    pub(crate) fn num_persistent_columns(&self) -> usize {
+        self.columns.iter().take_while(|column| !column.is_temporary()).count()
+    }
+
+    /// Returns a copy of this table with the temporary columns removed.
+    pub(crate) fn without_temporary_columns(&self) -> Arc<Table> {
+        let columns = self.columns[..self.num_persistent_columns()].to_vec();
+        Table::new(self.name.clone(), columns, self.long_string_refs)
+    }
+
     /// Returns true if this table has a column with the given name.
     #[must_use]
     pub fn has_column(&self, column_name: &str) -> bool {
@@ -95,11 +118,13 @@ impl Table {
     }
 
     /// Parses row data from the given data source and returns an interator
-    /// over the rows.
+    /// over the rows.  Returns an error if any string reference in the data
+    /// falls outside of the given string pool.
     pub fn read_rows<R: Read + Seek>(
         &self,
         mut reader: R,
-    ) -> io::Result<Vec<Vec<ValueRef>>> {
+        string_pool: &StringPool,
+    ) -> Result<Vec<Vec<ValueRef>>> {
         let data_length = reader.seek(SeekFrom::End(0))?;
         reader.rewind()?;
         let row_size = self
@@ -120,9 +145,21 @@ impl Table {
         for column in &self.columns {
             let coltype = column.coltype();
             for row in &mut rows {
-                row.push(
-                    coltype.read_value(&mut reader, self.long_string_refs)?,
-                );
+                let value_ref =
+                    coltype.read_value(&mut reader, self.long_string_refs)?;
+                if let ValueRef::Str(string_ref) = value_ref
+                    && string_ref.number() as u32 > string_pool.num_strings()
+                {
+                    invalid_data!(
+                        "Column {:?} of table {:?} refers to string {}, but \
+                         the string pool only has {} strings",
+                        column.name(),
+                        self.name,
+                        string_ref.number(),
+                        string_pool.num_strings()
+                    );
+                }
+                row.push(value_ref);
             }
         }
         Ok(rows)
@@ -149,15 +186,122 @@ impl Table {
 
 // ========================================================================= //
 
+/// Row data for a table that is held in memory rather than being read from
+/// and written to its stream on every query.  Temporary tables, and tables
+/// with temporary columns, are always held.
+pub(crate) struct HeldTable {
+    pub(crate) rows: Vec<Vec<ValueRef>>,
+    pub(crate) hold_count: usize,
+}
+
+/// Reads all rows of the given table, either from memory (if the table is
+/// held) or from its stream in the compound file.
+pub(crate) fn load_rows<F: Read + Seek>(
+    comp: &mut cfb::CompoundFile<F>,
+    string_pool: &StringPool,
+    held: &BTreeMap<String, HeldTable>,
+    table: &Table,
+) -> Result<Vec<Vec<ValueRef>>> {
+    if let Some(held_table) = held.get(table.name()) {
+        return Ok(held_table.rows.clone());
+    }
+    let stream_name = table.stream_name();
+    if comp.exists(&stream_name) {
+        let stream = comp.open_stream(&stream_name)?;
+        Ok(table.read_rows(stream, string_pool)?)
+    } else {
+        Ok(Vec::new())
+    }
+}
+
+/// Stores all rows of the given table, either in memory (if the table is held)
+/// or in its stream in the compound file.
+pub(crate) fn store_rows<F: Read + Write + Seek>(
+    comp: &mut cfb::CompoundFile<F>,
+    held: &mut BTreeMap<String, HeldTable>,
+    table: &Table,
+    rows: Vec<Vec<ValueRef>>,
+) -> io::Result<()> {
+    if let Some(held_table) = held.get_mut(table.name()) {
+        held_table.rows = rows;
+        return Ok(());
+    }
+    let stream = comp.create_stream(table.stream_name())?;
+    table.write_rows(stream, rows)
+}
+
+// ========================================================================= //
+
+/// The rows of a table, indexed by their decoded primary key values.  The
+/// index is cached by the package so that repeated lookups by key don't have
+/// to re-read and decode the whole table; it must be discarded whenever the
+/// table's rows or columns change.
+pub(crate) struct KeyIndex {
+    rows: Vec<Vec<ValueRef>>,
+    positions: HashMap<Vec<Value>, Vec<usize>>,
+}
+
+impl KeyIndex {
+    /// Builds an index over the given rows of the table.
+    pub(crate) fn new(
+        table: &Table,
+        string_pool: &StringPool,
+        rows: Vec<Vec<ValueRef>>,
+    ) -> KeyIndex {
+        let key_indices = table.primary_key_indices();
+        let mut positions = HashMap::<Vec<Value>, Vec<usize>>::new();
+        for (position, row) in rows.iter().enumerate() {
+            let key: Vec<Value> = key_indices
+                .iter()
+                .map(|&index| row[index].to_value(string_pool))
+                .collect();
+            positions.entry(key).or_default().push(position);
+        }
+        KeyIndex { rows, positions }
+    }
+
+    /// Returns the rows whose primary key values equal `key` (normally at most
+    /// one, unless the package is malformed).
+    pub(crate) fn get(&self, key: &[Value]) -> Vec<Vec<ValueRef>> {
+        match self.positions.get(key) {
+            Some(positions) => positions
+                .iter()
+                .map(|&position| self.rows[position].clone())
+                .collect(),
+            None => Vec::new(),
+        }
+    }
+}
+
+/// Returns the primary key index for the given table, building it (and
+/// caching it in `indices`) if necessary.
+pub(crate) fn load_key_index<'i, F: Read + Seek>(
+    comp: &mut cfb::CompoundFile<F>,
+    string_pool: &StringPool,
+    held: &BTreeMap<String, HeldTable>,
+    indices: &'i mut BTreeMap<String, KeyIndex>,
+    table: &Table,
+) -> Result<&'i KeyIndex> {
+    match indices.entry(table.name().to_string()) {
+        btree_map::Entry::Occupied(entry) => Ok(entry.into_mut()),
+        btree_map::Entry::Vacant(entry) => {
+            let rows = load_rows(comp, string_pool, held, table)?;
+            Ok(entry.insert(KeyIndex::new(table, string_pool, rows)))
+        }
+    }
+}
+
+// ========================================================================= //
+
 /// One row from a database table.
 #[derive(Clone)]
 pub struct Row {
-    table: Rc<Table>,
+    table: Arc<Table>,
     values: Vec<Value>,
 }
 
 impl Row {
-    pub(crate) fn new(table: Rc<Table>, values: Vec<Value>) -> Row {
+    pub(crate) fn new(table: Arc<Table>, values: Vec<Value>) -> Row {
         debug_assert_eq!(values.len(), table.columns().len());
         Row { table, values }
     }
@@ -240,7 +384,7 @@ impl Index<&str> for Row {
 /// An iterator over the rows in a database table.
 pub struct Rows<'a> {
     string_pool: &'a StringPool,
-    table: Rc<Table>,
+    table: Arc<Table>,
     rows: Vec<Vec<ValueRef>>,
     next_row_index: usize,
 }
@@ -248,7 +392,7 @@ pub struct Rows<'a> {
 impl<'a> Rows<'a> {
     pub fn new(
         string_pool: &'a StringPool,
-        table: Rc<Table>,
+        table: Arc<Table>,
         rows: Vec<Vec<ValueRef>>,
     ) -> Rows<'a> {
         Rows { table, string_pool, rows, next_row_index: 0 }
@@ -262,7 +406,7 @@ impl<'a> Rows<'a> {
 
     pub(crate) fn into_table_and_values(
         self,
-    ) -> (Rc<Table>, Vec<Vec<ValueRef>>) {
+    ) -> (Arc<Table>, Vec<Vec<ValueRef>>) {
         (self.table, self.rows)
     }
 }
@@ -1,3 +1,4 @@
+use crate::internal::error::Result;
 use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
 use std::io::{self, Read, Write};
 use std::time::{Duration, SystemTime, UNIX_EPOCH};
@@ -20,7 +21,7 @@ impl Timestamp {
         system_time_from_timestamp(self.0)
     }
 
-    pub fn read_from<R: Read>(reader: &mut R) -> io::Result<Timestamp> {
+    pub fn read_from<R: Read>(reader: &mut R) -> Result<Timestamp> {
         Ok(Timestamp(reader.read_u64::<LittleEndian>()?))
     }
 
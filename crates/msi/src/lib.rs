@@ -19,6 +19,7 @@ pub mod internal;
 pub use crate::internal::category::Category;
 pub use crate::internal::codepage::CodePage;
 pub use crate::internal::column::{Column, ColumnBuilder, ColumnType};
+pub use crate::internal::error::{MsiError, Result};
 pub use crate::internal::expr::Expr;
 pub use crate::internal::language::Language;
 pub use crate::internal::package::{Package, PackageType, Tables};
@@ -27,19 +28,19 @@ pub use crate::internal::stream::{StreamReader, StreamWriter, Streams};
 pub use crate::internal::summary::SummaryInfo;
 pub use crate::internal::table::{Row, Rows, Table};
 pub use crate::internal::value::Value;
+pub use cfb::Version as CfbVersion;
 use std::fs;
-use std::io;
 use std::path::Path;
 
 // ========================================================================= //
 
 /// Opens an existing MSI file at the given path in read-only mode.
-pub fn open<P: AsRef<Path>>(path: P) -> io::Result<Package<fs::File>> {
+pub fn open<P: AsRef<Path>>(path: P) -> Result<Package<fs::File>> {
     Package::open(fs::File::open(path)?)
 }
 
 /// Opens an existing MSI file at the given path in read-write mode.
-pub fn open_rw<P: AsRef<Path>>(path: P) -> io::Result<Package<fs::File>> {
+pub fn open_rw<P: AsRef<Path>>(path: P) -> Result<Package<fs::File>> {
     Package::open(fs::OpenOptions::new().read(true).write(true).open(path)?)
 }
 
@@ -0,0 +1,272 @@
+#[macro_use]
+mod testutil;
+
+use std::io::{Cursor, ErrorKind};
+use whimsi_msi::{
+    Column, ColumnType, Expr, Insert, Package, PackageType, Select, Update,
+    Value,
+};
+
+// ========================================================================= //
+
+#[test]
+fn add_column_to_nonexistent_table() {
+    let cursor = Cursor::new(Vec::new());
+    let mut package = Package::create(PackageType::Installer, cursor).unwrap();
+    assert_error!(
+        package.add_column("Foobar", Column::build("Foo").nullable().int16()),
+        ErrorKind::NotFound,
+        "Table \"Foobar\" does not exist"
+    );
+}
+
+#[test]
+fn add_duplicate_column() {
+    let cursor = Cursor::new(Vec::new());
+    let mut package = Package::create(PackageType::Installer, cursor).unwrap();
+    let columns = vec![Column::build("Foo").primary_key().int16()];
+    package.create_table("Foobar", columns).unwrap();
+    assert_error!(
+        package.add_column("Foobar", Column::build("Foo").nullable().int16()),
+        ErrorKind::AlreadyExists,
+        "Table \"Foobar\" already has a column named \"Foo\""
+    );
+}
+
+#[test]
+fn add_primary_key_column() {
+    let cursor = Cursor::new(Vec::new());
+    let mut package = Package::create(PackageType::Installer, cursor).unwrap();
+    let columns = vec![Column::build("Foo").primary_key().int16()];
+    package.create_table("Foobar", columns).unwrap();
+    assert_error!(
+        package
+            .add_column("Foobar", Column::build("Bar").primary_key().int16()),
+        ErrorKind::InvalidInput,
+        "Cannot add primary key column \"Bar\" to an existing table"
+    );
+}
+
+#[test]
+fn add_non_nullable_column_to_non_empty_table() {
+    let cursor = Cursor::new(Vec::new());
+    let mut package = Package::create(PackageType::Installer, cursor).unwrap();
+    let columns = vec![Column::build("Foo").primary_key().int16()];
+    package.create_table("Foobar", columns).unwrap();
+    package
+        .insert_rows(Insert::into("Foobar").row(vec![Value::Int(1)]))
+        .unwrap();
+    assert_error!(
+        package.add_column("Foobar", Column::build("Bar").string(16)),
+        ErrorKind::InvalidInput,
+        "Cannot add non-nullable column \"Bar\" to non-empty table \"Foobar\""
+    );
+    assert!(!package.get_table("Foobar").unwrap().has_column("Bar"));
+}
+
+#[test]
+fn add_column_to_existing_rows() {
+    let cursor = Cursor::new(Vec::new());
+    let mut package = Package::create(PackageType::Installer, cursor).unwrap();
+    let columns = vec![
+        Column::build("Number").primary_key().int16(),
+        Column::build("Word").nullable().string(50),
+    ];
+    package.create_table("Numbers", columns).unwrap();
+    package
+        .insert_rows(
+            Insert::into("Numbers")
+                .row(vec![Value::Int(1), Value::from("One")])
+                .row(vec![Value::Int(2), Value::from("Two")]),
+        )
+        .unwrap();
+    package
+        .add_column(
+            "Numbers",
+            Column::build("Roman").nullable().text_string(16),
+        )
+        .unwrap();
+    package
+        .update_rows(
+            Update::table("Numbers")
+                .set("Roman", Value::from("II"))
+                .with(Expr::col("Number").eq(Expr::integer(2))),
+        )
+        .unwrap();
+
+    let cursor = package.into_inner().unwrap();
+    let mut package = Package::open(cursor).unwrap();
+    let table = package.get_table("Numbers").unwrap();
+    assert_eq!(table.columns().len(), 3);
+    let column = table.get_column("Roman").unwrap();
+    assert_eq!(column.coltype(), ColumnType::Str(16));
+    assert!(column.is_nullable());
+    let rows = package.select_rows(Select::table("Numbers")).unwrap();
+    let values: Vec<(i32, String, Value)> = rows
+        .map(|row| {
+            (
+                row[0].as_int().unwrap(),
+                row[1].as_str().unwrap().to_string(),
+                row[2].clone(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        values,
+        vec![
+            (1, "One".to_string(), Value::Null),
+            (2, "Two".to_string(), Value::from("II")),
+        ]
+    );
+}
+
+#[test]
+fn temporary_table_is_not_persisted() {
+    let cursor = Cursor::new(Vec::new());
+    let mut package = Package::create(PackageType::Installer, cursor).unwrap();
+    let columns = vec![
+        Column::build("Key").temporary().primary_key().id_string(72),
+        Column::build("Value").temporary().nullable().string(0),
+    ];
+    package.create_table("Scratch", columns).unwrap();
+    assert!(package.get_table("Scratch").unwrap().is_temporary());
+    assert!(package.is_table_held("Scratch"));
+    package
+        .insert_rows(
+            Insert::into("Scratch")
+                .row(vec![Value::from("Foo"), Value::from("Bar")]),
+        )
+        .unwrap();
+    let rows = package.select_rows(Select::table("Scratch")).unwrap();
+    assert_eq!(rows.len(), 1);
+
+    let cursor = package.into_inner().unwrap();
+    let package = Package::open(cursor).unwrap();
+    assert!(!package.has_table("Scratch"));
+    assert!(!package.has_stream("Scratch"));
+}
+
+#[test]
+fn free_temporary_table() {
+    let cursor = Cursor::new(Vec::new());
+    let mut package = Package::create(PackageType::Installer, cursor).unwrap();
+    let columns = vec![Column::build("Key").temporary().primary_key().int16()];
+    package.create_table("Scratch", columns).unwrap();
+    package.hold_table("Scratch").unwrap();
+    package.free_table("Scratch").unwrap();
+    assert!(package.has_table("Scratch"));
+    package.free_table("Scratch").unwrap();
+    assert!(!package.has_table("Scratch"));
+    assert_error!(
+        package.free_table("Scratch"),
+        ErrorKind::InvalidInput,
+        "Table \"Scratch\" is not held"
+    );
+}
+
+#[test]
+fn temporary_column_is_not_persisted() {
+    let cursor = Cursor::new(Vec::new());
+    let mut package = Package::create(PackageType::Installer, cursor).unwrap();
+    let columns = vec![
+        Column::build("Number").primary_key().int16(),
+        Column::build("Word").nullable().string(50),
+    ];
+    package.create_table("Numbers", columns).unwrap();
+    package
+        .insert_rows(
+            Insert::into("Numbers")
+                .row(vec![Value::Int(1), Value::from("One")]),
+        )
+        .unwrap();
+    package
+        .add_column(
+            "Numbers",
+            Column::build("Scratch").temporary().nullable().string(0),
+        )
+        .unwrap();
+    package
+        .add_column("Numbers", Column::build("Roman").nullable().string(8))
+        .unwrap();
+    let table = package.get_table("Numbers").unwrap();
+    let names: Vec<&str> = table.columns().iter().map(Column::name).collect();
+    assert_eq!(names, vec!["Number", "Word", "Roman", "Scratch"]);
+    package
+        .update_rows(
+            Update::table("Numbers")
+                .set("Scratch", Value::from("temp"))
+                .set("Roman", Value::from("I")),
+        )
+        .unwrap();
+    package.flush().unwrap();
+    let rows = package.select_rows(Select::table("Numbers")).unwrap();
+    let row = rows.last().unwrap();
+    assert_eq!(row["Scratch"], Value::from("temp"));
+
+    let cursor = package.into_inner().unwrap();
+    let mut package = Package::open(cursor).unwrap();
+    let table = package.get_table("Numbers").unwrap();
+    assert!(!table.has_column("Scratch"));
+    let rows = package.select_rows(Select::table("Numbers")).unwrap();
+    let values: Vec<Vec<Value>> = rows
+        .map(|row| (0..row.len()).map(|index| row[index].clone()).collect())
+        .collect();
+    assert_eq!(
+        values,
+        vec![vec![Value::Int(1), Value::from("One"), Value::from("I")]]
+    );
+}
+
+#[test]
+fn free_table_with_temporary_column() {
+    let cursor = Cursor::new(Vec::new());
+    let mut package = Package::create(PackageType::Installer, cursor).unwrap();
+    let columns = vec![Column::build("Number").primary_key().int16()];
+    package.create_table("Numbers", columns).unwrap();
+    package
+        .insert_rows(Insert::into("Numbers").row(vec![Value::Int(7)]))
+        .unwrap();
+    package
+        .add_column(
+            "Numbers",
+            Column::build("Scratch").temporary().nullable().int32(),
+        )
+        .unwrap();
+    assert!(package.is_table_held("Numbers"));
+    package.free_table("Numbers").unwrap();
+    assert!(!package.is_table_held("Numbers"));
+    assert!(!package.get_table("Numbers").unwrap().has_column("Scratch"));
+    let rows = package.select_rows(Select::table("Numbers")).unwrap();
+    assert_eq!(rows.len(), 1);
+}
+
+#[test]
+fn add_persistent_column_to_temporary_table() {
+    let cursor = Cursor::new(Vec::new());
+    let mut package = Package::create(PackageType::Installer, cursor).unwrap();
+    let columns = vec![Column::build("Key").temporary().primary_key().int16()];
+    package.create_table("Scratch", columns).unwrap();
+    assert_error!(
+        package.add_column("Scratch", Column::build("Foo").nullable().int16()),
+        ErrorKind::InvalidInput,
+        "Cannot add persistent column \"Foo\" to temporary table \"Scratch\""
+    );
+}
+
+#[test]
+fn create_table_with_temporary_primary_key() {
+    let cursor = Cursor::new(Vec::new());
+    let mut package = Package::create(PackageType::Installer, cursor).unwrap();
+    let columns = vec![
+        Column::build("Foo").primary_key().int16(),
+        Column::build("Bar").temporary().primary_key().int16(),
+    ];
+    assert_error!(
+        package.create_table("Foobar", columns),
+        ErrorKind::InvalidInput,
+        "Cannot use temporary column \"Bar\" as a primary key of a \
+         persistent table"
+    );
+}
+
+// ========================================================================= //
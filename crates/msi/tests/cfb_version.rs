@@ -0,0 +1,75 @@
+use std::io::{Cursor, Read, Write};
+use whimsi_msi::{
+    CfbVersion, Column, Insert, Package, PackageType, Select, Value,
+};
+
+// ========================================================================= //
+
+// Offsets into the compound file header, per [MS-CFB] section 2.2.
+const MAJOR_VERSION_OFFSET: usize = 0x1a;
+const SECTOR_SHIFT_OFFSET: usize = 0x1e;
+
+fn header_u16(data: &[u8], offset: usize) -> u16 {
+    u16::from_le_bytes([data[offset], data[offset + 1]])
+}
+
+#[test]
+fn create_uses_version_3_by_default() {
+    let cursor = Cursor::new(Vec::new());
+    let package = Package::create(PackageType::Installer, cursor).unwrap();
+    assert_eq!(package.cfb_version(), CfbVersion::V3);
+    let data = package.into_inner().unwrap().into_inner();
+    assert_eq!(header_u16(&data, MAJOR_VERSION_OFFSET), 3);
+    assert_eq!(header_u16(&data, SECTOR_SHIFT_OFFSET), 9);
+}
+
+#[test]
+fn version_4_package_round_trip() {
+    let stream_data: Vec<u8> =
+        (0..100_000u32).map(|number| (number % 251) as u8).collect();
+
+    let cursor = Cursor::new(Vec::new());
+    let mut package = Package::create_with_version(
+        PackageType::Installer,
+        CfbVersion::V4,
+        cursor,
+    )
+    .unwrap();
+    assert_eq!(package.cfb_version(), CfbVersion::V4);
+    package.summary_info_mut().set_author("Jane Doe".to_string());
+    let columns = vec![
+        Column::build("Number").primary_key().int16(),
+        Column::build("Word").nullable().string(50),
+    ];
+    package.create_table("Numbers", columns).unwrap();
+    let query = Insert::into("Numbers")
+        .row(vec![Value::Int(1), Value::from("One")])
+        .row(vec![Value::Int(2), Value::from("Two")]);
+    package.insert_rows(query).unwrap();
+    {
+        let mut writer = package.write_stream("Data.cab").unwrap();
+        writer.write_all(&stream_data).unwrap();
+    }
+    let data = package.into_inner().unwrap().into_inner();
+    assert_eq!(header_u16(&data, MAJOR_VERSION_OFFSET), 4);
+    assert_eq!(header_u16(&data, SECTOR_SHIFT_OFFSET), 12);
+    assert_eq!(data.len() % 4096, 0);
+
+    let mut package = Package::open(Cursor::new(data)).unwrap();
+    assert_eq!(package.cfb_version(), CfbVersion::V4);
+    assert_eq!(package.package_type(), PackageType::Installer);
+    assert_eq!(package.summary_info().author(), Some("Jane Doe"));
+    let rows: Vec<_> =
+        package.select_rows(Select::table("Numbers")).unwrap().collect();
+    assert_eq!(rows.len(), 2);
+    assert_eq!(rows[1]["Word"], Value::from("Two"));
+    let mut read_data = Vec::new();
+    package
+        .read_stream("Data.cab")
+        .unwrap()
+        .read_to_end(&mut read_data)
+        .unwrap();
+    assert_eq!(read_data, stream_data);
+}
+
+// ========================================================================= //
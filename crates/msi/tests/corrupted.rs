@@ -0,0 +1,182 @@
+use std::fs;
+use std::io::{Cursor, ErrorKind, Read};
+use std::path::PathBuf;
+use whimsi_msi::{MsiError, Package, Select};
+
+// ========================================================================= //
+
+/// Opens the named package from the corrupted corpus and reads everything in
+/// it: summary info, every row of every table, and every stream.
+fn read_everything(name: &str) -> whimsi_msi::Result<()> {
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("corrupted")
+        .join(name);
+    let data = fs::read(path).unwrap();
+    let mut package = Package::open(Cursor::new(data))?;
+    let summary_info = package.summary_info();
+    let _ = summary_info.title();
+    let _ = summary_info.languages();
+    let _ = summary_info.creation_time();
+    let table_names: Vec<String> =
+        package.tables().map(|table| table.name().to_string()).collect();
+    for table_name in table_names {
+        for row in package.select_rows(Select::table(table_name))? {
+            for index in 0..row.len() {
+                let _ = row[index].clone();
+            }
+        }
+    }
+    let stream_names: Vec<String> = package.streams().collect();
+    for stream_name in stream_names {
+        let mut data = Vec::new();
+        package.read_stream(&stream_name)?.read_to_end(&mut data)?;
+    }
+    Ok(())
+}
+
+macro_rules! assert_corrupted {
+    ($name:expr, $kind:expr) => {
+        match read_everything($name) {
+            Ok(()) => panic!("Expected {} to fail to read", $name),
+            Err(error) => assert_eq!(
+                error.kind(),
+                $kind,
+                "Unexpected error for {}: {}",
+                $name,
+                error
+            ),
+        }
+    };
+    ($name:expr, $kind:expr, $description:expr) => {
+        match read_everything($name) {
+            Ok(()) => panic!("Expected {} to fail to read", $name),
+            Err(error) => {
+                assert_eq!(error.kind(), $kind, "{}: {}", $name, error);
+                assert_eq!(error.to_string(), $description);
+            }
+        }
+    };
+}
+
+// ========================================================================= //
+
+#[test]
+fn every_corrupted_package_fails_cleanly() {
+    let dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("corrupted");
+    for entry in fs::read_dir(dir).unwrap() {
+        let name = entry.unwrap().file_name().into_string().unwrap();
+        assert!(read_everything(&name).is_err(), "{} read successfully", name);
+    }
+}
+
+#[test]
+fn garbage() {
+    assert_corrupted!("garbage.msi", ErrorKind::InvalidData);
+}
+
+#[test]
+fn truncated_file() {
+    assert_corrupted!("truncated_file.msi", ErrorKind::InvalidData);
+}
+
+#[test]
+fn unknown_codepage() {
+    assert_corrupted!(
+        "unknown_codepage.msi",
+        ErrorKind::InvalidData,
+        "Unknown codepage for string pool (12345)"
+    );
+}
+
+#[test]
+fn huge_string_length() {
+    assert_corrupted!(
+        "huge_string_length.msi",
+        ErrorKind::InvalidData,
+        "String data is truncated (string 23 should have 4294967295 bytes, \
+         but only 0 remain)"
+    );
+}
+
+#[test]
+fn truncated_string_data() {
+    assert_corrupted!(
+        "truncated_string_data.msi",
+        ErrorKind::InvalidData,
+        "String data is truncated (string 16 should have 245 bytes, but only \
+         86 remain)"
+    );
+}
+
+#[test]
+fn corrupted_string_pool_is_invalid_data() {
+    for name in [
+        "unknown_codepage.msi",
+        "huge_string_length.msi",
+        "truncated_string_data.msi",
+    ] {
+        let error = read_everything(name).unwrap_err();
+        assert!(
+            matches!(error, MsiError::InvalidData(_)),
+            "{}: {:?}",
+            name,
+            error
+        );
+    }
+}
+
+#[test]
+fn out_of_range_string_ref() {
+    assert_corrupted!(
+        "out_of_range_string_ref.msi",
+        ErrorKind::InvalidData,
+        "Column \"Word\" of table \"Numbers\" refers to string 32767, but \
+         the string pool only has 22 strings"
+    );
+}
+
+#[test]
+fn null_table_name() {
+    assert_corrupted!(
+        "null_table_name.msi",
+        ErrorKind::InvalidData,
+        "Null table name in \"_Tables\" table"
+    );
+}
+
+#[test]
+fn null_column_name() {
+    assert_corrupted!(
+        "null_column_name.msi",
+        ErrorKind::InvalidData,
+        "Null key or name in \"_Columns\" table"
+    );
+}
+
+#[test]
+fn incomplete_columns() {
+    assert_corrupted!(
+        "incomplete_columns.msi",
+        ErrorKind::InvalidData,
+        "Table \"Numbers\" does not have a complete set of columns"
+    );
+}
+
+#[test]
+fn huge_summary_string() {
+    assert_corrupted!(
+        "huge_summary_string.msi",
+        ErrorKind::InvalidData,
+        "Property set string is truncated"
+    );
+}
+
+#[test]
+fn truncated_summary_info() {
+    assert_corrupted!("truncated_summary_info.msi", ErrorKind::UnexpectedEof);
+}
+
+// ========================================================================= //
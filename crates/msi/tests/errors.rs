@@ -0,0 +1,88 @@
+use std::io::{self, Cursor};
+use whimsi_msi::{
+    Column, ColumnType, Insert, MsiError, Package, PackageType, Select, Update,
+    Value,
+};
+
+// ========================================================================= //
+
+fn numbers_package() -> Package<Cursor<Vec<u8>>> {
+    let cursor = Cursor::new(Vec::new());
+    let mut package = Package::create(PackageType::Installer, cursor).unwrap();
+    let columns = vec![
+        Column::build("Number").primary_key().range(0, 100).int16(),
+        Column::build("Word").nullable().string(50),
+    ];
+    package.create_table("Numbers", columns).unwrap();
+    package
+        .insert_rows(
+            Insert::into("Numbers")
+                .row(vec![Value::Int(1), Value::from("One")]),
+        )
+        .unwrap();
+    package
+}
+
+#[test]
+fn table_not_found() {
+    let mut package = numbers_package();
+    match package.select_rows(Select::table("Letters")) {
+        Err(MsiError::TableNotFound { table }) => assert_eq!(table, "Letters"),
+        other => panic!("Unexpected result: {:?}", other.map(|_| ())),
+    }
+}
+
+#[test]
+fn column_not_found() {
+    let mut package = numbers_package();
+    let query = Update::table("Numbers").set("Roman", Value::from("I"));
+    match package.update_rows(query) {
+        Err(MsiError::ColumnNotFound { table, column }) => {
+            assert_eq!(table, "Numbers");
+            assert_eq!(column, "Roman");
+        }
+        other => panic!("Unexpected result: {other:?}"),
+    }
+}
+
+#[test]
+fn duplicate_key() {
+    let mut package = numbers_package();
+    let query =
+        Insert::into("Numbers").row(vec![Value::Int(1), Value::from("Uno")]);
+    match package.insert_rows(query) {
+        Err(MsiError::DuplicateKey { table, key }) => {
+            assert_eq!(table, "Numbers");
+            assert_eq!(key, vec![Value::Int(1)]);
+        }
+        other => panic!("Unexpected result: {other:?}"),
+    }
+}
+
+#[test]
+fn invalid_value() {
+    let mut package = numbers_package();
+    let query = Insert::into("Numbers").row(vec![Value::Int(101), Value::Null]);
+    match package.insert_rows(query) {
+        Err(MsiError::InvalidValue { table, column, column_type, value }) => {
+            assert_eq!(table, "Numbers");
+            assert_eq!(column, "Number");
+            assert_eq!(column_type, ColumnType::Int16);
+            assert_eq!(value, Value::Int(101));
+        }
+        other => panic!("Unexpected result: {other:?}"),
+    }
+}
+
+#[test]
+fn convert_to_io_error() {
+    let mut package = numbers_package();
+    let error: io::Error =
+        package.select_rows(Select::table("Letters")).err().unwrap().into();
+    assert_eq!(error.kind(), io::ErrorKind::NotFound);
+    assert_eq!(error.to_string(), "Table \"Letters\" does not exist");
+    let inner = MsiError::from_io_error(&error).unwrap();
+    assert_eq!(inner.table(), Some("Letters"));
+}
+
+// ========================================================================= //
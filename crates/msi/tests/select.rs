@@ -2,7 +2,9 @@
 mod testutil;
 
 use std::io::{Cursor, ErrorKind};
-use whimsi_msi::{Column, Expr, Insert, Package, PackageType, Select, Value};
+use whimsi_msi::{
+    Column, Delete, Expr, Insert, Package, PackageType, Select, Update, Value,
+};
 
 //===========================================================================//
 
@@ -203,4 +205,97 @@ fn nested_inner_join() {
     assert_eq!(values, vec![(6, 3, 2), (7, 4, 1)]);
 }
 
+#[test]
+fn select_by_primary_key() {
+    let cursor = Cursor::new(Vec::new());
+    let mut package = Package::create(PackageType::Installer, cursor).unwrap();
+    let columns = vec![
+        Column::build("Component").primary_key().id_string(72),
+        Column::build("Feature").primary_key().id_string(38),
+        Column::build("Weight").int16(),
+    ];
+    package.create_table("Mapping", columns).unwrap();
+    let query = Insert::into("Mapping")
+        .row(vec![Value::from("Foo"), Value::from("Main"), Value::Int(1)])
+        .row(vec![Value::from("Foo"), Value::from("Extra"), Value::Int(2)])
+        .row(vec![Value::from("Bar"), Value::from("Main"), Value::Int(3)]);
+    package.insert_rows(query).unwrap();
+
+    let query = Select::table("Mapping")
+        .with(Expr::col("Feature").eq(Expr::string("Extra")))
+        .with(Expr::string("Foo").eq(Expr::col("Component")))
+        .columns(&["Weight"]);
+    let rows = package.select_rows(query).unwrap();
+    let weights: Vec<i32> =
+        rows.map(|row| row["Weight"].as_int().unwrap()).collect();
+    assert_eq!(weights, vec![2]);
+
+    // Other terms of the condition still apply to the row found by key:
+    let query = Select::table("Mapping")
+        .with(Expr::col("Component").eq(Expr::string("Bar")))
+        .with(Expr::col("Feature").eq(Expr::string("Main")))
+        .with(Expr::col("Weight").gt(Expr::integer(5)));
+    assert_eq!(package.select_rows(query).unwrap().len(), 0);
+
+    // The index must not return stale rows after the table is modified:
+    let query = Update::table("Mapping")
+        .set("Weight", Value::Int(7))
+        .with(Expr::col("Component").eq(Expr::string("Bar")));
+    package.update_rows(query).unwrap();
+    let query = Select::table("Mapping")
+        .with(Expr::col("Component").eq(Expr::string("Bar")))
+        .with(Expr::col("Feature").eq(Expr::string("Main")));
+    let rows: Vec<_> = package.select_rows(query).unwrap().collect();
+    assert_eq!(rows.len(), 1);
+    assert_eq!(rows[0]["Weight"], Value::Int(7));
+}
+
+#[test]
+fn get_row_by_primary_key() {
+    let cursor = Cursor::new(Vec::new());
+    let mut package = Package::create(PackageType::Installer, cursor).unwrap();
+    let columns = vec![
+        Column::build("Number").primary_key().int16(),
+        Column::build("Word").nullable().string(50),
+    ];
+    package.create_table("Numbers", columns).unwrap();
+    let query = Insert::into("Numbers")
+        .row(vec![Value::Int(1), Value::from("One")])
+        .row(vec![Value::Int(2), Value::from("Two")]);
+    package.insert_rows(query).unwrap();
+
+    let row = package.get_row("Numbers", &[Value::Int(2)]).unwrap().unwrap();
+    assert_eq!(row["Word"], Value::from("Two"));
+    assert!(package.get_row("Numbers", &[Value::Int(3)]).unwrap().is_none());
+
+    let query = Insert::into("Numbers").row(vec![Value::Int(3), Value::Null]);
+    package.insert_rows(query).unwrap();
+    let row = package.get_row("Numbers", &[Value::Int(3)]).unwrap().unwrap();
+    assert_eq!(row["Word"], Value::Null);
+
+    let query =
+        Delete::from("Numbers").with(Expr::col("Number").eq(Expr::integer(1)));
+    package.delete_rows(query).unwrap();
+    assert!(package.get_row("Numbers", &[Value::Int(1)]).unwrap().is_none());
+}
+
+#[test]
+fn get_row_with_invalid_key() {
+    let cursor = Cursor::new(Vec::new());
+    let mut package = Package::create(PackageType::Installer, cursor).unwrap();
+    let columns = vec![Column::build("Number").primary_key().int16()];
+    package.create_table("Numbers", columns).unwrap();
+    assert_error!(
+        package.get_row("Letters", &[Value::from("A")]),
+        ErrorKind::NotFound,
+        "Table \"Letters\" does not exist"
+    );
+    assert_error!(
+        package.get_row("Numbers", &[Value::Int(1), Value::Int(2)]),
+        ErrorKind::InvalidInput,
+        "Table \"Numbers\" has 1 primary key column(s), but 2 key value(s) \
+         were given"
+    );
+}
+
 //===========================================================================//
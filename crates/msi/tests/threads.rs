@@ -0,0 +1,76 @@
+use std::fs::File;
+use std::io::Cursor;
+use std::sync::{Arc, Mutex};
+use std::thread;
+use whimsi_msi::{
+    Column, Insert, MsiError, Package, PackageType, Row, Rows, Select, Table,
+    Value,
+};
+
+// ========================================================================= //
+
+fn assert_send_sync<T: Send + Sync>() {}
+
+fn numbers_package() -> Package<Cursor<Vec<u8>>> {
+    let cursor = Cursor::new(Vec::new());
+    let mut package = Package::create(PackageType::Installer, cursor).unwrap();
+    let columns = vec![
+        Column::build("Number").primary_key().int16(),
+        Column::build("Word").nullable().string(50),
+    ];
+    package.create_table("Numbers", columns).unwrap();
+    package
+        .insert_rows(
+            Insert::into("Numbers")
+                .row(vec![Value::Int(1), Value::from("One")])
+                .row(vec![Value::Int(2), Value::from("Two")]),
+        )
+        .unwrap();
+    package
+}
+
+#[test]
+fn types_are_send_and_sync() {
+    assert_send_sync::<Package<Cursor<Vec<u8>>>>();
+    assert_send_sync::<Package<File>>();
+    assert_send_sync::<Table>();
+    assert_send_sync::<Row>();
+    assert_send_sync::<Rows<'static>>();
+    assert_send_sync::<MsiError>();
+}
+
+#[test]
+fn build_package_on_another_thread() {
+    let cursor = thread::spawn(|| numbers_package().into_inner().unwrap())
+        .join()
+        .unwrap();
+    let mut package = Package::open(cursor).unwrap();
+    let rows = package.select_rows(Select::table("Numbers")).unwrap();
+    assert_eq!(rows.len(), 2);
+}
+
+#[test]
+fn share_package_across_threads() {
+    let cursor = numbers_package().into_inner().unwrap();
+    let package = Arc::new(Mutex::new(Package::open(cursor).unwrap()));
+    let handles: Vec<_> = (0..4)
+        .map(|_| {
+            let package = Arc::clone(&package);
+            thread::spawn(move || {
+                let mut package = package.lock().unwrap();
+                let rows: Vec<Row> = package
+                    .select_rows(Select::table("Numbers"))
+                    .unwrap()
+                    .collect();
+                rows
+            })
+        })
+        .collect();
+    for handle in handles {
+        let rows = handle.join().unwrap();
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[1]["Word"], Value::from("Two"));
+    }
+}
+
+// ========================================================================= //
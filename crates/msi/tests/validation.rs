@@ -25,13 +25,15 @@ fn int_column_value_range() {
     assert_error!(
         package.insert_rows(query),
         ErrorKind::InvalidInput,
-        "-7 is not a valid value for column \"Number\""
+        "-7 is not a valid value for column \"Number\" of type SMALLINT in \
+         table \"Numbers\""
     );
     let query = Insert::into("Numbers").row(vec![Value::Int(101)]);
     assert_error!(
         package.insert_rows(query),
         ErrorKind::InvalidInput,
-        "101 is not a valid value for column \"Number\""
+        "101 is not a valid value for column \"Number\" of type SMALLINT in \
+         table \"Numbers\""
     );
     let query = Insert::into("Numbers").row(vec![Value::Int(100)]);
     package.insert_rows(query).unwrap();
@@ -58,7 +60,8 @@ fn string_column_category() {
     assert_error!(
         package.insert_rows(query),
         ErrorKind::InvalidInput,
-        "\"$99\" is not a valid value for column \"Property\""
+        "\"$99\" is not a valid value for column \"Property\" of type \
+         VARCHAR(32) in table \"Properties\""
     );
     let query = Insert::into("Properties").row(vec![Value::from("%Foo")]);
     package.insert_rows(query).unwrap();
@@ -99,7 +102,8 @@ fn string_column_enum_values() {
     assert_error!(
         package.insert_rows(query),
         ErrorKind::InvalidInput,
-        "\"Sit\" is not a valid value for column \"Day\""
+        "\"Sit\" is not a valid value for column \"Day\" of type VARCHAR(3) \
+         in table \"Days\""
     );
     let query = Insert::into("Days").row(vec![Value::from("Sat")]);
     package.insert_rows(query).unwrap();